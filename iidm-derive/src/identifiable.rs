@@ -32,62 +32,83 @@ pub fn impl_identifiable_trait(ast: DeriveInput) -> TokenStream {
         }
     };
 
-    expanded.into()
+    expanded
 }
 
 pub fn generate_register_impl(data: &Data) -> TokenStream {
     match data {
-        Data::Struct(data_struct) => {
-            match &data_struct.fields {
-                Fields::Named(fields) => {
-                    let field_registers = fields.named.iter().map(|field| {
-                        let field_name = &field.ident;
-                        let field_type = &field.ty;
-
-                        // Check if field type implements Identifiable
-                        if is_identifiable_type(field_type) {
-                            // Handle Vec<T> where T: Identifiable
-                            if let Type::Path(TypePath { path, .. }) = field_type {
-                                if path
-                                    .segments
-                                    .last()
-                                    .map(|s| s.ident == "Vec")
-                                    .unwrap_or(false)
-                                {
-                                    return quote! {
-                                        for item in &self.#field_name {
-                                            item.register(world, schedule);
-                                        }
-                                    };
-                                }
-                            }
-
-                            // Handle single Identifiable field
-                            quote! {
-                                self.#field_name.register(world, schedule);
-                            }
-                        } else {
-                            quote! {}
-                        }
-                    });
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields) => {
+                let field_registers = fields.named.iter().map(|field| {
+                    let field_name = &field.ident;
+                    generate_field_register(&field.ty, quote! { self.#field_name })
+                });
 
-                    quote! {
-                        #(#field_registers)*
-                    }
+                quote! {
+                    #(#field_registers)*
                 }
-                _ => quote! {},
             }
-        }
+            _ => quote! {},
+        },
         _ => quote! {},
     }
 }
 
+fn generate_field_register(ty: &Type, value: TokenStream) -> TokenStream {
+    // Check if field type implements Identifiable
+    if !is_identifiable_type(ty) {
+        return quote! {};
+    }
+
+    // Handle Vec<T> and Option<T> where T: Identifiable
+    if let Some((wrapper, inner_type)) = wrapped_type(ty) {
+        let inner_register = generate_field_register(inner_type, quote! { item });
+        return if wrapper == "Vec" {
+            quote! {
+                for item in #value.iter() {
+                    #inner_register
+                }
+            }
+        } else {
+            quote! {
+                if let Some(item) = #value.as_ref() {
+                    #inner_register
+                }
+            }
+        };
+    }
+
+    // Handle single Identifiable field
+    quote! {
+        #value.register(world, schedule);
+    }
+}
+
+// Returns the wrapper name and the inner type of a Vec<T> or an Option<T>
+fn wrapped_type(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let last_segment = path.segments.last()?;
+    let wrapper = last_segment.ident.to_string();
+    if wrapper != "Vec" && wrapper != "Option" {
+        return None;
+    }
+    match &last_segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner_type)) => Some((wrapper, inner_type)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn is_identifiable_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty {
         let segments = &type_path.path.segments;
         if let Some(last_segment) = segments.last() {
-            // Si c'est un Vec, regarder le type à l'intérieur
-            if last_segment.ident == "Vec" {
+            // Si c'est un Vec ou une Option, regarder le type à l'intérieur
+            if last_segment.ident == "Vec" || last_segment.ident == "Option" {
                 if let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments {
                     if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
                         return is_identifiable_type(inner_type);
@@ -104,6 +125,7 @@ fn is_identifiable_type(ty: &Type) -> bool {
                     | "VoltageLevel"
                     | "Generator"
                     | "Load"
+                    | "Battery"
                     | "Line"
                     | "Switch"
                     | "ShuntCompensator"
@@ -115,6 +137,8 @@ fn is_identifiable_type(ty: &Type) -> bool {
                     | "BusbarSection"
                     | "TwoWindingsTransformer"
                     | "ThreeWindingsTransformer"
+                    | "Area"
+                    | "ExtensionHolder"
             )
         } else {
             false
@@ -165,6 +189,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_identifiable_type_options() {
+        let type_option_vec: Type = parse_quote!(Option<Vec<Generator>>);
+        assert!(
+            is_identifiable_type(&type_option_vec),
+            "Option<Vec<Generator>> devrait être identifiable"
+        );
+
+        let type_option: Type = parse_quote!(Option<NodeBreakerTopology>);
+        assert!(
+            !is_identifiable_type(&type_option),
+            "Option<NodeBreakerTopology> ne devrait pas être identifiable"
+        );
+    }
+
+    #[test]
+    fn test_generate_register_option_vec() {
+        let generated = generate_field_register(
+            &parse_quote!(Option<Vec<Generator>>),
+            quote! { self.generators },
+        );
+        let expected = quote! {
+            if let Some(item) = self.generators.as_ref() {
+                for item in item.iter() {
+                    item.register(world, schedule);
+                }
+            }
+        };
+        assert_eq!(generated.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_generate_register_option() {
+        let generated = generate_field_register(&parse_quote!(Option<Area>), quote! { self.area });
+        let expected = quote! {
+            if let Some(item) = self.area.as_ref() {
+                item.register(world, schedule);
+            }
+        };
+        assert_eq!(generated.to_string(), expected.to_string());
+
+        let generated =
+            generate_field_register(&parse_quote!(Option<String>), quote! { self.name });
+        assert!(generated.is_empty());
+    }
+
     #[test]
     fn test_is_identifiable_type_all_types() {
         // Test individuellement chaque type
@@ -173,6 +243,7 @@ mod tests {
             (parse_quote!(VoltageLevel), true),
             (parse_quote!(Generator), true),
             (parse_quote!(Load), true),
+            (parse_quote!(Battery), true),
             (parse_quote!(Line), true),
             (parse_quote!(Switch), true),
             (parse_quote!(ShuntCompensator), true),
@@ -184,6 +255,8 @@ mod tests {
             (parse_quote!(BusbarSection), true),
             (parse_quote!(TwoWindingsTransformer), true),
             (parse_quote!(ThreeWindingsTransformer), true),
+            (parse_quote!(Area), true),
+            (parse_quote!(ExtensionHolder), true),
             (parse_quote!(String), false),
             (parse_quote!(i32), false),
         ];
@@ -15,7 +15,7 @@ pub fn reflective_identifiable_macro(item: TokenStream) -> TokenStream {
     impl_identifiable_trait(ast).into()
 }
 
-#[proc_macro_derive(Updatable)]
+#[proc_macro_derive(Updatable, attributes(updatable))]
 pub fn derive_updatable(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse(item).unwrap();
@@ -7,12 +7,15 @@ use syn::{Data, DeriveInput, Fields, LitStr};
 fn extract_serde_rename(attrs: &[syn::Attribute], default_name: &str) -> String {
     attrs
         .iter()
-        .find(|attr| attr.path().is_ident("serde"))
-        .and_then(|attr| {
+        .filter(|attr| attr.path().is_ident("serde"))
+        .find_map(|attr| {
             let mut rename_value = None;
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("rename") {
                     rename_value = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip values of other serde options (default = "...", skip_serializing_if = "...")
+                    meta.value()?.parse::<syn::Expr>()?;
                 }
                 Ok(())
             })
@@ -22,6 +25,59 @@ fn extract_serde_rename(attrs: &[syn::Attribute], default_name: &str) -> String
         .unwrap_or_else(|| default_name.to_string())
 }
 
+// Unmodelled attributes kept for export are not part of the updates
+fn is_unknown_fields(field: &syn::Field) -> bool {
+    match &field.ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "UnknownFields"),
+        _ => false,
+    }
+}
+
+// Utility to extract a path declared by #[updatable(key = "path")], such as the update check
+// of a struct or the deserializer of a field in the Updater
+fn extract_updatable_path(attrs: &[syn::Attribute], key: &str) -> Option<LitStr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("updatable"))
+        .find_map(|attr| {
+            let mut path = None;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(key) {
+                    path = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<LitStr>()?;
+                }
+                Ok(())
+            })
+            .ok();
+            path
+        })
+}
+
+// Flattened fields are read from the attributes of their parent
+fn is_serde_flatten(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .any(|attr| {
+            let mut flatten = false;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("flatten") {
+                    flatten = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            })
+            .ok();
+            flatten
+        })
+}
+
 // Utility to extract named fields from a struct
 fn extract_named_fields(
     ast: &DeriveInput,
@@ -42,7 +98,7 @@ pub fn impl_error_for_struct(ast: &DeriveInput) -> TokenStream {
 
     let fields = extract_named_fields(ast);
 
-    let error_variants = fields.iter().map(|f| {
+    let error_variants = fields.iter().filter(|f| !is_unknown_fields(f)).map(|f| {
         let field_name = &f.ident;
         let variant_name = field_name.as_ref().unwrap().to_string();
         let rename = extract_serde_rename(&f.attrs, &variant_name);
@@ -85,32 +141,89 @@ pub fn impl_updatable_trait(ast: DeriveInput) -> TokenStream {
     // Extract fields
     let fields = extract_named_fields(&ast);
 
-    // Filter to exclude 'id' field
+    // Filter to exclude 'id' and unknown fields
     let filtered_fields = fields
         .iter()
-        .filter(|f| f.ident.as_ref().map_or(true, |id| id != "id"));
+        .filter(|f| f.ident.as_ref().is_none_or(|id| id != "id"))
+        .filter(|f| !is_unknown_fields(f));
 
     // Generate field definitions for the Updater
+    // Only the serde rename and flatten are kept: other options (default, skip_serializing_if...)
+    // target the original type and not its Option wrapper, which has its own deserializer.
+    // Flattened fields are left out of the schema, where their alternatives would be required,
+    // their keys are checked by fields_json and their deserializer
     let field_defs = filtered_fields.clone().map(|f| {
         let name = &f.ident;
         let ty = &f.ty;
-        let attrs = &f.attrs;
+        let attrs = f
+            .attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("serde") && !attr.path().is_ident("updatable"));
+        let field_name = name.as_ref().unwrap().to_string();
+        let rename = extract_serde_rename(&f.attrs, &field_name);
+        let serde_attr = if is_serde_flatten(&f.attrs) {
+            quote! {
+                #[serde(flatten)]
+                #[schemars(skip)]
+            }
+        } else {
+            quote! { #[serde(rename = #rename)] }
+        };
+        let deserialize_with = extract_updatable_path(&f.attrs, "deserialize_with")
+            .map(|path| quote! { #[serde(deserialize_with = #path)] });
         quote! {
             #(#attrs)*
+            #serde_attr
+            #deserialize_with
             pub #name: Option<#ty>
         }
     });
 
-    // Generate implementation for fields_json
+    // Generate implementation for fields_json, flattened fields expand to their own keys
     let fields_json_impl = filtered_fields.clone().map(|f| {
         let field_name = f.ident.as_ref().unwrap().to_string();
         let rename = extract_serde_rename(&f.attrs, &field_name);
+        let ty = &f.ty;
 
-        quote! {
-            #rename.to_string()
+        if is_serde_flatten(&f.attrs) {
+            quote! {
+                fields.extend(crate::libs::json::schema_keys::<#ty>());
+            }
+        } else {
+            quote! {
+                fields.push(#rename.to_string());
+            }
         }
     });
 
+    // Flattened fields are left out of the Updater schema, their nested keys are checked
+    // against the schema of their own type
+    let nested_keys_impl = filtered_fields
+        .clone()
+        .filter(|f| is_serde_flatten(&f.attrs))
+        .map(|f| {
+            let ty = &f.ty;
+            quote! {
+                crate::libs::json::check_nested_keys::<#ty>(&value)
+                    .map_err(|e| Self::Err::Deserialization(e))?;
+            }
+        })
+        .collect::<Vec<_>>();
+    let validate_impl = if nested_keys_impl.is_empty() {
+        quote! {
+            crate::libs::json::validate_json(json).map_err(|e| Self::Err::Deserialization(e))
+        }
+    } else {
+        quote! {
+            let updates = crate::libs::json::validate_json(json)
+                .map_err(|e| Self::Err::Deserialization(e))?;
+            let value: serde_json::Value =
+                serde_json::from_str(json).map_err(|e| Self::Err::Deserialization(e))?;
+            #(#nested_keys_impl)*
+            Ok(updates)
+        }
+    };
+
     // Generate implementation for update
     let update_impl = filtered_fields.map(|f| {
         let name = &f.ident;
@@ -121,13 +234,25 @@ pub fn impl_updatable_trait(ast: DeriveInput) -> TokenStream {
         }
     });
 
+    // Generate the check of updates against the current state
+    let check_impl = extract_updatable_path(&ast.attrs, "check").map(|check| {
+        let check = check
+            .parse::<syn::Path>()
+            .expect("invalid update check path");
+        quote! {
+            fn check_update(&self, updates: &Self::Updater) -> Result<(), Self::Err> {
+                #check(self, updates)
+            }
+        }
+    });
+
     // Generate complete implementation
     quote! {
         // Include error enum
         #error_type
 
         #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
-        #[serde(default)]
+        #[serde(default, deny_unknown_fields)]
         pub struct #update_name {
             #(#field_defs,)*
         }
@@ -136,13 +261,13 @@ pub fn impl_updatable_trait(ast: DeriveInput) -> TokenStream {
             type Err = #error_name;
 
             fn fields_json() -> Vec<String> {
-                vec![
-                    #(#fields_json_impl,)*
-                ]
+                let mut fields = Vec::new();
+                #(#fields_json_impl)*
+                fields
             }
 
             fn validate_json(json: &str) -> Result<Self, Self::Err> {
-                crate::libs::json::validate_json(json).map_err(|e| Self::Err::Deserialization(e))
+                #validate_impl
             }
         }
 
@@ -154,13 +279,14 @@ pub fn impl_updatable_trait(ast: DeriveInput) -> TokenStream {
                 #(#update_impl)*
             }
 
+            #check_impl
+
             fn update_from_json(&mut self, json: &str) -> Result<(), Self::Err> {
-                serde_json::from_str(json)
-                    .map_err(|e| Self::Err::Deserialization(e))
-                    .and_then(|updates| {
-                        self.update(updates);
-                        Ok(())
-                    })
+                let updates =
+                    <#update_name as crate::extensions::JsonSchema>::validate_json(json)?;
+                self.check_update(&updates)?;
+                self.update(updates);
+                Ok(())
             }
         }
     }
@@ -257,6 +383,21 @@ mod tests {
         assert_tokens_eq(generated, expected);
     }
 
+    #[test]
+    fn test_extract_serde_rename_with_multiple_attributes() {
+        // Le rename peut se trouver après d'autres attributs serde
+        let input: DeriveInput = parse_quote! {
+            struct Terminal {
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                #[serde(rename = "activePower")]
+                p: Option<f64>,
+            }
+        };
+
+        let field = extract_named_fields(&input).first().unwrap();
+        assert_eq!(extract_serde_rename(&field.attrs, "p"), "activePower");
+    }
+
     #[test]
     fn test_impl_updatable_trait() {
         // Structure pour tester l'implémentation du trait Updatable
@@ -297,4 +438,37 @@ mod tests {
         assert!(generated.to_string().contains("type Updater = UserUpdater"));
         assert!(generated.to_string().contains("type Err = UserError"));
     }
+
+    #[test]
+    fn test_impl_updatable_trait_skips_unknown_fields() {
+        // Les attributs non modélisés ne doivent pas être modifiables
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                id: i64,
+                name: String,
+                #[serde(flatten)]
+                unknown_fields: UnknownFields,
+            }
+        };
+
+        let generated = impl_updatable_trait(input).to_string();
+        assert!(generated.contains("pub name : Option < String >"));
+        assert!(!generated.contains("pub unknown_fields"));
+        assert!(!generated.contains("self . unknown_fields"));
+        assert!(!generated.contains("UnknownFields"));
+    }
+
+    #[test]
+    fn test_impl_updatable_trait_denies_unknown_fields() {
+        // Une clé inconnue dans une mise à jour doit être rejetée
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                id: i64,
+                name: String,
+            }
+        };
+
+        let generated = impl_updatable_trait(input).to_string();
+        assert!(generated.contains("# [serde (default , deny_unknown_fields)]"));
+    }
 }
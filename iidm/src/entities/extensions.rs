@@ -0,0 +1,111 @@
+use bevy_ecs::component::Component;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::extensions::Updatable;
+
+/// A typed IIDM extension, identified by its name in the network `extensions` section
+pub trait Extension: Component + Clone + Updatable {
+    const NAME: &'static str;
+}
+
+/// Extensions attached to one extendable, kept as raw JSON so that extensions
+/// without a typed model are written back verbatim
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExtensionHolder {
+    pub id: String,
+    #[serde(default)]
+    pub extensions: Map<String, Value>,
+}
+
+impl ExtensionHolder {
+    /// Deserializes the extension `T` if the extendable carries it
+    pub fn get<T: Extension>(&self) -> Option<Result<T, serde_json::Error>> {
+        self.extensions
+            .get(T::NAME)
+            .map(|value| serde_json::from_value(value.clone()))
+    }
+
+    /// Adds or replaces the extension `T`
+    pub fn insert<T: Extension>(&mut self, extension: &T) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(extension)?;
+        self.extensions.insert(T::NAME.to_string(), value);
+        Ok(())
+    }
+}
+
+/// Extensions of an entity that have no typed model, by name
+#[derive(Debug, Default, Clone, Component)]
+pub struct UnknownExtensions(pub Map<String, Value>);
+
+macro_rules! impl_extension {
+    ($($extension:ty => $name:literal),* $(,)?) => {
+        $(
+            impl Extension for $extension {
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+impl_extension!(
+    SubstationPosition => "substationPosition",
+    BusbarSectionPosition => "busbarSectionPosition",
+    ActivePowerControl => "activePowerControl",
+    GeneratorShortCircuit => "generatorShortCircuit",
+    HvdcAngleDroopActivePowerControl => "hvdcAngleDroopActivePowerControl",
+    EntsoeArea => "entsoeArea",
+);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct SubstationPosition {
+    pub coordinate: Coordinate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct BusbarSectionPosition {
+    #[serde(rename = "busbarIndex")]
+    pub busbar_index: i32,
+    #[serde(rename = "sectionIndex")]
+    pub section_index: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ActivePowerControl {
+    pub participate: bool,
+    pub droop: f64,
+    #[serde(rename = "participationFactor")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub participation_factor: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct GeneratorShortCircuit {
+    #[serde(rename = "directSubtransX")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direct_subtrans_x: Option<f64>,
+    #[serde(rename = "directTransX")]
+    pub direct_trans_x: f64,
+    #[serde(rename = "stepUpTransformerX")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_up_transformer_x: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct HvdcAngleDroopActivePowerControl {
+    pub p0: f64,
+    pub droop: f64,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct EntsoeArea {
+    pub code: String,
+}
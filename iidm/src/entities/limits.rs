@@ -0,0 +1,190 @@
+use super::{
+    DanglingLine, DanglingLineError, DanglingLineUpdater, Leg, LegError, LegUpdater, Line,
+    LineError, LineUpdater, OperationalLimitsGroup, Side, ThreeWindingsTransformer,
+    ThreeWindingsTransformerError, ThreeWindingsTransformerUpdater, TwoWindingsTransformer,
+    TwoWindingsTransformerError, TwoWindingsTransformerUpdater,
+};
+
+/// Access to the operational limits groups held by each side of an equipment
+pub trait OperationalLimitsHolder {
+    /// Returns the groups defined on a side, empty if the side doesn't exist
+    fn operational_limits_groups(&self, side: Side) -> &[OperationalLimitsGroup];
+
+    /// Returns the id of the group selected on a side
+    fn selected_operational_limits_group_id(&self, side: Side) -> Option<&str>;
+
+    /// Finds a group by its id on a side
+    fn operational_limits_group(&self, side: Side, id: &str) -> Option<&OperationalLimitsGroup> {
+        self.operational_limits_groups(side)
+            .iter()
+            .find(|group| group.id == id)
+    }
+
+    /// Returns the group currently selected on a side
+    fn selected_operational_limits_group(&self, side: Side) -> Option<&OperationalLimitsGroup> {
+        self.selected_operational_limits_group_id(side)
+            .and_then(|id| self.operational_limits_group(side, id))
+    }
+}
+
+macro_rules! impl_two_sides_limits_holder {
+    ($($branch:ty),* $(,)?) => {
+        $(
+            impl OperationalLimitsHolder for $branch {
+                fn operational_limits_groups(&self, side: Side) -> &[OperationalLimitsGroup] {
+                    match side {
+                        Side::One => &self.operational_limits_groups1,
+                        Side::Two => &self.operational_limits_groups2,
+                        Side::Three => &[],
+                    }
+                }
+
+                fn selected_operational_limits_group_id(&self, side: Side) -> Option<&str> {
+                    match side {
+                        Side::One => self.selected_operational_limits_group_id1.as_deref(),
+                        Side::Two => self.selected_operational_limits_group_id2.as_deref(),
+                        Side::Three => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_two_sides_limits_holder!(Line, TwoWindingsTransformer);
+
+impl OperationalLimitsHolder for ThreeWindingsTransformer {
+    fn operational_limits_groups(&self, side: Side) -> &[OperationalLimitsGroup] {
+        &self.leg(side).operational_limits_groups
+    }
+
+    fn selected_operational_limits_group_id(&self, side: Side) -> Option<&str> {
+        self.leg(side)
+            .selected_operational_limits_group_id
+            .as_deref()
+    }
+}
+
+/// A dangling line only has one terminal, exposed as side one
+impl OperationalLimitsHolder for DanglingLine {
+    fn operational_limits_groups(&self, side: Side) -> &[OperationalLimitsGroup] {
+        match side {
+            Side::One => &self.operational_limits_groups,
+            Side::Two | Side::Three => &[],
+        }
+    }
+
+    fn selected_operational_limits_group_id(&self, side: Side) -> Option<&str> {
+        match side {
+            Side::One => self.selected_operational_limits_group_id.as_deref(),
+            Side::Two | Side::Three => None,
+        }
+    }
+}
+
+/// Checks that the group selected on a side once updated is one of the groups of that side.
+/// Sides whose groups and selection are both left unchanged are not checked.
+fn check_selected_group_update(
+    selected: &Option<Option<String>>,
+    groups: &Option<Vec<OperationalLimitsGroup>>,
+    current_selected: &Option<String>,
+    current_groups: &[OperationalLimitsGroup],
+) -> Result<(), String> {
+    if selected.is_none() && groups.is_none() {
+        return Ok(());
+    }
+    check_selected_group(
+        selected.as_ref().unwrap_or(current_selected),
+        groups.as_deref().unwrap_or(current_groups),
+    )
+}
+
+fn check_selected_group(
+    selected: &Option<String>,
+    groups: &[OperationalLimitsGroup],
+) -> Result<(), String> {
+    match selected {
+        Some(id) if !groups.iter().any(|group| &group.id == id) => Err(format!(
+            "The operational limits group '{}' does not exist",
+            id
+        )),
+        _ => Ok(()),
+    }
+}
+
+macro_rules! impl_two_sides_update_check {
+    ($($check:ident: $branch:ty, $updater:ty, $error:ident;)*) => {
+        $(
+            pub(super) fn $check(branch: &$branch, updates: &$updater) -> Result<(), $error> {
+                check_selected_group_update(
+                    &updates.selected_operational_limits_group_id1,
+                    &updates.operational_limits_groups1,
+                    &branch.selected_operational_limits_group_id1,
+                    &branch.operational_limits_groups1,
+                )
+                .map_err($error::SelectedOperationalLimitsGroupId1)?;
+                check_selected_group_update(
+                    &updates.selected_operational_limits_group_id2,
+                    &updates.operational_limits_groups2,
+                    &branch.selected_operational_limits_group_id2,
+                    &branch.operational_limits_groups2,
+                )
+                .map_err($error::SelectedOperationalLimitsGroupId2)
+            }
+        )*
+    };
+}
+
+impl_two_sides_update_check! {
+    check_line_update: Line, LineUpdater, LineError;
+    check_two_windings_transformer_update:
+        TwoWindingsTransformer, TwoWindingsTransformerUpdater, TwoWindingsTransformerError;
+}
+
+pub(super) fn check_dangling_line_update(
+    dangling_line: &DanglingLine,
+    updates: &DanglingLineUpdater,
+) -> Result<(), DanglingLineError> {
+    check_selected_group_update(
+        &updates.selected_operational_limits_group_id,
+        &updates.operational_limits_groups,
+        &dangling_line.selected_operational_limits_group_id,
+        &dangling_line.operational_limits_groups,
+    )
+    .map_err(DanglingLineError::SelectedOperationalLimitsGroupId)
+}
+
+pub(super) fn check_leg_update(leg: &Leg, updates: &LegUpdater) -> Result<(), LegError> {
+    check_selected_group_update(
+        &updates.selected_operational_limits_group_id,
+        &updates.operational_limits_groups,
+        &leg.selected_operational_limits_group_id,
+        &leg.operational_limits_groups,
+    )
+    .map_err(LegError::SelectedOperationalLimitsGroupId)
+}
+
+/// Legs are replaced as a whole, each new leg must select one of its own groups
+pub(super) fn check_three_windings_transformer_update(
+    _transformer: &ThreeWindingsTransformer,
+    updates: &ThreeWindingsTransformerUpdater,
+) -> Result<(), ThreeWindingsTransformerError> {
+    let legs = [
+        (
+            &updates.leg1,
+            ThreeWindingsTransformerError::Leg1 as fn(String) -> _,
+        ),
+        (&updates.leg2, ThreeWindingsTransformerError::Leg2),
+        (&updates.leg3, ThreeWindingsTransformerError::Leg3),
+    ];
+    for (leg, error) in legs {
+        if let Some(leg) = leg {
+            check_selected_group(
+                &leg.selected_operational_limits_group_id,
+                &leg.operational_limits_groups,
+            )
+            .map_err(error)?;
+        }
+    }
+    Ok(())
+}
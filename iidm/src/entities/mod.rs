@@ -1,3 +1,15 @@
+mod extensions;
+mod limits;
+mod reactive_limits;
+mod shunt_compensator;
+mod three_windings_transformer;
+mod topology;
+
+pub use extensions::*;
+pub use limits::*;
+pub use reactive_limits::*;
+pub use topology::*;
+
 use crate::extensions::{Identifiable, Updatable};
 use crate::plugins::RegisterEvent;
 
@@ -5,6 +17,10 @@ use bevy_ecs::component::Component;
 use chrono::{DateTime, FixedOffset};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Attributes of an entity that have no model yet, written back as they were read
+pub type UnknownFields = Map<String, Value>;
 
 #[derive(
     Debug, Default, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema,
@@ -57,6 +73,14 @@ pub struct Network {
     #[serde(rename = "hvdcLines")]
     #[serde(default)]
     pub hvdc_lines: Vec<HvdcLine>,
+
+    #[serde(default)]
+    pub areas: Vec<Area>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<ExtensionHolder>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -70,6 +94,47 @@ pub struct Substation {
     pub voltage_levels: Vec<VoltageLevel>,
     #[serde(rename = "twoWindingsTransformers")]
     pub two_windings_transformers: Vec<TwoWindingsTransformer>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+pub struct Area {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "areaType")]
+    pub area_type: String,
+    #[serde(rename = "interchangeTarget")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interchange_target: Option<f64>,
+    #[serde(rename = "voltageLevelIds")]
+    #[serde(default)]
+    pub voltage_level_ids: Vec<String>,
+    #[serde(rename = "areaBoundaries")]
+    #[serde(default)]
+    pub area_boundaries: Vec<AreaBoundary>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct AreaBoundary {
+    #[serde(rename = "type")]
+    pub boundary_type: AreaBoundaryType,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<Side>,
+    pub ac: bool,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Component, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AreaBoundaryType {
+    TerminalRef,
+    Boundary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -84,11 +149,15 @@ pub struct VoltageLevel {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub loads: Option<Vec<Load>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub batteries: Option<Vec<Battery>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub busbar_sections: Option<Vec<BusbarSection>>,
     #[serde(rename = "nodeBreakerTopology")]
     pub node_breaker_topology: Option<NodeBreakerTopology>,
     #[serde(rename = "busBreakerTopology")]
     pub bus_breaker_topology: Option<BusBreakerTopology>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Component, JsonSchema)]
@@ -139,18 +208,46 @@ pub struct Generator {
     pub target_v: f64,
     #[serde(rename = "targetQ")]
     pub target_q: f64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "regulatingTerminal")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulating_terminal: Option<TerminalRef>,
+    #[serde(
+        rename = "reactiveLimits",
+        flatten,
+        deserialize_with = "reactive_limits::deserialize_reactive_limits"
+    )]
+    #[updatable(deserialize_with = "reactive_limits::deserialize_reactive_limits_update")]
+    pub reactive_limits: Option<ReactiveLimits>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+/// Reactive limits of a generator, a battery or a VSC station, serialized as the
+/// `reactiveCapabilityCurve` or the `minMaxReactiveLimits` attribute
+#[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
+pub enum ReactiveLimits {
     #[serde(rename = "reactiveCapabilityCurve")]
-    pub reactive_capability_curve: Option<ReactiveCapabilityCurve>,
+    Curve(ReactiveCapabilityCurve),
     #[serde(rename = "minMaxReactiveLimits")]
-    pub min_max_reactive_limits: Option<MinMaxReactiveLimits>,
+    MinMax(MinMaxReactiveLimits),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
 pub struct ReactiveCapabilityCurve {
     pub points: Vec<ReactiveCapabilityCurvePoint>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -160,6 +257,8 @@ pub struct ReactiveCapabilityCurvePoint {
     pub min_q: f64,
     #[serde(rename = "maxQ")]
     pub max_q: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -168,6 +267,8 @@ pub struct MinMaxReactiveLimits {
     pub min_q: f64,
     #[serde(rename = "maxQ")]
     pub max_q: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -177,13 +278,56 @@ pub struct Load {
     pub load_type: LoadType,
     pub p0: f64,
     pub q0: f64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
     #[serde(rename = "exponentialModel")]
     pub exponential_model: Option<ExponentialLoadModel>,
     #[serde(rename = "zipModel")]
     pub zip_model: Option<ZipLoadModel>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+pub struct Battery {
+    pub id: String,
+    #[serde(rename = "targetP")]
+    pub target_p: f64,
+    #[serde(rename = "targetQ")]
+    pub target_q: f64,
+    #[serde(rename = "minP")]
+    pub min_p: f64,
+    #[serde(rename = "maxP")]
+    pub max_p: f64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub bus: String,
+    #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(
+        rename = "reactiveLimits",
+        flatten,
+        deserialize_with = "reactive_limits::deserialize_reactive_limits"
+    )]
+    #[updatable(deserialize_with = "reactive_limits::deserialize_reactive_limits_update")]
+    pub reactive_limits: Option<ReactiveLimits>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Component, JsonSchema)]
@@ -200,6 +344,8 @@ pub struct ExponentialLoadModel {
     pub q0: f64,
     pub np: f64,
     pub nq: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -218,6 +364,8 @@ pub struct ZipLoadModel {
     pub p_p: f64,
     #[serde(rename = "pQ")]
     pub p_q: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -226,6 +374,8 @@ pub struct BusBreakerTopology {
     pub buses: Vec<Bus>,
     #[serde(default)]
     pub switches: Vec<Switch>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -234,34 +384,57 @@ pub struct NodeBreakerTopology {
     pub switches: Vec<Switch>,
     #[serde(rename = "internalConnections")]
     pub internal_connections: Vec<InternalConnection>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
 pub struct Node {
     pub id: i32,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
 pub struct InternalConnection {
     pub node1: i32,
     pub node2: i32,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
 pub struct Bus {
     pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub angle: Option<f64>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
 pub struct BusbarSection {
     pub id: String,
     pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub angle: Option<f64>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+#[updatable(check = "limits::check_two_windings_transformer_update")]
 pub struct TwoWindingsTransformer {
     pub id: String,
     pub r: f64,
@@ -274,14 +447,30 @@ pub struct TwoWindingsTransformer {
     pub rated_u2: f64,
     #[serde(rename = "voltageLevelId1")]
     pub voltage_level_id1: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus1: String,
     #[serde(rename = "connectableBus1")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node1: Option<i32>,
     #[serde(rename = "voltageLevelId2")]
     pub voltage_level_id2: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus2: String,
     #[serde(rename = "connectableBus2")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node2: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p1: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q1: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q2: Option<f64>,
     #[serde(rename = "ratioTapChanger")]
     pub ratio_tap_changer: Option<RatioTapChanger>,
     #[serde(rename = "phaseTapChanger")]
@@ -290,54 +479,86 @@ pub struct TwoWindingsTransformer {
     pub current_limits1: Option<CurrentLimits>,
     #[serde(rename = "currentLimits2")]
     pub current_limits2: Option<CurrentLimits>,
+    #[serde(rename = "operationalLimitsGroups1")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups1: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id1: Option<String>,
+    #[serde(rename = "operationalLimitsGroups2")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups2: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id2: Option<String>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable)]
+#[updatable(check = "limits::check_three_windings_transformer_update")]
+#[serde(
+    from = "three_windings_transformer::FlatThreeWindingsTransformer",
+    into = "three_windings_transformer::FlatThreeWindingsTransformer"
+)]
 pub struct ThreeWindingsTransformer {
     pub id: String,
-    pub r1: f64,
-    pub x1: f64,
-    pub g1: f64,
-    pub b1: f64,
-    #[serde(rename = "ratedU1")]
-    pub rated_u1: f64,
-    pub r2: f64,
-    pub x2: f64,
-    pub g2: f64,
-    pub b2: f64,
-    #[serde(rename = "ratedU2")]
-    pub rated_u2: f64,
-    pub r3: f64,
-    pub x3: f64,
-    pub g3: f64,
-    pub b3: f64,
-    #[serde(rename = "ratedU3")]
-    pub rated_u3: f64,
-    #[serde(rename = "voltageLevelId1")]
-    pub voltage_level_id1: String,
-    #[serde(rename = "voltageLevelId2")]
-    pub voltage_level_id2: String,
-    #[serde(rename = "voltageLevelId3")]
-    pub voltage_level_id3: String,
-    pub bus1: String,
-    pub bus2: String,
-    pub bus3: String,
-    #[serde(rename = "connectableBus1")]
-    pub connectable_bus1: String,
-    #[serde(rename = "connectableBus2")]
-    pub connectable_bus2: String,
-    #[serde(rename = "connectableBus3")]
-    pub connectable_bus3: String,
-    #[serde(rename = "ratioTapChanger2")]
-    pub ratio_tap_changer2: Option<RatioTapChanger>,
-    #[serde(rename = "ratioTapChanger3")]
-    pub ratio_tap_changer3: Option<RatioTapChanger>,
-    #[serde(rename = "currentLimits1")]
-    pub current_limits1: Option<CurrentLimits>,
-    #[serde(rename = "currentLimits2")]
-    pub current_limits2: Option<CurrentLimits>,
-    #[serde(rename = "currentLimits3")]
-    pub current_limits3: Option<CurrentLimits>,
+    /// Star bus voltage, kept absent when it was not given
+    #[serde(rename = "ratedU0")]
+    pub rated_u0: Option<f64>,
+    pub leg1: Leg,
+    pub leg2: Leg,
+    pub leg3: Leg,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+#[updatable(check = "limits::check_leg_update")]
+pub struct Leg {
+    pub r: f64,
+    pub x: f64,
+    pub g: f64,
+    pub b: f64,
+    #[serde(rename = "ratedU")]
+    pub rated_u: f64,
+    #[serde(rename = "ratedS")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rated_s: Option<f64>,
+    #[serde(rename = "voltageLevelId")]
+    pub voltage_level_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub bus: String,
+    #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "ratioTapChanger")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratio_tap_changer: Option<RatioTapChanger>,
+    #[serde(rename = "phaseTapChanger")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase_tap_changer: Option<PhaseTapChanger>,
+    #[serde(rename = "currentLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_limits: Option<CurrentLimits>,
+    #[serde(rename = "activePowerLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_power_limits: Option<ActivePowerLimits>,
+    #[serde(rename = "apparentPowerLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apparent_power_limits: Option<ApparentPowerLimits>,
+    #[serde(rename = "operationalLimitsGroups")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -358,6 +579,8 @@ pub struct RatioTapChanger {
     #[serde(rename = "terminalRef")]
     pub terminal_ref: TerminalRef,
     pub steps: Vec<TapStep>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -376,6 +599,8 @@ pub struct PhaseTapChanger {
     #[serde(rename = "terminalRef")]
     pub terminal_ref: TerminalRef,
     pub steps: Vec<PhaseTapStep>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
@@ -394,6 +619,7 @@ pub enum RatioRegulationMode {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+#[updatable(check = "limits::check_line_update")]
 pub struct Line {
     pub id: String,
     pub r: f64,
@@ -404,18 +630,48 @@ pub struct Line {
     pub b2: f64,
     #[serde(rename = "voltageLevelId1")]
     pub voltage_level_id1: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus1: String,
     #[serde(rename = "connectableBus1")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node1: Option<i32>,
     #[serde(rename = "voltageLevelId2")]
     pub voltage_level_id2: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus2: String,
     #[serde(rename = "connectableBus2")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node2: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p1: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q1: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q2: Option<f64>,
     #[serde(rename = "currentLimits1")]
     pub current_limits1: Option<CurrentLimits>,
     #[serde(rename = "currentLimits2")]
     pub current_limits2: Option<CurrentLimits>,
+    #[serde(rename = "operationalLimitsGroups1")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups1: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id1: Option<String>,
+    #[serde(rename = "operationalLimitsGroups2")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups2: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id2: Option<String>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -424,10 +680,18 @@ pub struct Switch {
     pub kind: SwitchKind,
     pub open: bool,
     pub retained: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus1: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node1: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node2: Option<i32>,
     #[serde(rename = "voltageLevelId")]
     pub voltage_level_id: String,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
@@ -441,15 +705,71 @@ pub enum SwitchKind {
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
 pub struct ShuntCompensator {
     pub id: String,
-    #[serde(rename = "bPerSection")]
-    pub b_per_section: f64,
-    #[serde(rename = "maximumSectionCount")]
-    pub maximum_section_count: i32,
     #[serde(rename = "sectionCount")]
     pub section_count: i32,
+    #[serde(rename = "model", flatten)]
+    #[updatable(deserialize_with = "shunt_compensator::deserialize_model_update")]
+    pub model: ShuntCompensatorModel,
+    #[serde(rename = "voltageLevelId")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub voltage_level_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "regulatingTerminal")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulating_terminal: Option<TerminalRef>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+/// Susceptance model of a shunt compensator, serialized as the `shuntLinearModel` or the
+/// `shuntNonLinearModel` attribute
+#[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
+pub enum ShuntCompensatorModel {
+    #[serde(rename = "shuntLinearModel")]
+    Linear(ShuntLinearModel),
+    #[serde(rename = "shuntNonLinearModel")]
+    NonLinear(ShuntNonLinearModel),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ShuntLinearModel {
+    #[serde(rename = "bPerSection")]
+    pub b_per_section: f64,
+    #[serde(rename = "gPerSection")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub g_per_section: Option<f64>,
+    #[serde(rename = "maximumSectionCount")]
+    pub maximum_section_count: i32,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ShuntNonLinearModel {
+    pub sections: Vec<ShuntSection>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+/// Section of a non-linear shunt compensator, `b` and `g` are the total admittance
+/// when this section is the last one in service
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ShuntSection {
+    pub b: f64,
+    #[serde(default)]
+    pub g: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -465,9 +785,25 @@ pub struct StaticVarCompensator {
     pub voltage_setpoint: f64,
     #[serde(rename = "reactivePowerSetpoint")]
     pub reactive_power_setpoint: f64,
+    #[serde(rename = "voltageLevelId")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub voltage_level_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "regulatingTerminal")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulating_terminal: Option<TerminalRef>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
@@ -479,6 +815,7 @@ pub enum StaticVarCompensatorRegulationMode {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
+#[updatable(check = "limits::check_dangling_line_update")]
 pub struct DanglingLine {
     pub id: String,
     pub p0: f64,
@@ -487,9 +824,28 @@ pub struct DanglingLine {
     pub x: f64,
     pub g: f64,
     pub b: f64,
+    #[serde(rename = "voltageLevelId")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub voltage_level_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "operationalLimitsGroups")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_limits_groups: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_operational_limits_group_id: Option<String>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -502,6 +858,8 @@ pub struct TieLine {
     pub dangling_line2: DanglingLine,
     #[serde(rename = "ucteXnodeCode")]
     pub ucte_xnode_code: String,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -522,6 +880,8 @@ pub struct HvdcLine {
     pub converter_station1: HvdcConverterStation,
     #[serde(rename = "converterStation2")]
     pub converter_station2: HvdcConverterStation,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
@@ -530,13 +890,33 @@ pub struct HvdcConverterStation {
     pub name: String,
     #[serde(rename = "voltageLevelId")]
     pub voltage_level_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub bus: String,
     #[serde(rename = "connectableBus")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub connectable_bus: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    #[serde(rename = "regulatingTerminal")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulating_terminal: Option<TerminalRef>,
     #[serde(rename = "lossFactor")]
     pub loss_factor: f64,
     #[serde(rename = "reactivePowerSetpoint")]
     pub reactive_power_setpoint: f64,
+    #[serde(
+        rename = "reactiveLimits",
+        flatten,
+        deserialize_with = "reactive_limits::deserialize_reactive_limits"
+    )]
+    #[updatable(deserialize_with = "reactive_limits::deserialize_reactive_limits_update")]
+    pub reactive_limits: Option<ReactiveLimits>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
@@ -549,7 +929,10 @@ pub enum ConvertersMode {
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Identifiable, Updatable, JsonSchema)]
 pub struct TerminalRef {
     pub id: String,
-    pub side: Side,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<Side>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -559,6 +942,8 @@ pub struct TapStep {
     pub g: f64,
     pub b: f64,
     pub rho: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -569,6 +954,8 @@ pub struct PhaseTapStep {
     pub b: f64,
     pub rho: f64,
     pub alpha: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -577,6 +964,46 @@ pub struct CurrentLimits {
     pub permanent_limit: f64,
     #[serde(rename = "temporaryLimits")]
     pub temporary_limits: Vec<TemporaryLimit>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ActivePowerLimits {
+    #[serde(rename = "permanentLimit")]
+    pub permanent_limit: f64,
+    #[serde(rename = "temporaryLimits")]
+    #[serde(default)]
+    pub temporary_limits: Vec<TemporaryLimit>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct ApparentPowerLimits {
+    #[serde(rename = "permanentLimit")]
+    pub permanent_limit: f64,
+    #[serde(rename = "temporaryLimits")]
+    #[serde(default)]
+    pub temporary_limits: Vec<TemporaryLimit>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
+pub struct OperationalLimitsGroup {
+    pub id: String,
+    #[serde(rename = "currentLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_limits: Option<CurrentLimits>,
+    #[serde(rename = "activePowerLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_power_limits: Option<ActivePowerLimits>,
+    #[serde(rename = "apparentPowerLimits")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apparent_power_limits: Option<ApparentPowerLimits>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component, Updatable, JsonSchema)]
@@ -585,9 +1012,11 @@ pub struct TemporaryLimit {
     #[serde(rename = "acceptableDuration")]
     pub acceptable_duration: i32,
     pub value: f64,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Component, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Component, JsonSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Side {
     One,
@@ -0,0 +1,161 @@
+use serde::{de::Error, Deserialize, Deserializer};
+
+use super::{
+    Battery, Generator, HvdcConverterStation, MinMaxReactiveLimits, ReactiveCapabilityCurve,
+    ReactiveLimits,
+};
+
+/// Attributes holding the reactive limits of an equipment, which are exclusive
+#[derive(Deserialize)]
+struct ReactiveLimitsAttributes {
+    #[serde(rename = "reactiveCapabilityCurve")]
+    curve: Option<ReactiveCapabilityCurve>,
+    #[serde(rename = "minMaxReactiveLimits")]
+    min_max: Option<MinMaxReactiveLimits>,
+}
+
+/// Reads the reactive limits from the attributes of their equipment. Unlike a flattened
+/// `Option`, malformed limits are reported, as are limits given by both attributes.
+pub(super) fn deserialize_reactive_limits<'de, D>(
+    deserializer: D,
+) -> Result<Option<ReactiveLimits>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let attributes = ReactiveLimitsAttributes::deserialize(deserializer)?;
+    match (attributes.curve, attributes.min_max) {
+        (Some(_), Some(_)) => Err(D::Error::custom(
+            "reactiveCapabilityCurve and minMaxReactiveLimits are exclusive",
+        )),
+        (Some(curve), None) => Ok(Some(ReactiveLimits::Curve(curve))),
+        (None, Some(limits)) => Ok(Some(ReactiveLimits::MinMax(limits))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Reads the reactive limits of an update from the attributes of their equipment, updates
+/// without limits leave them unchanged
+pub(super) fn deserialize_reactive_limits_update<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<ReactiveLimits>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_reactive_limits(deserializer)?.map(Some))
+}
+
+/// Reactive setpoint found outside the reactive limits of an equipment
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Reactive setpoint {q} MVar is outside [{min_q}, {max_q}] MVar at {p} MW")]
+pub struct ReactiveLimitsViolation {
+    pub p: f64,
+    pub q: f64,
+    pub min_q: f64,
+    pub max_q: f64,
+}
+
+impl ReactiveLimits {
+    /// Returns the allowed reactive power range `(min_q, max_q)` at an active power.
+    ///
+    /// Capability curve points are linearly interpolated and the extreme points are used
+    /// outside of the curve. Returns `None` for a curve without points.
+    pub fn reactive_range(&self, p: f64) -> Option<(f64, f64)> {
+        match self {
+            ReactiveLimits::MinMax(limits) => Some((limits.min_q, limits.max_q)),
+            ReactiveLimits::Curve(curve) => {
+                let mut points: Vec<_> = curve.points.iter().collect();
+                points.sort_by(|a, b| a.p.total_cmp(&b.p));
+
+                let first = points.first()?;
+                let last = points.last()?;
+                if p <= first.p {
+                    return Some((first.min_q, first.max_q));
+                }
+                if p >= last.p {
+                    return Some((last.min_q, last.max_q));
+                }
+
+                points.windows(2).find_map(|window| {
+                    let (a, b) = (window[0], window[1]);
+                    if p < a.p || p > b.p {
+                        return None;
+                    }
+                    let ratio = (p - a.p) / (b.p - a.p);
+                    Some((
+                        a.min_q + ratio * (b.min_q - a.min_q),
+                        a.max_q + ratio * (b.max_q - a.max_q),
+                    ))
+                })
+            }
+        }
+    }
+
+    /// Returns the widest reactive power range `(min_q, max_q)`, whatever the active power.
+    /// Returns `None` for a curve without points.
+    pub fn bounds(&self) -> Option<(f64, f64)> {
+        match self {
+            ReactiveLimits::MinMax(limits) => Some((limits.min_q, limits.max_q)),
+            ReactiveLimits::Curve(curve) => curve.points.iter().fold(None, |bounds, point| {
+                let (min_q, max_q) = bounds.unwrap_or((point.min_q, point.max_q));
+                Some((min_q.min(point.min_q), max_q.max(point.max_q)))
+            }),
+        }
+    }
+
+    /// Checks that a reactive setpoint lies in the range allowed at an active setpoint
+    pub fn check(&self, p: f64, q: f64) -> Result<(), ReactiveLimitsViolation> {
+        match self.reactive_range(p) {
+            Some((min_q, max_q)) if q < min_q || q > max_q => {
+                Err(ReactiveLimitsViolation { p, q, min_q, max_q })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Equipments whose reactive setpoint is bounded by reactive limits
+pub trait ReactiveLimitsHolder {
+    fn reactive_limits(&self) -> Option<&ReactiveLimits>;
+
+    /// Returns the `(p, q)` setpoint checked against the reactive limits
+    fn reactive_setpoint(&self) -> (f64, f64);
+
+    /// Checks the reactive setpoint, equipments without limits are always valid
+    fn check_reactive_limits(&self) -> Result<(), ReactiveLimitsViolation> {
+        let (p, q) = self.reactive_setpoint();
+        self.reactive_limits()
+            .map_or(Ok(()), |limits| limits.check(p, q))
+    }
+}
+
+impl ReactiveLimitsHolder for Generator {
+    fn reactive_limits(&self) -> Option<&ReactiveLimits> {
+        self.reactive_limits.as_ref()
+    }
+
+    fn reactive_setpoint(&self) -> (f64, f64) {
+        (self.target_p, self.target_q)
+    }
+}
+
+impl ReactiveLimitsHolder for Battery {
+    fn reactive_limits(&self) -> Option<&ReactiveLimits> {
+        self.reactive_limits.as_ref()
+    }
+
+    fn reactive_setpoint(&self) -> (f64, f64) {
+        (self.target_p, self.target_q)
+    }
+}
+
+/// The active power of a converter station is driven by its HVDC line, the flow
+/// on its terminal is used when known
+impl ReactiveLimitsHolder for HvdcConverterStation {
+    fn reactive_limits(&self) -> Option<&ReactiveLimits> {
+        self.reactive_limits.as_ref()
+    }
+
+    fn reactive_setpoint(&self) -> (f64, f64) {
+        (self.p.unwrap_or(0.0), self.reactive_power_setpoint)
+    }
+}
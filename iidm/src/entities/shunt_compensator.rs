@@ -0,0 +1,74 @@
+use serde::{de::Error, Deserialize, Deserializer};
+
+use super::{ShuntCompensator, ShuntCompensatorModel, ShuntLinearModel, ShuntNonLinearModel};
+
+/// Attributes holding the model of a shunt compensator, which are exclusive
+#[derive(Deserialize)]
+struct ModelAttributes {
+    #[serde(rename = "shuntLinearModel")]
+    linear: Option<ShuntLinearModel>,
+    #[serde(rename = "shuntNonLinearModel")]
+    non_linear: Option<ShuntNonLinearModel>,
+}
+
+/// Reads the model of an update from the IIDM attributes of the shunt compensator, updates
+/// without a model leave it unchanged. Malformed models are reported, as are models given by
+/// both attributes.
+pub(super) fn deserialize_model_update<'de, D>(
+    deserializer: D,
+) -> Result<Option<ShuntCompensatorModel>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let attributes = ModelAttributes::deserialize(deserializer)?;
+    match (attributes.linear, attributes.non_linear) {
+        (Some(_), Some(_)) => Err(D::Error::custom(
+            "shuntLinearModel and shuntNonLinearModel are exclusive",
+        )),
+        (Some(model), None) => Ok(Some(ShuntCompensatorModel::Linear(model))),
+        (None, Some(model)) => Ok(Some(ShuntCompensatorModel::NonLinear(model))),
+        (None, None) => Ok(None),
+    }
+}
+
+impl ShuntCompensatorModel {
+    /// Returns the number of sections that can be put in service
+    pub fn maximum_section_count(&self) -> i32 {
+        match self {
+            ShuntCompensatorModel::Linear(model) => model.maximum_section_count,
+            ShuntCompensatorModel::NonLinear(model) => model.sections.len() as i32,
+        }
+    }
+
+    /// Returns the susceptance and the conductance `(b, g)` with `section_count` sections
+    /// in service, `None` if the model doesn't have that many sections.
+    pub fn admittance(&self, section_count: i32) -> Option<(f64, f64)> {
+        if section_count < 0 || section_count > self.maximum_section_count() {
+            return None;
+        }
+        match self {
+            ShuntCompensatorModel::Linear(model) => {
+                let sections = f64::from(section_count);
+                Some((
+                    model.b_per_section * sections,
+                    model.g_per_section.unwrap_or(0.0) * sections,
+                ))
+            }
+            ShuntCompensatorModel::NonLinear(model) => match section_count {
+                0 => Some((0.0, 0.0)),
+                count => model
+                    .sections
+                    .get(count as usize - 1)
+                    .map(|section| (section.b, section.g)),
+            },
+        }
+    }
+}
+
+impl ShuntCompensator {
+    /// Returns the current susceptance and conductance `(b, g)` of the shunt, `None` if
+    /// its section count exceeds its model.
+    pub fn admittance(&self) -> Option<(f64, f64)> {
+        self.model.admittance(self.section_count)
+    }
+}
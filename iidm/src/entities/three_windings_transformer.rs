@@ -0,0 +1,346 @@
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ActivePowerLimits, ApparentPowerLimits, CurrentLimits, Leg, OperationalLimitsGroup,
+    PhaseTapChanger, RatioTapChanger, Side, ThreeWindingsTransformer, UnknownFields,
+};
+
+impl ThreeWindingsTransformer {
+    /// Returns the star bus voltage, which defaults to the rated voltage of the first leg
+    pub fn rated_u0(&self) -> f64 {
+        self.rated_u0.unwrap_or(self.leg1.rated_u)
+    }
+
+    /// Returns the leg connected on a side
+    pub fn leg(&self, side: Side) -> &Leg {
+        match side {
+            Side::One => &self.leg1,
+            Side::Two => &self.leg2,
+            Side::Three => &self.leg3,
+        }
+    }
+
+    /// Returns the leg connected on a side for modification
+    pub fn leg_mut(&mut self, side: Side) -> &mut Leg {
+        match side {
+            Side::One => &mut self.leg1,
+            Side::Two => &mut self.leg2,
+            Side::Three => &mut self.leg3,
+        }
+    }
+
+    /// Iterates over the legs with their side
+    pub fn legs(&self) -> [(Side, &Leg); 3] {
+        [
+            (Side::One, &self.leg1),
+            (Side::Two, &self.leg2),
+            (Side::Three, &self.leg3),
+        ]
+    }
+}
+
+/// The schema describes the serialized shape of the transformer
+impl JsonSchema for ThreeWindingsTransformer {
+    fn schema_name() -> String {
+        "ThreeWindingsTransformer".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        FlatThreeWindingsTransformer::json_schema(gen)
+    }
+}
+
+/// IIDM serializes the three legs as flat attributes suffixed by the side number,
+/// this is the on-disk shape of a [`ThreeWindingsTransformer`]
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub(super) struct FlatThreeWindingsTransformer {
+    id: String,
+    #[serde(rename = "ratedU0")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rated_u0: Option<f64>,
+    r1: f64,
+    x1: f64,
+    g1: f64,
+    b1: f64,
+    #[serde(rename = "ratedU1")]
+    rated_u1: f64,
+    #[serde(rename = "ratedS1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rated_s1: Option<f64>,
+    #[serde(rename = "voltageLevelId1")]
+    voltage_level_id1: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    bus1: String,
+    #[serde(rename = "connectableBus1")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    connectable_bus1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node1: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p1: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    q1: Option<f64>,
+    #[serde(rename = "ratioTapChanger1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratio_tap_changer1: Option<RatioTapChanger>,
+    #[serde(rename = "phaseTapChanger1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phase_tap_changer1: Option<PhaseTapChanger>,
+    #[serde(rename = "currentLimits1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current_limits1: Option<CurrentLimits>,
+    #[serde(rename = "activePowerLimits1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active_power_limits1: Option<ActivePowerLimits>,
+    #[serde(rename = "apparentPowerLimits1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    apparent_power_limits1: Option<ApparentPowerLimits>,
+    #[serde(rename = "operationalLimitsGroups1")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    operational_limits_groups1: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId1")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    selected_operational_limits_group_id1: Option<String>,
+    r2: f64,
+    x2: f64,
+    g2: f64,
+    b2: f64,
+    #[serde(rename = "ratedU2")]
+    rated_u2: f64,
+    #[serde(rename = "ratedS2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rated_s2: Option<f64>,
+    #[serde(rename = "voltageLevelId2")]
+    voltage_level_id2: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    bus2: String,
+    #[serde(rename = "connectableBus2")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    connectable_bus2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node2: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p2: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    q2: Option<f64>,
+    #[serde(rename = "ratioTapChanger2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratio_tap_changer2: Option<RatioTapChanger>,
+    #[serde(rename = "phaseTapChanger2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phase_tap_changer2: Option<PhaseTapChanger>,
+    #[serde(rename = "currentLimits2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current_limits2: Option<CurrentLimits>,
+    #[serde(rename = "activePowerLimits2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active_power_limits2: Option<ActivePowerLimits>,
+    #[serde(rename = "apparentPowerLimits2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    apparent_power_limits2: Option<ApparentPowerLimits>,
+    #[serde(rename = "operationalLimitsGroups2")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    operational_limits_groups2: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    selected_operational_limits_group_id2: Option<String>,
+    r3: f64,
+    x3: f64,
+    g3: f64,
+    b3: f64,
+    #[serde(rename = "ratedU3")]
+    rated_u3: f64,
+    #[serde(rename = "ratedS3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rated_s3: Option<f64>,
+    #[serde(rename = "voltageLevelId3")]
+    voltage_level_id3: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    bus3: String,
+    #[serde(rename = "connectableBus3")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    connectable_bus3: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node3: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p3: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    q3: Option<f64>,
+    #[serde(rename = "ratioTapChanger3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratio_tap_changer3: Option<RatioTapChanger>,
+    #[serde(rename = "phaseTapChanger3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phase_tap_changer3: Option<PhaseTapChanger>,
+    #[serde(rename = "currentLimits3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current_limits3: Option<CurrentLimits>,
+    #[serde(rename = "activePowerLimits3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active_power_limits3: Option<ActivePowerLimits>,
+    #[serde(rename = "apparentPowerLimits3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    apparent_power_limits3: Option<ApparentPowerLimits>,
+    #[serde(rename = "operationalLimitsGroups3")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    operational_limits_groups3: Vec<OperationalLimitsGroup>,
+    #[serde(rename = "selectedOperationalLimitsGroupId3")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    selected_operational_limits_group_id3: Option<String>,
+    #[serde(flatten)]
+    unknown_fields: UnknownFields,
+}
+
+impl From<FlatThreeWindingsTransformer> for ThreeWindingsTransformer {
+    fn from(flat: FlatThreeWindingsTransformer) -> Self {
+        let leg1 = Leg {
+            r: flat.r1,
+            x: flat.x1,
+            g: flat.g1,
+            b: flat.b1,
+            rated_u: flat.rated_u1,
+            rated_s: flat.rated_s1,
+            voltage_level_id: flat.voltage_level_id1,
+            bus: flat.bus1,
+            connectable_bus: flat.connectable_bus1,
+            node: flat.node1,
+            p: flat.p1,
+            q: flat.q1,
+            ratio_tap_changer: flat.ratio_tap_changer1,
+            phase_tap_changer: flat.phase_tap_changer1,
+            current_limits: flat.current_limits1,
+            active_power_limits: flat.active_power_limits1,
+            apparent_power_limits: flat.apparent_power_limits1,
+            operational_limits_groups: flat.operational_limits_groups1,
+            selected_operational_limits_group_id: flat.selected_operational_limits_group_id1,
+        };
+        let leg2 = Leg {
+            r: flat.r2,
+            x: flat.x2,
+            g: flat.g2,
+            b: flat.b2,
+            rated_u: flat.rated_u2,
+            rated_s: flat.rated_s2,
+            voltage_level_id: flat.voltage_level_id2,
+            bus: flat.bus2,
+            connectable_bus: flat.connectable_bus2,
+            node: flat.node2,
+            p: flat.p2,
+            q: flat.q2,
+            ratio_tap_changer: flat.ratio_tap_changer2,
+            phase_tap_changer: flat.phase_tap_changer2,
+            current_limits: flat.current_limits2,
+            active_power_limits: flat.active_power_limits2,
+            apparent_power_limits: flat.apparent_power_limits2,
+            operational_limits_groups: flat.operational_limits_groups2,
+            selected_operational_limits_group_id: flat.selected_operational_limits_group_id2,
+        };
+        let leg3 = Leg {
+            r: flat.r3,
+            x: flat.x3,
+            g: flat.g3,
+            b: flat.b3,
+            rated_u: flat.rated_u3,
+            rated_s: flat.rated_s3,
+            voltage_level_id: flat.voltage_level_id3,
+            bus: flat.bus3,
+            connectable_bus: flat.connectable_bus3,
+            node: flat.node3,
+            p: flat.p3,
+            q: flat.q3,
+            ratio_tap_changer: flat.ratio_tap_changer3,
+            phase_tap_changer: flat.phase_tap_changer3,
+            current_limits: flat.current_limits3,
+            active_power_limits: flat.active_power_limits3,
+            apparent_power_limits: flat.apparent_power_limits3,
+            operational_limits_groups: flat.operational_limits_groups3,
+            selected_operational_limits_group_id: flat.selected_operational_limits_group_id3,
+        };
+
+        Self {
+            id: flat.id,
+            rated_u0: flat.rated_u0,
+            leg1,
+            leg2,
+            leg3,
+            unknown_fields: flat.unknown_fields,
+        }
+    }
+}
+
+impl From<ThreeWindingsTransformer> for FlatThreeWindingsTransformer {
+    fn from(transformer: ThreeWindingsTransformer) -> Self {
+        let ThreeWindingsTransformer {
+            id,
+            rated_u0,
+            leg1,
+            leg2,
+            leg3,
+            unknown_fields,
+        } = transformer;
+
+        Self {
+            id,
+            rated_u0,
+            r1: leg1.r,
+            x1: leg1.x,
+            g1: leg1.g,
+            b1: leg1.b,
+            rated_u1: leg1.rated_u,
+            rated_s1: leg1.rated_s,
+            voltage_level_id1: leg1.voltage_level_id,
+            bus1: leg1.bus,
+            connectable_bus1: leg1.connectable_bus,
+            node1: leg1.node,
+            p1: leg1.p,
+            q1: leg1.q,
+            ratio_tap_changer1: leg1.ratio_tap_changer,
+            phase_tap_changer1: leg1.phase_tap_changer,
+            current_limits1: leg1.current_limits,
+            active_power_limits1: leg1.active_power_limits,
+            apparent_power_limits1: leg1.apparent_power_limits,
+            operational_limits_groups1: leg1.operational_limits_groups,
+            selected_operational_limits_group_id1: leg1.selected_operational_limits_group_id,
+            r2: leg2.r,
+            x2: leg2.x,
+            g2: leg2.g,
+            b2: leg2.b,
+            rated_u2: leg2.rated_u,
+            rated_s2: leg2.rated_s,
+            voltage_level_id2: leg2.voltage_level_id,
+            bus2: leg2.bus,
+            connectable_bus2: leg2.connectable_bus,
+            node2: leg2.node,
+            p2: leg2.p,
+            q2: leg2.q,
+            ratio_tap_changer2: leg2.ratio_tap_changer,
+            phase_tap_changer2: leg2.phase_tap_changer,
+            current_limits2: leg2.current_limits,
+            active_power_limits2: leg2.active_power_limits,
+            apparent_power_limits2: leg2.apparent_power_limits,
+            operational_limits_groups2: leg2.operational_limits_groups,
+            selected_operational_limits_group_id2: leg2.selected_operational_limits_group_id,
+            r3: leg3.r,
+            x3: leg3.x,
+            g3: leg3.g,
+            b3: leg3.b,
+            rated_u3: leg3.rated_u,
+            rated_s3: leg3.rated_s,
+            voltage_level_id3: leg3.voltage_level_id,
+            bus3: leg3.bus,
+            connectable_bus3: leg3.connectable_bus,
+            node3: leg3.node,
+            p3: leg3.p,
+            q3: leg3.q,
+            ratio_tap_changer3: leg3.ratio_tap_changer,
+            phase_tap_changer3: leg3.phase_tap_changer,
+            current_limits3: leg3.current_limits,
+            active_power_limits3: leg3.active_power_limits,
+            apparent_power_limits3: leg3.apparent_power_limits,
+            operational_limits_groups3: leg3.operational_limits_groups,
+            selected_operational_limits_group_id3: leg3.selected_operational_limits_group_id,
+            unknown_fields,
+        }
+    }
+}
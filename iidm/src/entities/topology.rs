@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Network, Node, TopologyKind, UnknownFields, VoltageLevel};
+
+/// Node connection that does not match the node-breaker topology of its voltage level
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NodeConnectionError {
+    #[error("Equipment {equipment} has no node in node-breaker voltage level {voltage_level}")]
+    MissingNode {
+        equipment: String,
+        voltage_level: String,
+    },
+    #[error(
+        "Equipment {equipment} references unknown node {node} of voltage level {voltage_level}"
+    )]
+    UnknownNode {
+        equipment: String,
+        voltage_level: String,
+        node: i32,
+    },
+}
+
+impl VoltageLevel {
+    /// Returns the node numbers declared by the node-breaker topology, `None` for bus-breaker
+    /// voltage levels
+    pub fn nodes(&self) -> Option<HashSet<i32>> {
+        if self.topology_kind != TopologyKind::NodeBreaker {
+            return None;
+        }
+        Some(
+            self.node_breaker_topology
+                .iter()
+                .flat_map(|topology| topology.nodes.iter().map(|node| node.id))
+                .collect(),
+        )
+    }
+}
+
+impl Network {
+    /// Checks that every equipment connected to a node-breaker voltage level references a
+    /// node declared in its topology.
+    ///
+    /// Equipments connected to bus-breaker voltage levels, or to voltage levels missing from
+    /// the network, are not checked.
+    pub fn validate_node_connections(&self) -> Vec<NodeConnectionError> {
+        let nodes: HashMap<&str, HashSet<i32>> = self
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.voltage_levels)
+            .filter_map(|voltage_level| Some((voltage_level.id.as_str(), voltage_level.nodes()?)))
+            .collect();
+
+        let mut errors = Vec::new();
+        self.for_each_node_connection(|equipment, voltage_level, node| {
+            let Some(nodes) = nodes.get(voltage_level) else {
+                return;
+            };
+            let error = match node {
+                None => NodeConnectionError::MissingNode {
+                    equipment: equipment.to_string(),
+                    voltage_level: voltage_level.to_string(),
+                },
+                Some(node) if !nodes.contains(&node) => NodeConnectionError::UnknownNode {
+                    equipment: equipment.to_string(),
+                    voltage_level: voltage_level.to_string(),
+                    node,
+                },
+                Some(_) => return,
+            };
+            errors.push(error);
+        });
+
+        errors
+    }
+
+    /// Declares in the node-breaker topologies the nodes referenced by their equipments,
+    /// for formats such as IIDM XML where nodes only exist through their connections.
+    pub fn declare_referenced_nodes(&mut self) {
+        let referenced = self.referenced_nodes();
+        for voltage_level in self
+            .substations
+            .iter_mut()
+            .flat_map(|substation| substation.voltage_levels.iter_mut())
+        {
+            let (Some(declared), Some(topology)) = (
+                voltage_level.nodes(),
+                voltage_level.node_breaker_topology.as_mut(),
+            ) else {
+                continue;
+            };
+            let mut missing: Vec<i32> = referenced
+                .get(&voltage_level.id)
+                .into_iter()
+                .flatten()
+                .filter(|node| !declared.contains(node))
+                .copied()
+                .collect();
+            missing.sort();
+            topology.nodes.extend(missing.into_iter().map(|id| Node {
+                id,
+                unknown_fields: UnknownFields::new(),
+            }));
+        }
+    }
+
+    /// Nodes referenced by the equipments of the network, by voltage level id
+    pub(crate) fn referenced_nodes(&self) -> HashMap<String, HashSet<i32>> {
+        let mut referenced: HashMap<String, HashSet<i32>> = HashMap::new();
+        self.for_each_node_connection(|_, voltage_level, node| {
+            if let Some(node) = node {
+                referenced
+                    .entry(voltage_level.to_string())
+                    .or_default()
+                    .insert(node);
+            }
+        });
+        referenced
+    }
+
+    /// Calls `f` with the equipment id, the voltage level id and the node of every
+    /// connection of the network
+    fn for_each_node_connection<F>(&self, mut f: F)
+    where
+        F: FnMut(&str, &str, Option<i32>),
+    {
+        for substation in &self.substations {
+            for voltage_level in &substation.voltage_levels {
+                let vl = voltage_level.id.as_str();
+                for generator in voltage_level.generators.iter().flatten() {
+                    f(&generator.id, vl, generator.node);
+                }
+                for load in voltage_level.loads.iter().flatten() {
+                    f(&load.id, vl, load.node);
+                }
+                for battery in voltage_level.batteries.iter().flatten() {
+                    f(&battery.id, vl, battery.node);
+                }
+                for busbar_section in voltage_level.busbar_sections.iter().flatten() {
+                    f(&busbar_section.id, vl, busbar_section.node);
+                }
+                if let Some(topology) = &voltage_level.node_breaker_topology {
+                    for switch in &topology.switches {
+                        f(&switch.id, vl, switch.node1);
+                        f(&switch.id, vl, switch.node2);
+                    }
+                    for connection in &topology.internal_connections {
+                        let equipment = format!("{}-{}", connection.node1, connection.node2);
+                        f(&equipment, vl, Some(connection.node1));
+                        f(&equipment, vl, Some(connection.node2));
+                    }
+                }
+            }
+
+            for transformer in &substation.two_windings_transformers {
+                f(
+                    &transformer.id,
+                    &transformer.voltage_level_id1,
+                    transformer.node1,
+                );
+                f(
+                    &transformer.id,
+                    &transformer.voltage_level_id2,
+                    transformer.node2,
+                );
+            }
+        }
+
+        for line in &self.lines {
+            f(&line.id, &line.voltage_level_id1, line.node1);
+            f(&line.id, &line.voltage_level_id2, line.node2);
+        }
+        for transformer in &self.three_windings_transformers {
+            for (_, leg) in transformer.legs() {
+                f(&transformer.id, &leg.voltage_level_id, leg.node);
+            }
+        }
+        for switch in &self.switches {
+            f(&switch.id, &switch.voltage_level_id, switch.node1);
+            f(&switch.id, &switch.voltage_level_id, switch.node2);
+        }
+        for shunt in &self.shunt_compensators {
+            f(&shunt.id, &shunt.voltage_level_id, shunt.node);
+        }
+        for compensator in &self.static_var_compensators {
+            f(
+                &compensator.id,
+                &compensator.voltage_level_id,
+                compensator.node,
+            );
+        }
+        for dangling_line in &self.dangling_lines {
+            f(
+                &dangling_line.id,
+                &dangling_line.voltage_level_id,
+                dangling_line.node,
+            );
+        }
+        for tie_line in &self.tie_lines {
+            for half in [&tie_line.dangling_line1, &tie_line.dangling_line2] {
+                f(&half.id, &half.voltage_level_id, half.node);
+            }
+        }
+        for hvdc_line in &self.hvdc_lines {
+            for station in [&hvdc_line.converter_station1, &hvdc_line.converter_station2] {
+                f(&station.id, &station.voltage_level_id, station.node);
+            }
+        }
+    }
+}
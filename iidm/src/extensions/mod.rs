@@ -13,6 +13,12 @@ pub trait Updatable: Sized + Serialize + for<'de> Deserialize<'de> {
     type Err;
 
     fn update(&mut self, updates: Self::Updater);
+
+    /// Checks that updates are consistent with the current state before they are applied
+    fn check_update(&self, _updates: &Self::Updater) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
     fn update_from_json(&mut self, json: &str) -> Result<(), Self::Err>;
 }
 
@@ -0,0 +1,249 @@
+//! Helpers shared by the bus-branch formats, whose networks are made of buses and branches:
+//! MATPOWER, PSS/E, IEEE CDF and UCTE-DEF.
+
+use serde_json::{json, Value};
+
+use crate::libs::version::DEFAULT_LIMITS_GROUP;
+use crate::{Generator, Network, ReactiveLimits};
+
+/// Equipments of a network that bus-branch formats can't express, described for the
+/// export warnings
+pub(crate) fn unsupported_equipments(network: &Network) -> impl Iterator<Item = String> + '_ {
+    network
+        .three_windings_transformers
+        .iter()
+        .map(|transformer| format!("Three windings transformer {}", transformer.id))
+        .chain(
+            network
+                .hvdc_lines
+                .iter()
+                .map(|line| format!("HVDC line {}", line.id)),
+        )
+        .chain(
+            network
+                .static_var_compensators
+                .iter()
+                .map(|svc| format!("Static VAR compensator {}", svc.id)),
+        )
+        .chain(
+            network
+                .substations
+                .iter()
+                .flat_map(|substation| &substation.voltage_levels)
+                .flat_map(|voltage_level| voltage_level.batteries.iter().flatten())
+                .map(|battery| format!("Battery {}", battery.id)),
+        )
+}
+
+/// Reactive power range `(min_q, max_q)` of a generator in formats without capability
+/// curves, `None` when it has no limits. Curves are exported as their bounds, which is
+/// reported in the warnings.
+pub(crate) fn reactive_bounds(
+    generator: &Generator,
+    warnings: &mut Vec<String>,
+) -> Option<(f64, f64)> {
+    let limits = generator.reactive_limits.as_ref()?;
+    if let ReactiveLimits::Curve(curve) = limits {
+        if curve.points.is_empty() {
+            warnings.push(format!(
+                "Reactive capability curve of generator {} has no points, it is not exported",
+                generator.id
+            ));
+        } else {
+            warnings.push(format!(
+                "Reactive capability curve of generator {} is exported as its bounds",
+                generator.id
+            ));
+        }
+    }
+    limits.bounds()
+}
+
+/// Buses merged in voltage levels or substations, by their index
+pub(crate) struct Groups {
+    parents: Vec<usize>,
+}
+
+impl Groups {
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+        }
+    }
+
+    /// Index of the first bus of the group of a bus
+    pub(crate) fn root(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        self.parents[index] = root;
+        root
+    }
+
+    pub(crate) fn join(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.root(a), self.root(b));
+        self.parents[a.max(b)] = a.min(b);
+    }
+}
+
+/// Nominal voltage of a bus from its base voltage, buses without base voltage being in kV
+pub(crate) fn nominal_v(base_kv: f64) -> f64 {
+    if base_kv > 0.0 {
+        base_kv
+    } else {
+        1.0
+    }
+}
+
+/// Sets the permanent current limit of a side of a branch from its rating, an apparent power
+/// in MVA, nothing being set for null ratings
+pub(crate) fn current_limits(object: &mut Value, side: &str, rating: f64, nominal_v: f64) {
+    if rating > 0.0 {
+        let permanent_limit = rating * 1000.0 / (3f64.sqrt() * nominal_v);
+        object[format!("operationalLimitsGroups{}", side)] = json!([{
+            "id": DEFAULT_LIMITS_GROUP,
+            "currentLimits": {"permanentLimit": permanent_limit, "temporaryLimits": []},
+        }]);
+        object[format!("selectedOperationalLimitsGroupId{}", side)] = json!(DEFAULT_LIMITS_GROUP);
+    }
+}
+
+/// Appends an item to an array of a JSON object
+pub(crate) fn push(object: &mut Value, key: &str, item: Value) {
+    if let Some(items) = object.get_mut(key).and_then(Value::as_array_mut) {
+        items.push(item);
+    }
+}
+
+/// Substations of the voltage levels of each bus, the ones of the buses in the same group being
+/// merged. Substations are named by `id` after the index of their first bus, and hold the
+/// transformers of the group of the bus they are given with.
+pub(crate) fn substations(
+    voltage_levels: Vec<Value>,
+    transformers: Vec<(usize, Value)>,
+    groups: &mut Groups,
+    id: impl Fn(usize) -> String,
+) -> Vec<Value> {
+    let mut substations: Vec<(usize, Value)> = Vec::new();
+    for (index, voltage_level) in voltage_levels.into_iter().enumerate() {
+        let root = groups.root(index);
+        let position = match substations.iter().position(|(other, _)| *other == root) {
+            Some(position) => position,
+            None => {
+                substations.push((
+                    root,
+                    json!({
+                        "id": id(root),
+                        "country": "",
+                        "tso": "",
+                        "geographicalTags": [],
+                        "voltageLevels": [],
+                        "twoWindingsTransformers": [],
+                    }),
+                ));
+                substations.len() - 1
+            }
+        };
+        push(&mut substations[position].1, "voltageLevels", voltage_level);
+    }
+
+    for (index, transformer) in transformers {
+        let root = groups.root(index);
+        if let Some((_, substation)) = substations.iter_mut().find(|(other, _)| *other == root) {
+            push(substation, "twoWindingsTransformers", transformer);
+        }
+    }
+    substations
+        .into_iter()
+        .map(|(_, substation)| substation)
+        .collect()
+}
+
+/// End of a branch of a bus-branch case
+pub(crate) struct BranchEnd {
+    pub(crate) voltage_level_id: String,
+    pub(crate) bus_id: String,
+    pub(crate) nominal_v: f64,
+}
+
+/// Branch of a bus-branch case, whose values are in per-unit of the case base power and of
+/// the base voltage of its second side
+pub(crate) struct PerUnitBranch {
+    pub(crate) from: BranchEnd,
+    pub(crate) to: BranchEnd,
+    pub(crate) r: f64,
+    pub(crate) x: f64,
+    pub(crate) b: f64,
+    /// Apparent power rating in MVA, null for branches without limits
+    pub(crate) rating: f64,
+    /// Off-nominal turns ratio of the first side, null for nominal ratios
+    pub(crate) ratio: f64,
+    /// Phase shift of the first side voltage, in degrees
+    pub(crate) shift: f64,
+    pub(crate) in_service: bool,
+}
+
+impl PerUnitBranch {
+    fn base_impedance(&self, base_mva: f64) -> f64 {
+        self.to.nominal_v.powi(2) / base_mva
+    }
+
+    fn object(&self, id: String, base_mva: f64) -> Value {
+        let base_impedance = self.base_impedance(base_mva);
+        let mut object = json!({
+            "id": id,
+            "r": self.r * base_impedance,
+            "x": self.x * base_impedance,
+            "voltageLevelId1": self.from.voltage_level_id,
+            "connectableBus1": self.from.bus_id,
+            "voltageLevelId2": self.to.voltage_level_id,
+            "connectableBus2": self.to.bus_id,
+        });
+        if self.in_service {
+            object["bus1"] = json!(self.from.bus_id);
+            object["bus2"] = json!(self.to.bus_id);
+        }
+        current_limits(&mut object, "1", self.rating, self.from.nominal_v);
+        current_limits(&mut object, "2", self.rating, self.to.nominal_v);
+        object
+    }
+
+    /// Line whose charging susceptance is split between both sides
+    pub(crate) fn line(&self, id: String, base_mva: f64) -> Value {
+        let b = self.b / self.base_impedance(base_mva) / 2.0;
+        let mut object = self.object(id, base_mva);
+        for key in ["g1", "g2"] {
+            object[key] = json!(0.0);
+        }
+        for key in ["b1", "b2"] {
+            object[key] = json!(b);
+        }
+        object
+    }
+
+    /// Two windings transformer whose off-nominal ratio is held by the rated voltage of its
+    /// first side, a phase shift being a fixed phase tap changer
+    pub(crate) fn two_windings_transformer(&self, id: String, base_mva: f64) -> Value {
+        let mut object = self.object(id.clone(), base_mva);
+        object["g"] = json!(0.0);
+        object["b"] = json!(self.b / self.base_impedance(base_mva));
+        let ratio = if self.ratio == 0.0 { 1.0 } else { self.ratio };
+        object["ratedU1"] = json!(self.from.nominal_v * ratio);
+        object["ratedU2"] = json!(self.to.nominal_v);
+        // Shifts are the angle of the first side voltage, IIDM angles being added to it
+        if self.shift != 0.0 {
+            object["phaseTapChanger"] = json!({
+                "regulating": false,
+                "lowTapPosition": 0,
+                "tapPosition": 0,
+                "regulationMode": "FIXED_TAP",
+                "regulationValue": 0.0,
+                "targetDeadband": 0.0,
+                "terminalRef": {"id": id, "side": "ONE"},
+                "steps": [{"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 1.0, "alpha": -self.shift}],
+            });
+        }
+        object
+    }
+}
@@ -0,0 +1,29 @@
+//! Common Grid Model Exchange Standard (CGMES), the CIM 16 and CIM 100 RDF/XML profiles
+//! exchanged between European TSOs.
+//!
+//! The equipment (EQ), steady state hypothesis (SSH), topology (TP) and state variables (SV)
+//! profiles of a model are merged by the identifiers of their objects. Voltage levels are
+//! read in node-breaker topology, each connectivity node and each equipment terminal having
+//! its own node. The classes without IIDM equivalent are counted in the import report.
+//! Models exchanged as zip archives of their profiles are read by [`from_cgmes_zip`].
+
+mod model;
+mod reader;
+
+pub use reader::{from_cgmes, from_cgmes_zip, CgmesImport};
+
+/// Error while reading CGMES profiles, with the index of the faulty document
+#[derive(Debug, thiserror::Error)]
+pub enum CgmesError {
+    #[error("Document {document}: {error}")]
+    Xml {
+        document: usize,
+        error: roxmltree::Error,
+    },
+    #[error("Document {document}: {message}")]
+    Syntax { document: usize, message: String },
+    #[error("Invalid archive: {0}")]
+    Archive(#[from] std::io::Error),
+    #[error("Invalid network: {0}")]
+    Network(#[from] serde_json::Error),
+}
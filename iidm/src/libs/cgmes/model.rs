@@ -0,0 +1,168 @@
+//! Objects of CIM RDF/XML documents, merged by their identifiers across profiles
+
+use std::collections::HashMap;
+
+use super::CgmesError;
+
+const RDF_NAMESPACE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+enum Property {
+    Literal(String),
+    Resource(String),
+}
+
+/// Object of the model, with the properties given by every profile describing it
+pub(super) struct Object {
+    pub class: String,
+    properties: HashMap<String, Property>,
+}
+
+impl Object {
+    /// Literal value of a property, named after its class as in `IdentifiedObject.name`
+    pub fn text(&self, property: &str) -> Option<&str> {
+        match self.properties.get(property)? {
+            Property::Literal(value) => Some(value),
+            Property::Resource(_) => None,
+        }
+    }
+
+    pub fn number(&self, property: &str) -> Option<f64> {
+        self.text(property)?.parse().ok()
+    }
+
+    pub fn boolean(&self, property: &str) -> Option<bool> {
+        self.text(property)?.parse().ok()
+    }
+
+    /// Identifier of the object referenced by a property
+    pub fn resource(&self, property: &str) -> Option<&str> {
+        match self.properties.get(property)? {
+            Property::Resource(id) => Some(id),
+            Property::Literal(_) => None,
+        }
+    }
+
+    /// Value of an enumeration property, e.g. `voltage` for `RegulatingControlModeKind.voltage`
+    pub fn enumeration(&self, property: &str) -> Option<&str> {
+        let value = self.resource(property)?;
+        Some(value.rsplit('.').next().unwrap_or(value))
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.text("IdentifiedObject.name")
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Model {
+    objects: HashMap<String, Object>,
+    // Identifiers in the order of their first description
+    order: Vec<String>,
+    // Objects referencing another one, by property and referenced identifier
+    references: HashMap<(String, String), Vec<String>>,
+}
+
+impl Model {
+    pub fn parse(documents: &[&str]) -> Result<Self, CgmesError> {
+        let mut model = Self::default();
+        for (document, text) in documents.iter().enumerate() {
+            let xml = roxmltree::Document::parse(text)
+                .map_err(|error| CgmesError::Xml { document, error })?;
+            let root = xml.root_element();
+            if root.tag_name().name() != "RDF" || root.tag_name().namespace() != Some(RDF_NAMESPACE)
+            {
+                return Err(CgmesError::Syntax {
+                    document,
+                    message: format!("unexpected root element {}", root.tag_name().name()),
+                });
+            }
+            for element in root.children().filter(|node| node.is_element()) {
+                let Some(id) = element
+                    .attribute((RDF_NAMESPACE, "ID"))
+                    .or_else(|| element.attribute((RDF_NAMESPACE, "about")))
+                else {
+                    return Err(CgmesError::Syntax {
+                        document,
+                        message: format!(
+                            "{} without rdf:ID nor rdf:about",
+                            element.tag_name().name()
+                        ),
+                    });
+                };
+                let id = identifier(id);
+                if !model.objects.contains_key(&id) {
+                    model.order.push(id.clone());
+                }
+                let object = model.objects.entry(id.clone()).or_insert_with(|| Object {
+                    class: element.tag_name().name().to_string(),
+                    properties: HashMap::new(),
+                });
+                for property in element.children().filter(|node| node.is_element()) {
+                    let name = property.tag_name().name().to_string();
+                    let value = match property.attribute((RDF_NAMESPACE, "resource")) {
+                        Some(resource) => {
+                            let resource = identifier(resource);
+                            model
+                                .references
+                                .entry((name.clone(), resource.clone()))
+                                .or_default()
+                                .push(id.clone());
+                            Property::Resource(resource)
+                        }
+                        None => Property::Literal(
+                            property.text().unwrap_or_default().trim().to_string(),
+                        ),
+                    };
+                    object.properties.insert(name, value);
+                }
+            }
+        }
+        Ok(model)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Object> {
+        self.objects.get(id)
+    }
+
+    /// Objects of some classes, in the order of the documents
+    pub fn of_class<'a>(
+        &'a self,
+        classes: &'a [&str],
+    ) -> impl Iterator<Item = (&'a str, &'a Object)> + 'a {
+        self.order.iter().filter_map(move |id| {
+            let object = &self.objects[id];
+            classes
+                .contains(&object.class.as_str())
+                .then_some((id.as_str(), object))
+        })
+    }
+
+    /// Objects whose property references an object, in the order of the documents
+    pub fn referencing<'a>(
+        &'a self,
+        property: &str,
+        id: &str,
+    ) -> impl Iterator<Item = (&'a str, &'a Object)> + 'a {
+        self.references
+            .get(&(property.to_string(), id.to_string()))
+            .into_iter()
+            .flatten()
+            .filter_map(|id| Some((id.as_str(), self.objects.get(id)?)))
+    }
+
+    /// Number of objects of each class
+    pub fn classes(&self) -> HashMap<&str, usize> {
+        let mut classes = HashMap::new();
+        for object in self.objects.values() {
+            *classes.entry(object.class.as_str()).or_default() += 1;
+        }
+        classes
+    }
+}
+
+// References are written `#_id` or `urn:uuid:id`, enumeration values are prefixed with the
+// namespace of their class
+fn identifier(value: &str) -> String {
+    let value = value.rsplit('#').next().unwrap_or(value);
+    value.strip_prefix("urn:uuid:").unwrap_or(value).to_string()
+}
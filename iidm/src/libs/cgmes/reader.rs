@@ -0,0 +1,1238 @@
+//! Conversion of the objects of CGMES profiles to IIDM equipments
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use super::model::{Model, Object};
+use super::CgmesError;
+use crate::libs::compression::archive_entries;
+use crate::libs::version::IidmVersion;
+use crate::Network;
+
+/// Network read from CGMES profiles, with the objects that could not be represented
+pub struct CgmesImport {
+    pub network: Network,
+    /// Number of objects of each class without IIDM equivalent
+    pub unmapped_classes: BTreeMap<String, usize>,
+    pub warnings: Vec<String>,
+}
+
+/// Reads a network from the RDF/XML documents of the profiles of a CGMES model, given in
+/// any order.
+///
+/// Equipments whose terminals are not in a voltage level of the model are skipped, except
+/// the line segments ending at a boundary node, read as dangling lines whose pairing key is
+/// the boundary node.
+pub fn from_cgmes(id: &str, profiles: &[&str]) -> Result<CgmesImport, CgmesError> {
+    let model = Model::parse(profiles)?;
+    let mut converter = Converter::new(&model);
+    let network = converter.convert(id);
+    let unmapped_classes = model
+        .classes()
+        .into_iter()
+        .filter(|(class, _)| !MAPPED_CLASSES.contains(class))
+        .map(|(class, count)| (class.to_string(), count))
+        .collect();
+    Ok(CgmesImport {
+        network: serde_json::from_value(network)?,
+        unmapped_classes,
+        warnings: converter.warnings,
+    })
+}
+
+/// Reads a network from a zip archive of the profiles of a CGMES model, as they are exchanged.
+///
+/// Every `.xml` entry is read as a profile, the profiles archived or compressed on their own
+/// being decompressed.
+pub fn from_cgmes_zip(id: &str, bytes: &[u8]) -> Result<CgmesImport, CgmesError> {
+    let entries = archive_entries(bytes, &[".xml"])?;
+    if entries.is_empty() {
+        return Err(
+            io::Error::new(io::ErrorKind::InvalidData, "zip archive without .xml entry").into(),
+        );
+    }
+    let profiles = entries
+        .iter()
+        .map(|(name, document)| {
+            std::str::from_utf8(document).map_err(|error| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", name, error))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    from_cgmes(id, &profiles)
+}
+
+const SWITCH_CLASSES: &[&str] = &[
+    "Switch",
+    "Breaker",
+    "Disconnector",
+    "LoadBreakSwitch",
+    "Fuse",
+    "GroundDisconnector",
+    "Jumper",
+];
+
+const LOAD_CLASSES: &[&str] = &[
+    "EnergyConsumer",
+    "ConformLoad",
+    "NonConformLoad",
+    "StationSupply",
+];
+
+const SHUNT_CLASSES: &[&str] = &["LinearShuntCompensator", "NonlinearShuntCompensator"];
+
+const PHASE_TAP_CHANGER_CLASSES: &[&str] = &[
+    "PhaseTapChangerLinear",
+    "PhaseTapChangerSymmetrical",
+    "PhaseTapChangerAsymmetrical",
+    "PhaseTapChangerTabular",
+];
+
+/// Generating units and the energy source of their machines
+const GENERATING_UNIT_CLASSES: &[(&str, &str)] = &[
+    ("GeneratingUnit", "OTHER"),
+    ("ThermalGeneratingUnit", "THERMAL"),
+    ("HydroGeneratingUnit", "HYDRO"),
+    ("NuclearGeneratingUnit", "NUCLEAR"),
+    ("WindGeneratingUnit", "WIND"),
+    ("SolarGeneratingUnit", "SOLAR"),
+];
+
+/// Classes converted to IIDM, or read while converting the objects referencing them
+const MAPPED_CLASSES: &[&str] = &[
+    "FullModel",
+    "GeographicalRegion",
+    "SubGeographicalRegion",
+    "Substation",
+    "BaseVoltage",
+    "VoltageLevel",
+    "Bay",
+    "ConnectivityNode",
+    "TopologicalNode",
+    "Terminal",
+    "BusbarSection",
+    "Switch",
+    "Breaker",
+    "Disconnector",
+    "LoadBreakSwitch",
+    "Fuse",
+    "GroundDisconnector",
+    "Jumper",
+    "EnergyConsumer",
+    "ConformLoad",
+    "NonConformLoad",
+    "StationSupply",
+    "SynchronousMachine",
+    "GeneratingUnit",
+    "ThermalGeneratingUnit",
+    "HydroGeneratingUnit",
+    "NuclearGeneratingUnit",
+    "WindGeneratingUnit",
+    "SolarGeneratingUnit",
+    "RegulatingControl",
+    "TapChangerControl",
+    "LinearShuntCompensator",
+    "NonlinearShuntCompensator",
+    "NonlinearShuntCompensatorPoint",
+    "ACLineSegment",
+    "PowerTransformer",
+    "PowerTransformerEnd",
+    "RatioTapChanger",
+    "RatioTapChangerTable",
+    "RatioTapChangerTablePoint",
+    "PhaseTapChangerLinear",
+    "PhaseTapChangerSymmetrical",
+    "PhaseTapChangerAsymmetrical",
+    "PhaseTapChangerTabular",
+    "PhaseTapChangerTable",
+    "PhaseTapChangerTablePoint",
+    "OperationalLimitSet",
+    "OperationalLimitType",
+    "CurrentLimit",
+    "SvVoltage",
+    "SvPowerFlow",
+    "SvTapStep",
+    "SvShuntCompensatorSections",
+    "SvStatus",
+];
+
+const SIDES: [&str; 3] = ["ONE", "TWO", "THREE"];
+
+struct VoltageLevel<'a> {
+    id: &'a str,
+    substation: usize,
+    nominal_v: f64,
+    object: Value,
+    // Number of nodes allocated so far
+    nodes: i32,
+}
+
+impl VoltageLevel<'_> {
+    fn node(&mut self) -> i32 {
+        self.nodes += 1;
+        self.nodes - 1
+    }
+
+    fn connect(&mut self, node1: i32, node2: i32) {
+        push(
+            &mut self.object["nodeBreakerTopology"],
+            "internalConnections",
+            json!({"node1": node1, "node2": node2}),
+        );
+    }
+}
+
+/// Tap of a transformer end, as the ratio and the angle added to its rated voltage with the
+/// deviations in percent of its impedance
+struct Tap {
+    ratio: f64,
+    angle: f64,
+    r: f64,
+    x: f64,
+    g: f64,
+    b: f64,
+}
+
+/// End of a power transformer connected to a voltage level
+struct End<'a> {
+    id: &'a str,
+    object: &'a Object,
+    terminal: &'a str,
+    voltage_level: usize,
+    node: i32,
+    rated_u: f64,
+}
+
+struct Converter<'a> {
+    model: &'a Model,
+    substations: Vec<Value>,
+    voltage_levels: Vec<VoltageLevel<'a>>,
+    indexes: HashMap<&'a str, usize>,
+    // Voltage level and node of the connectivity and topological nodes
+    nodes: HashMap<&'a str, (usize, i32)>,
+    // Transformers with the index of their substation
+    transformers: Vec<(usize, Value)>,
+    three_windings_transformers: Vec<Value>,
+    lines: Vec<Value>,
+    dangling_lines: Vec<Value>,
+    shunts: Vec<Value>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(model: &'a Model) -> Self {
+        Self {
+            model,
+            substations: Vec::new(),
+            voltage_levels: Vec::new(),
+            indexes: HashMap::new(),
+            nodes: HashMap::new(),
+            transformers: Vec::new(),
+            three_windings_transformers: Vec::new(),
+            lines: Vec::new(),
+            dangling_lines: Vec::new(),
+            shunts: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn convert(&mut self, id: &str) -> Value {
+        let model = self.model;
+        let mut substations = HashMap::new();
+        for (id, substation) in model.of_class(&["Substation"]) {
+            substations.insert(id, self.substations.len());
+            self.substations.push(self.substation(id, substation));
+        }
+        for (id, voltage_level) in model.of_class(&["VoltageLevel"]) {
+            let Some(substation) = voltage_level
+                .resource("VoltageLevel.Substation")
+                .and_then(|substation| substations.get(substation))
+            else {
+                self.warnings
+                    .push(format!("Voltage level {} is not in a substation", id));
+                continue;
+            };
+            self.voltage_level(id, voltage_level, *substation);
+        }
+
+        for (id, busbar_section) in model.of_class(&["BusbarSection"]) {
+            self.busbar_section(id, busbar_section);
+        }
+        for (id, switch) in model.of_class(SWITCH_CLASSES) {
+            self.switch(id, switch);
+        }
+        for (id, load) in model.of_class(LOAD_CLASSES) {
+            self.load(id, load);
+        }
+        for (id, machine) in model.of_class(&["SynchronousMachine"]) {
+            self.generator(id, machine);
+        }
+        for (id, shunt) in model.of_class(SHUNT_CLASSES) {
+            self.shunt(id, shunt);
+        }
+        for (id, segment) in model.of_class(&["ACLineSegment"]) {
+            self.line(id, segment);
+        }
+        for (id, transformer) in model.of_class(&["PowerTransformer"]) {
+            self.transformer(id, transformer);
+        }
+
+        for mut voltage_level in std::mem::take(&mut self.voltage_levels) {
+            voltage_level.object["nodeBreakerTopology"]["nodes"] = (0..voltage_level.nodes)
+                .map(|node| json!({"id": node}))
+                .collect();
+            push(
+                &mut self.substations[voltage_level.substation],
+                "voltageLevels",
+                voltage_level.object,
+            );
+        }
+        for (substation, transformer) in std::mem::take(&mut self.transformers) {
+            push(
+                &mut self.substations[substation],
+                "twoWindingsTransformers",
+                transformer,
+            );
+        }
+
+        // The scenario time is the instant of the state variables
+        let case_date = model
+            .of_class(&["FullModel"])
+            .filter_map(|(_, header)| header.text("Model.scenarioTime"))
+            .find_map(|time| DateTime::parse_from_rfc3339(time).ok())
+            .unwrap_or(DateTime::UNIX_EPOCH.fixed_offset());
+        json!({
+            "version": IidmVersion::LATEST.to_string(),
+            "id": id,
+            "caseDate": case_date,
+            "forecastDistance": 0,
+            "sourceFormat": "CGMES",
+            "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+            "substations": std::mem::take(&mut self.substations),
+            "lines": std::mem::take(&mut self.lines),
+            "threeWindingsTransformers": std::mem::take(&mut self.three_windings_transformers),
+            "shuntCompensators": std::mem::take(&mut self.shunts),
+            "danglingLines": std::mem::take(&mut self.dangling_lines),
+        })
+    }
+
+    // Countries are the geographical regions named after an ISO 3166 code
+    fn substation(&self, id: &str, substation: &Object) -> Value {
+        let country = substation
+            .resource("Substation.Region")
+            .and_then(|region| self.model.get(region))
+            .and_then(|region| region.resource("SubGeographicalRegion.Region"))
+            .and_then(|region| self.model.get(region))
+            .and_then(Object::name)
+            .filter(|name| name.len() == 2 && name.chars().all(|c| c.is_ascii_uppercase()))
+            .unwrap_or_default();
+        let mut object = json!({
+            "id": id,
+            "country": country,
+            "tso": "",
+            "geographicalTags": [],
+            "voltageLevels": [],
+            "twoWindingsTransformers": [],
+        });
+        name(&mut object, substation);
+        object
+    }
+
+    fn voltage_level(&mut self, id: &'a str, voltage_level: &Object, substation: usize) {
+        let nominal_v = voltage_level
+            .resource("VoltageLevel.BaseVoltage")
+            .and_then(|base_voltage| self.model.get(base_voltage))
+            .and_then(|base_voltage| base_voltage.number("BaseVoltage.nominalVoltage"))
+            .unwrap_or(1.0);
+        let mut object = json!({
+            "id": id,
+            "nominalV": nominal_v,
+            "topologyKind": "NODE_BREAKER",
+            "generators": [],
+            "loads": [],
+            "busbar_sections": [],
+            "nodeBreakerTopology": {"nodes": [], "switches": [], "internalConnections": []},
+        });
+        name(&mut object, voltage_level);
+        if let Some(low) = voltage_level.number("VoltageLevel.lowVoltageLimit") {
+            object["lowVoltageLimit"] = json!(low);
+        }
+        if let Some(high) = voltage_level.number("VoltageLevel.highVoltageLimit") {
+            object["highVoltageLimit"] = json!(high);
+        }
+        self.indexes.insert(id, self.voltage_levels.len());
+        self.voltage_levels.push(VoltageLevel {
+            id,
+            substation,
+            nominal_v,
+            object,
+            nodes: 0,
+        });
+    }
+
+    /// Voltage level and node of a connectivity or topological node, contained by a voltage
+    /// level or by one of its bays
+    fn node(&mut self, id: &'a str) -> Option<(usize, i32)> {
+        if let Some(point) = self.nodes.get(id) {
+            return Some(*point);
+        }
+        let object = self.model.get(id)?;
+        let mut container = object
+            .resource("ConnectivityNode.ConnectivityNodeContainer")
+            .or_else(|| object.resource("TopologicalNode.ConnectivityNodeContainer"))?;
+        if let Some(voltage_level) = self
+            .model
+            .get(container)
+            .filter(|container| container.class == "Bay")
+            .and_then(|bay| bay.resource("Bay.VoltageLevel"))
+        {
+            container = voltage_level;
+        }
+        let index = *self.indexes.get(container)?;
+        let point = (index, self.voltage_levels[index].node());
+        self.nodes.insert(id, point);
+        Some(point)
+    }
+
+    // Bus-branch models only have topological nodes
+    fn terminal_node(&mut self, terminal: &'a Object) -> Option<(usize, i32)> {
+        if let Some(point) = terminal
+            .resource("Terminal.ConnectivityNode")
+            .and_then(|node| self.node(node))
+        {
+            return Some(point);
+        }
+        terminal
+            .resource("Terminal.TopologicalNode")
+            .and_then(|node| self.node(node))
+    }
+
+    /// Voltage level and node of an equipment terminal, linked to the node of the terminal
+    /// unless the terminal or its equipment is disconnected
+    fn connect(&mut self, terminal_id: &str) -> Option<(usize, i32)> {
+        let model = self.model;
+        let terminal = model.get(terminal_id)?;
+        let (index, point) = self.terminal_node(terminal)?;
+        let voltage_level = &mut self.voltage_levels[index];
+        let node = voltage_level.node();
+        let in_service = terminal
+            .resource("Terminal.ConductingEquipment")
+            .and_then(|equipment| {
+                model
+                    .referencing("SvStatus.ConductingEquipment", equipment)
+                    .next()
+            })
+            .and_then(|(_, status)| status.boolean("SvStatus.inService"))
+            .unwrap_or(true);
+        if in_service && terminal.boolean("ACDCTerminal.connected").unwrap_or(true) {
+            voltage_level.connect(point, node);
+        }
+        Some((index, node))
+    }
+
+    /// Terminals of an equipment, by sequence number
+    fn terminals(&self, equipment: &str) -> Vec<&'a str> {
+        let mut terminals: Vec<_> = self
+            .model
+            .referencing("Terminal.ConductingEquipment", equipment)
+            .collect();
+        terminals.sort_by_key(|(_, terminal)| {
+            terminal
+                .number("ACDCTerminal.sequenceNumber")
+                .unwrap_or_default() as i64
+        });
+        terminals.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Sets the flow of the state variables at a terminal
+    fn flow(&self, object: &mut Value, side: &str, terminal: &str) {
+        if let Some((_, flow)) = self
+            .model
+            .referencing("SvPowerFlow.Terminal", terminal)
+            .next()
+        {
+            if let Some(p) = flow.number("SvPowerFlow.p") {
+                object[format!("p{}", side)] = json!(p);
+            }
+            if let Some(q) = flow.number("SvPowerFlow.q") {
+                object[format!("q{}", side)] = json!(q);
+            }
+        }
+    }
+
+    /// Reference to the branch side or the injection of a terminal
+    fn terminal_ref(&self, terminal: &str) -> Option<Value> {
+        let equipment = self
+            .model
+            .get(terminal)?
+            .resource("Terminal.ConductingEquipment")?;
+        let side = match self.model.get(equipment)?.class.as_str() {
+            "PowerTransformer" => self
+                .model
+                .referencing("TransformerEnd.Terminal", terminal)
+                .next()
+                .and_then(|(_, end)| end.number("TransformerEnd.endNumber")),
+            "ACLineSegment" => self
+                .terminals(equipment)
+                .iter()
+                .position(|other| *other == terminal)
+                .map(|position| position as f64 + 1.0),
+            _ => None,
+        };
+        let side = side.and_then(|side| SIDES.get((side as usize).checked_sub(1)?));
+        Some(match side {
+            Some(side) => json!({"id": equipment, "side": side}),
+            None => json!({"id": equipment}),
+        })
+    }
+
+    /// Sets the current limits of the operational limit sets of a terminal, the first set
+    /// being selected
+    fn limits(&mut self, object: &mut Value, side: &str, terminal: &str) {
+        let model = self.model;
+        let mut groups = Vec::new();
+        for (set_id, _) in model.referencing("OperationalLimitSet.Terminal", terminal) {
+            let mut permanent_limit: Option<f64> = None;
+            let mut temporary_limits = Vec::new();
+            for (limit_id, limit) in
+                model.referencing("OperationalLimit.OperationalLimitSet", set_id)
+            {
+                // Current limits are given by normal values since CIM 100
+                let Some(value) = limit
+                    .number("CurrentLimit.value")
+                    .or_else(|| limit.number("CurrentLimit.normalValue"))
+                else {
+                    continue;
+                };
+                let Some(limit_type) = limit
+                    .resource("OperationalLimit.OperationalLimitType")
+                    .and_then(|limit_type| model.get(limit_type))
+                else {
+                    continue;
+                };
+                let kind = limit_type
+                    .enumeration("OperationalLimitType.limitType")
+                    .or_else(|| limit_type.enumeration("OperationalLimitType.kind"));
+                match kind {
+                    Some("patl") => {
+                        permanent_limit =
+                            Some(permanent_limit.map_or(value, |other| other.min(value)))
+                    }
+                    Some("tatl") => {
+                        let duration = limit_type
+                            .number("OperationalLimitType.acceptableDuration")
+                            .map_or(i32::MAX, |duration| duration as i32);
+                        let name = limit
+                            .name()
+                            .or_else(|| limit_type.name())
+                            .unwrap_or(limit_id);
+                        temporary_limits.push((duration, name, value));
+                    }
+                    _ => {}
+                }
+            }
+            let Some(permanent_limit) = permanent_limit else {
+                if !temporary_limits.is_empty() {
+                    self.warnings.push(format!(
+                        "Operational limit set {} has no permanent current limit",
+                        set_id
+                    ));
+                }
+                continue;
+            };
+            temporary_limits.sort_by_key(|(duration, ..)| std::cmp::Reverse(*duration));
+            groups.push(json!({
+                "id": set_id,
+                "currentLimits": {
+                    "permanentLimit": permanent_limit,
+                    "temporaryLimits": temporary_limits
+                        .into_iter()
+                        .map(|(duration, name, value)| json!({
+                            "name": name,
+                            "acceptableDuration": duration,
+                            "value": value,
+                        }))
+                        .collect::<Vec<_>>(),
+                },
+            }));
+        }
+        if let Some(selected) = groups.first().map(|group| group["id"].clone()) {
+            object[format!("operationalLimitsGroups{}", side)] = json!(groups);
+            object[format!("selectedOperationalLimitsGroupId{}", side)] = selected;
+        }
+    }
+
+    /// First terminal of an injection, connected to a voltage level
+    fn injection(&mut self, id: &str) -> Option<(&'a str, usize, i32)> {
+        let terminal = self.terminals(id).first().copied();
+        match terminal.and_then(|terminal| Some((terminal, self.connect(terminal)?))) {
+            Some((terminal, (index, node))) => Some((terminal, index, node)),
+            None => {
+                self.warnings
+                    .push(format!("Equipment {} is not in a voltage level", id));
+                None
+            }
+        }
+    }
+
+    fn busbar_section(&mut self, id: &str, busbar_section: &Object) {
+        let Some((terminal, index, node)) = self.injection(id) else {
+            return;
+        };
+        let mut object = json!({
+            "id": id,
+            "name": busbar_section.name().unwrap_or(id),
+            "node": node,
+        });
+        if let Some((_, voltage)) = self
+            .model
+            .get(terminal)
+            .and_then(|terminal| terminal.resource("Terminal.TopologicalNode"))
+            .and_then(|node| {
+                self.model
+                    .referencing("SvVoltage.TopologicalNode", node)
+                    .next()
+            })
+        {
+            object["v"] = json!(voltage.number("SvVoltage.v"));
+            object["angle"] = json!(voltage.number("SvVoltage.angle"));
+        }
+        push(
+            &mut self.voltage_levels[index].object,
+            "busbar_sections",
+            object,
+        );
+    }
+
+    // Switches directly link the nodes of their terminals
+    fn switch(&mut self, id: &str, switch: &Object) {
+        let model = self.model;
+        let mut points = Vec::new();
+        for terminal in self.terminals(id) {
+            if let Some(point) = model
+                .get(terminal)
+                .and_then(|terminal| self.terminal_node(terminal))
+            {
+                points.push(point);
+            }
+        }
+        let [(index, node1), (other, node2)] = points[..] else {
+            self.warnings
+                .push(format!("Switch {} does not have two terminals", id));
+            return;
+        };
+        if index != other {
+            self.warnings.push(format!(
+                "Switch {} links two voltage levels and is not supported",
+                id
+            ));
+            return;
+        }
+        let kind = match switch.class.as_str() {
+            "Breaker" => "BREAKER",
+            "Disconnector" | "GroundDisconnector" => "DISCONNECTOR",
+            _ => "LOAD_BREAK_SWITCH",
+        };
+        let voltage_level = &mut self.voltage_levels[index];
+        let mut object = json!({
+            "id": id,
+            "kind": kind,
+            "open": switch
+                .boolean("Switch.open")
+                .or_else(|| switch.boolean("Switch.normalOpen"))
+                .unwrap_or(false),
+            "retained": switch.boolean("Switch.retained").unwrap_or(false),
+            "node1": node1,
+            "node2": node2,
+            "voltageLevelId": voltage_level.id,
+        });
+        name(&mut object, switch);
+        push(
+            &mut voltage_level.object["nodeBreakerTopology"],
+            "switches",
+            object,
+        );
+    }
+
+    fn load(&mut self, id: &str, load: &Object) {
+        let Some((terminal, index, node)) = self.injection(id) else {
+            return;
+        };
+        let mut object = json!({
+            "id": id,
+            "loadType": if load.class == "StationSupply" { "AUXILIARY" } else { "UNDEFINED" },
+            "p0": load.number("EnergyConsumer.p").unwrap_or_default(),
+            "q0": load.number("EnergyConsumer.q").unwrap_or_default(),
+            "node": node,
+        });
+        name(&mut object, load);
+        self.flow(&mut object, "", terminal);
+        push(&mut self.voltage_levels[index].object, "loads", object);
+    }
+
+    /// Synchronous machines, whose powers are given in load sign convention
+    fn generator(&mut self, id: &str, machine: &Object) {
+        let model = self.model;
+        let Some((terminal, index, node)) = self.injection(id) else {
+            return;
+        };
+        let unit = machine
+            .resource("RotatingMachine.GeneratingUnit")
+            .and_then(|unit| model.get(unit));
+        let energy_source = unit
+            .and_then(|unit| {
+                GENERATING_UNIT_CLASSES
+                    .iter()
+                    .find(|(class, _)| *class == unit.class)
+            })
+            .map_or("OTHER", |(_, source)| source);
+        let control = machine
+            .resource("RegulatingCondEq.RegulatingControl")
+            .and_then(|control| model.get(control))
+            .filter(|control| control.enumeration("RegulatingControl.mode") == Some("voltage"));
+        let voltage_regulator_on = machine
+            .boolean("RegulatingCondEq.controlEnabled")
+            .unwrap_or(false)
+            && control.is_some_and(|control| {
+                control.boolean("RegulatingControl.enabled").unwrap_or(true)
+            });
+        let nominal_v = self.voltage_levels[index].nominal_v;
+        let target_v = control
+            .and_then(|control| control.number("RegulatingControl.targetValue"))
+            .filter(|target| *target > 0.0)
+            .unwrap_or(nominal_v);
+
+        let mut object = json!({
+            "id": id,
+            "energySource": energy_source,
+            "minP": unit
+                .and_then(|unit| unit.number("GeneratingUnit.minOperatingP"))
+                .unwrap_or(-9999.0),
+            "maxP": unit
+                .and_then(|unit| unit.number("GeneratingUnit.maxOperatingP"))
+                .unwrap_or(9999.0),
+            "voltageRegulatorOn": voltage_regulator_on,
+            "targetP": -machine.number("RotatingMachine.p").unwrap_or_default(),
+            "targetV": target_v,
+            "targetQ": -machine.number("RotatingMachine.q").unwrap_or_default(),
+            "node": node,
+            "minMaxReactiveLimits": {
+                "minQ": machine.number("SynchronousMachine.minQ").unwrap_or(-9999.0),
+                "maxQ": machine.number("SynchronousMachine.maxQ").unwrap_or(9999.0),
+            },
+        });
+        name(&mut object, machine);
+        if let Some(regulating_terminal) = control
+            .and_then(|control| control.resource("RegulatingControl.Terminal"))
+            .filter(|regulated| *regulated != terminal)
+            .and_then(|regulated| self.terminal_ref(regulated))
+        {
+            object["regulatingTerminal"] = regulating_terminal;
+        }
+        self.flow(&mut object, "", terminal);
+        push(&mut self.voltage_levels[index].object, "generators", object);
+    }
+
+    /// Shunt compensators, non-linear ones giving the admittance of each of their sections
+    fn shunt(&mut self, id: &str, shunt: &Object) {
+        let model = self.model;
+        let Some((terminal, index, node)) = self.injection(id) else {
+            return;
+        };
+        let section_count = model
+            .referencing("SvShuntCompensatorSections.ShuntCompensator", id)
+            .next()
+            .and_then(|(_, sections)| sections.number("SvShuntCompensatorSections.sections"))
+            .or_else(|| shunt.number("ShuntCompensator.sections"))
+            .or_else(|| shunt.number("ShuntCompensator.normalSections"))
+            .unwrap_or_default()
+            .round() as i64;
+        let mut object = if shunt.class == "LinearShuntCompensator" {
+            json!({
+                "id": id,
+                "sectionCount": section_count,
+                "shuntLinearModel": {
+                    "bPerSection": shunt.number("LinearShuntCompensator.bPerSection").unwrap_or_default(),
+                    "gPerSection": shunt.number("LinearShuntCompensator.gPerSection").unwrap_or_default(),
+                    "maximumSectionCount": shunt
+                        .number("ShuntCompensator.maximumSections")
+                        .map_or(1, |sections| sections.round() as i64),
+                },
+            })
+        } else {
+            let mut points: Vec<_> = model
+                .referencing(
+                    "NonlinearShuntCompensatorPoint.NonlinearShuntCompensator",
+                    id,
+                )
+                .map(|(_, point)| point)
+                .collect();
+            points.sort_by_key(|point| {
+                point
+                    .number("NonlinearShuntCompensatorPoint.sectionNumber")
+                    .unwrap_or_default() as i64
+            });
+            let (mut b, mut g) = (0.0, 0.0);
+            let sections: Vec<_> = points
+                .into_iter()
+                .map(|point| {
+                    b += point
+                        .number("NonlinearShuntCompensatorPoint.b")
+                        .unwrap_or_default();
+                    g += point
+                        .number("NonlinearShuntCompensatorPoint.g")
+                        .unwrap_or_default();
+                    json!({"b": b, "g": g})
+                })
+                .collect();
+            json!({
+                "id": id,
+                "sectionCount": section_count,
+                "shuntNonLinearModel": {"sections": sections},
+            })
+        };
+        name(&mut object, shunt);
+        object["node"] = json!(node);
+        object["voltageLevelId"] = json!(self.voltage_levels[index].id);
+        self.flow(&mut object, "", terminal);
+        self.shunts.push(object);
+    }
+
+    /// Line segments, with their shunt admittance split between both sides
+    fn line(&mut self, id: &str, segment: &Object) {
+        let terminals = self.terminals(id);
+        let [terminal1, terminal2] = terminals[..] else {
+            self.warnings
+                .push(format!("Line segment {} does not have two terminals", id));
+            return;
+        };
+        let r = segment.number("ACLineSegment.r").unwrap_or_default();
+        let x = segment.number("ACLineSegment.x").unwrap_or_default();
+        let g = segment.number("ACLineSegment.gch").unwrap_or_default();
+        let b = segment.number("ACLineSegment.bch").unwrap_or_default();
+        let end1 = self.connect(terminal1);
+        let end2 = self.connect(terminal2);
+        let (mut object, terminals) = match (end1, end2) {
+            (Some((index1, node1)), Some((index2, node2))) => {
+                let mut object = json!({
+                    "id": id,
+                    "r": r,
+                    "x": x,
+                    "g1": g / 2.0,
+                    "b1": b / 2.0,
+                    "g2": g / 2.0,
+                    "b2": b / 2.0,
+                    "voltageLevelId1": self.voltage_levels[index1].id,
+                    "node1": node1,
+                    "voltageLevelId2": self.voltage_levels[index2].id,
+                    "node2": node2,
+                });
+                self.flow(&mut object, "1", terminal1);
+                self.flow(&mut object, "2", terminal2);
+                self.limits(&mut object, "1", terminal1);
+                self.limits(&mut object, "2", terminal2);
+                (object, &mut self.lines)
+            }
+            (Some((index, node)), None) | (None, Some((index, node))) => {
+                let (terminal, boundary) = if end1.is_some() {
+                    (terminal1, terminal2)
+                } else {
+                    (terminal2, terminal1)
+                };
+                let Some(boundary_node) = self.model.get(boundary).and_then(|boundary| {
+                    boundary
+                        .resource("Terminal.ConnectivityNode")
+                        .or_else(|| boundary.resource("Terminal.TopologicalNode"))
+                }) else {
+                    self.warnings
+                        .push(format!("Line segment {} is not in a voltage level", id));
+                    return;
+                };
+                // The injection at the boundary is the opposite of the flow entering the line
+                let mut boundary_flow = json!({});
+                self.flow(&mut boundary_flow, "", boundary);
+                let mut object = json!({
+                    "id": id,
+                    "p0": -boundary_flow["p"].as_f64().unwrap_or_default(),
+                    "q0": -boundary_flow["q"].as_f64().unwrap_or_default(),
+                    "r": r,
+                    "x": x,
+                    "g": g,
+                    "b": b,
+                    "node": node,
+                    "voltageLevelId": self.voltage_levels[index].id,
+                    "pairingKey": boundary_node,
+                });
+                self.flow(&mut object, "", terminal);
+                self.limits(&mut object, "", terminal);
+                (object, &mut self.dangling_lines)
+            }
+            (None, None) => {
+                self.warnings
+                    .push(format!("Line segment {} is not in a voltage level", id));
+                return;
+            }
+        };
+        name(&mut object, segment);
+        terminals.push(object);
+    }
+
+    /// Power transformers, whose two windings ones are held by the substation of their first
+    /// end and three windings ones by the network
+    fn transformer(&mut self, id: &str, transformer: &Object) {
+        let model = self.model;
+        let mut objects: Vec<_> = model
+            .referencing("PowerTransformerEnd.PowerTransformer", id)
+            .collect();
+        objects.sort_by_key(|(_, end)| {
+            end.number("TransformerEnd.endNumber").unwrap_or_default() as i64
+        });
+        let mut ends = Vec::new();
+        for (end_id, end) in objects {
+            let Some(terminal) = end.resource("TransformerEnd.Terminal") else {
+                continue;
+            };
+            let Some((voltage_level, node)) = self.connect(terminal) else {
+                continue;
+            };
+            ends.push(End {
+                id: end_id,
+                object: end,
+                terminal,
+                voltage_level,
+                node,
+                rated_u: end.number("PowerTransformerEnd.ratedU").unwrap_or(1.0),
+            });
+        }
+        match ends[..] {
+            [ref end1, ref end2] => self.two_windings_transformer(id, transformer, end1, end2),
+            [_, _, _] => self.three_windings_transformer(id, transformer, &ends),
+            _ => self.warnings.push(format!(
+                "Transformer {} does not have two or three ends in voltage levels",
+                id
+            )),
+        }
+    }
+
+    // The impedance of both ends is brought to the rated voltage of the second one
+    fn two_windings_transformer(&mut self, id: &str, transformer: &Object, end1: &End, end2: &End) {
+        let ratio = end2.rated_u / end1.rated_u;
+        let value = |end: &End, property: &str| end.object.number(property).unwrap_or_default();
+        let series = ratio.powi(2);
+        let mut object = json!({
+            "id": id,
+            "r": value(end1, "PowerTransformerEnd.r") * series + value(end2, "PowerTransformerEnd.r"),
+            "x": value(end1, "PowerTransformerEnd.x") * series + value(end2, "PowerTransformerEnd.x"),
+            "g": value(end1, "PowerTransformerEnd.g") / series + value(end2, "PowerTransformerEnd.g"),
+            "b": value(end1, "PowerTransformerEnd.b") / series + value(end2, "PowerTransformerEnd.b"),
+            "ratedU1": end1.rated_u,
+            "ratedU2": end2.rated_u,
+            "voltageLevelId1": self.voltage_levels[end1.voltage_level].id,
+            "node1": end1.node,
+            "voltageLevelId2": self.voltage_levels[end2.voltage_level].id,
+            "node2": end2.node,
+        });
+        name(&mut object, transformer);
+        if let Some(rated_s) = end1.object.number("PowerTransformerEnd.ratedS") {
+            object["ratedS"] = json!(rated_s);
+        }
+        for (side, end) in [("1", end1), ("2", end2)] {
+            self.flow(&mut object, side, end.terminal);
+            self.limits(&mut object, side, end.terminal);
+        }
+
+        // IIDM transformers have a single tap changer of each kind
+        let ratio_tap_changers = [
+            self.ratio_tap_changer(id, end1, 2),
+            self.ratio_tap_changer(id, end2, 2),
+        ];
+        let phase_tap_changers = [
+            self.phase_tap_changer(id, end1, 2),
+            self.phase_tap_changer(id, end2, 2),
+        ];
+        for (key, changers) in [
+            ("ratioTapChanger", ratio_tap_changers),
+            ("phaseTapChanger", phase_tap_changers),
+        ] {
+            let mut changers = changers.into_iter().flatten();
+            if let Some(changer) = changers.next() {
+                object[key] = changer;
+            }
+            if changers.next().is_some() {
+                self.warnings.push(format!(
+                    "Transformer {} has tap changers of the same kind on both ends, only the first one is kept",
+                    id
+                ));
+            }
+        }
+        let substation = self.voltage_levels[end1.voltage_level].substation;
+        self.transformers.push((substation, object));
+    }
+
+    // The star bus is at the rated voltage of the first end, to which the legs are brought
+    fn three_windings_transformer(&mut self, id: &str, transformer: &Object, ends: &[End]) {
+        let rated_u0 = ends[0].rated_u;
+        let mut object = json!({"id": id, "ratedU0": rated_u0});
+        name(&mut object, transformer);
+        for (position, end) in ends.iter().enumerate() {
+            let side = (position + 1).to_string();
+            let series = (rated_u0 / end.rated_u).powi(2);
+            let value = |property: &str| end.object.number(property).unwrap_or_default();
+            object[format!("r{}", side)] = json!(value("PowerTransformerEnd.r") * series);
+            object[format!("x{}", side)] = json!(value("PowerTransformerEnd.x") * series);
+            object[format!("g{}", side)] = json!(value("PowerTransformerEnd.g") / series);
+            object[format!("b{}", side)] = json!(value("PowerTransformerEnd.b") / series);
+            object[format!("ratedU{}", side)] = json!(end.rated_u);
+            if let Some(rated_s) = end.object.number("PowerTransformerEnd.ratedS") {
+                object[format!("ratedS{}", side)] = json!(rated_s);
+            }
+            object[format!("voltageLevelId{}", side)] =
+                json!(self.voltage_levels[end.voltage_level].id);
+            object[format!("node{}", side)] = json!(end.node);
+            self.flow(&mut object, &side, end.terminal);
+            self.limits(&mut object, &side, end.terminal);
+            if let Some(changer) = self.ratio_tap_changer(id, end, 3) {
+                object[format!("ratioTapChanger{}", side)] = changer;
+            }
+            if let Some(changer) = self.phase_tap_changer(id, end, 3) {
+                object[format!("phaseTapChanger{}", side)] = changer;
+            }
+        }
+        self.three_windings_transformers.push(object);
+    }
+
+    fn ratio_tap_changer(&mut self, id: &str, end: &End, windings: usize) -> Option<Value> {
+        let model = self.model;
+        let (tap_changer_id, tap_changer) = model
+            .referencing("RatioTapChanger.TransformerEnd", end.id)
+            .next()?;
+        let (low, position, taps) = self.taps(tap_changer_id, tap_changer);
+        let (mut regulating, mode, value, deadband, terminal_ref) =
+            self.regulation(id, end, tap_changer);
+        let mode = match mode {
+            Some("voltage") | None => "VOLTAGE",
+            Some("reactivePower") => "REACTIVE_POWER",
+            Some(mode) => {
+                self.warnings.push(format!(
+                    "Regulation mode {} of tap changer {} is not supported",
+                    mode, tap_changer_id
+                ));
+                regulating = false;
+                "VOLTAGE"
+            }
+        };
+        let is_side_two = windings == 2 && end_number(end) == 2;
+        Some(json!({
+            "regulating": regulating,
+            "lowTapPosition": low,
+            "tapPosition": position,
+            "targetDeadband": deadband,
+            "loadTapChangingCapabilities": tap_changer
+                .boolean("TapChanger.ltcFlag")
+                .unwrap_or(regulating),
+            "regulationMode": mode,
+            "regulationValue": value,
+            "terminalRef": terminal_ref,
+            "steps": taps
+                .iter()
+                .map(|tap| json!({
+                    "r": tap.r,
+                    "x": tap.x,
+                    "g": tap.g,
+                    "b": tap.b,
+                    "rho": if is_side_two { tap.ratio } else { 1.0 / tap.ratio },
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+
+    fn phase_tap_changer(&mut self, id: &str, end: &End, windings: usize) -> Option<Value> {
+        let model = self.model;
+        let (tap_changer_id, tap_changer) = model
+            .referencing("PhaseTapChanger.TransformerEnd", end.id)
+            .find(|(_, tap_changer)| {
+                PHASE_TAP_CHANGER_CLASSES.contains(&tap_changer.class.as_str())
+            })?;
+        let (low, position, taps) = self.taps(tap_changer_id, tap_changer);
+        let (mut regulating, mode, value, deadband, terminal_ref) =
+            self.regulation(id, end, tap_changer);
+        let mode = match mode {
+            Some("activePower") => "ACTIVE_POWER_CONTROL",
+            Some("currentFlow") => "CURRENT_LIMITER",
+            None => "FIXED_TAP",
+            Some(mode) => {
+                self.warnings.push(format!(
+                    "Regulation mode {} of tap changer {} is not supported",
+                    mode, tap_changer_id
+                ));
+                "FIXED_TAP"
+            }
+        };
+        regulating &= mode != "FIXED_TAP";
+        let is_side_two = windings == 2 && end_number(end) == 2;
+        Some(json!({
+            "regulating": regulating,
+            "lowTapPosition": low,
+            "tapPosition": position,
+            "regulationMode": mode,
+            "regulationValue": value,
+            "targetDeadband": deadband,
+            "terminalRef": terminal_ref,
+            "steps": taps
+                .iter()
+                .map(|tap| json!({
+                    "r": tap.r,
+                    "x": tap.x,
+                    "g": tap.g,
+                    "b": tap.b,
+                    "rho": if is_side_two { tap.ratio } else { 1.0 / tap.ratio },
+                    "alpha": if is_side_two { -tap.angle } else { tap.angle },
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Low position, position of the state variables and taps of a tap changer
+    fn taps(&self, id: &str, tap_changer: &Object) -> (i32, i32, Vec<Tap>) {
+        let model = self.model;
+        let step = |property: &str| tap_changer.number(property).map(|step| step.round() as i32);
+        let low = step("TapChanger.lowStep").unwrap_or_default();
+        let high = step("TapChanger.highStep").unwrap_or(low).max(low);
+        let neutral = step("TapChanger.neutralStep").unwrap_or(low);
+        let position = model
+            .referencing("SvTapStep.TapChanger", id)
+            .next()
+            .and_then(|(_, tap_step)| tap_step.number("SvTapStep.position"))
+            .map(|position| position.round() as i32)
+            .or_else(|| step("TapChanger.step"))
+            .or_else(|| step("TapChanger.normalStep"))
+            .unwrap_or(neutral)
+            .clamp(low, high);
+
+        let table = match tap_changer.class.as_str() {
+            "RatioTapChanger" => tap_changer
+                .resource("RatioTapChanger.RatioTapChangerTable")
+                .map(|table| ("RatioTapChangerTablePoint.RatioTapChangerTable", table)),
+            "PhaseTapChangerTabular" => tap_changer
+                .resource("PhaseTapChangerTabular.PhaseTapChangerTable")
+                .map(|table| ("PhaseTapChangerTablePoint.PhaseTapChangerTable", table)),
+            _ => None,
+        };
+        let points: HashMap<i32, &Object> = table
+            .into_iter()
+            .flat_map(|(property, table)| model.referencing(property, table))
+            .filter_map(|(_, point)| {
+                Some((
+                    point.number("TapChangerTablePoint.step")?.round() as i32,
+                    point,
+                ))
+            })
+            .collect();
+
+        let increment = tap_changer
+            .number("RatioTapChanger.stepVoltageIncrement")
+            .or_else(|| tap_changer.number("PhaseTapChangerNonLinear.voltageStepIncrement"))
+            .unwrap_or_default()
+            / 100.0;
+        let taps = (low..=high)
+            .map(|step| {
+                let deviation = (step - neutral) as f64;
+                let (ratio, angle) = match tap_changer.class.as_str() {
+                    "PhaseTapChangerLinear" => (
+                        1.0,
+                        deviation
+                            * tap_changer
+                                .number("PhaseTapChangerLinear.stepPhaseShiftIncrement")
+                                .unwrap_or_default(),
+                    ),
+                    "PhaseTapChangerSymmetrical" => {
+                        (1.0, 2.0 * (deviation * increment / 2.0).atan().to_degrees())
+                    }
+                    "PhaseTapChangerAsymmetrical" => {
+                        let connection = tap_changer
+                            .number("PhaseTapChangerAsymmetrical.windingConnectionAngle")
+                            .unwrap_or(90.0)
+                            .to_radians();
+                        let dx = 1.0 + deviation * increment * connection.cos();
+                        let dy = deviation * increment * connection.sin();
+                        (dx.hypot(dy), dy.atan2(dx).to_degrees())
+                    }
+                    "PhaseTapChangerTabular" => (1.0, 0.0),
+                    _ => (1.0 + deviation * increment, 0.0),
+                };
+                let point = points.get(&step);
+                let value = |property: &str, default: f64| {
+                    point
+                        .and_then(|point| point.number(property))
+                        .unwrap_or(default)
+                };
+                Tap {
+                    ratio: value("TapChangerTablePoint.ratio", ratio),
+                    angle: value("PhaseTapChangerTablePoint.angle", angle),
+                    r: value("TapChangerTablePoint.r", 0.0),
+                    x: value("TapChangerTablePoint.x", 0.0),
+                    g: value("TapChangerTablePoint.g", 0.0),
+                    b: value("TapChangerTablePoint.b", 0.0),
+                }
+            })
+            .collect();
+        (low, position, taps)
+    }
+
+    /// Regulation of a tap changer, as whether it is regulating, the mode, target value and
+    /// deadband of its control, and the regulated terminal, the end itself by default
+    fn regulation(
+        &self,
+        id: &str,
+        end: &End,
+        tap_changer: &'a Object,
+    ) -> (bool, Option<&'a str>, f64, f64, Value) {
+        let control = tap_changer
+            .resource("TapChanger.TapChangerControl")
+            .and_then(|control| self.model.get(control));
+        let regulating = tap_changer
+            .boolean("TapChanger.controlEnabled")
+            .unwrap_or(false)
+            && control.is_some_and(|control| {
+                control.boolean("RegulatingControl.enabled").unwrap_or(true)
+            });
+        let number = |property: &str| {
+            control
+                .and_then(|control| control.number(property))
+                .unwrap_or_default()
+        };
+        let terminal_ref = control
+            .and_then(|control| control.resource("RegulatingControl.Terminal"))
+            .and_then(|terminal| self.terminal_ref(terminal))
+            .unwrap_or_else(|| json!({"id": id, "side": SIDES[end_number(end) - 1]}));
+        (
+            regulating,
+            control.and_then(|control| control.enumeration("RegulatingControl.mode")),
+            number("RegulatingControl.targetValue"),
+            number("RegulatingControl.targetDeadband"),
+            terminal_ref,
+        )
+    }
+}
+
+fn end_number(end: &End) -> usize {
+    end.object
+        .number("TransformerEnd.endNumber")
+        .map_or(1, |number| number as usize)
+        .clamp(1, 3)
+}
+
+fn name(object: &mut Value, source: &Object) {
+    if let Some(name) = source.name() {
+        object["name"] = json!(name);
+    }
+}
+
+fn push(object: &mut Value, key: &str, item: Value) {
+    if let Some(items) = object.get_mut(key).and_then(Value::as_array_mut) {
+        items.push(item);
+    }
+}
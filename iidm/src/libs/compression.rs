@@ -0,0 +1,224 @@
+//! Compressed and archived IIDM documents, recognized by their magic bytes.
+//!
+//! Gzip and Zstandard streams hold a single document, zip archives are read from their first
+//! `.json` or `.xiidm` entry. Formats made of several documents, such as CGMES, read every
+//! entry of their archives.
+
+use std::borrow::Cow;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::error::IidmError;
+use super::json::{from_json, to_json};
+use super::version::IidmVersion;
+use super::xml::{from_xml, to_xml};
+use crate::Network;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Extensions of the zip entries holding IIDM documents
+const DOCUMENT_EXTENSIONS: [&str; 2] = [".json", ".xiidm"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Zip,
+}
+
+impl Compression {
+    /// Compression of a document given its first bytes, `None` for plain documents
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Some(Compression::Zstd)
+        } else if bytes.starts_with(ZIP_MAGIC) {
+            Some(Compression::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Extension appended to the name of compressed documents, e.g. `gz` for
+    /// `network.json.gz`
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+            Compression::Zip => "zip",
+        }
+    }
+}
+
+/// Returns the document held by compressed bytes, or the bytes themselves when they are not
+/// compressed
+pub fn decompress(bytes: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    let mut document = Vec::new();
+    match Compression::detect(bytes) {
+        None => return Ok(Cow::Borrowed(bytes)),
+        Some(Compression::Gzip) => {
+            MultiGzDecoder::new(bytes).read_to_end(&mut document)?;
+        }
+        Some(Compression::Zstd) => document = zstd::decode_all(bytes)?,
+        Some(Compression::Zip) => {
+            let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+            let index = (0..archive.len())
+                .find(|index| archive.name_for_index(*index).is_some_and(is_document))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "zip archive without .json or .xiidm entry",
+                    )
+                })?;
+            archive.by_index(index)?.read_to_end(&mut document)?;
+        }
+    }
+    Ok(Cow::Owned(document))
+}
+
+/// Entries of a zip archive whose names end with one of `extensions`, in the order of the
+/// archive. Entries that are zip archives themselves are read the same way, compressed entries
+/// being matched by their name without the compression extension and decompressed.
+pub(crate) fn archive_entries(
+    bytes: &[u8],
+    extensions: &[&str],
+) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        let compression = Compression::detect(&content);
+        if compression == Some(Compression::Zip) {
+            entries.extend(archive_entries(&content, extensions)?);
+            continue;
+        }
+        let stem = compression
+            .and_then(|compression| name.strip_suffix(&format!(".{}", compression.extension())))
+            .unwrap_or(&name);
+        if extensions.iter().any(|extension| stem.ends_with(extension)) {
+            let document = decompress(&content)?.into_owned();
+            entries.push((name, document));
+        }
+    }
+    Ok(entries)
+}
+
+/// Calls `read` with the document of a stream, decompressed as it is read, or the stream itself
+/// when it is not compressed. Zip archives are read from their local headers without seeking,
+/// unless their sizes follow the entries in data descriptors: they are then read whole.
+pub fn read_decompressed<R: Read, T>(
+    reader: R,
+    read: impl FnOnce(&mut dyn Read) -> T,
+) -> io::Result<T> {
+    let mut reader = BufReader::new(reader);
+    match Compression::detect(reader.fill_buf()?) {
+        None => Ok(read(&mut reader)),
+        Some(Compression::Gzip) => Ok(read(&mut MultiGzDecoder::new(reader))),
+        Some(Compression::Zstd) => Ok(read(&mut zstd::Decoder::with_buffer(reader)?)),
+        Some(Compression::Zip) if has_data_descriptor(reader.fill_buf()?) => {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes)?;
+            Ok(read(&mut decompress(&bytes)?.as_ref()))
+        }
+        Some(Compression::Zip) => loop {
+            let Some(mut entry) = zip::read::read_zipfile_from_stream(&mut reader)? else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "zip archive without .json or .xiidm entry",
+                ));
+            };
+            if is_document(entry.name()) {
+                return Ok(read(&mut entry));
+            }
+        },
+    }
+}
+
+/// Compresses a document, `name` being the name of its entry in zip archives
+pub fn compress(document: &[u8], compression: Compression, name: &str) -> io::Result<Vec<u8>> {
+    match compression {
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(document)?;
+            encoder.finish()
+        }
+        Compression::Zstd => zstd::encode_all(document, zstd::DEFAULT_COMPRESSION_LEVEL),
+        Compression::Zip => {
+            let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+            let options =
+                SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+            writer.start_file(name, options)?;
+            writer.write_all(document)?;
+            Ok(writer.finish()?.into_inner())
+        }
+    }
+}
+
+/// Reads a network from an IIDM JSON or XML document, possibly compressed or archived
+pub fn read_iidm(bytes: &[u8]) -> Result<Network, IidmError> {
+    let document = decompress(bytes)?;
+    match document.iter().find(|byte| !byte.is_ascii_whitespace()) {
+        Some(b'<') => {
+            let xml = std::str::from_utf8(&document)
+                .map_err(|error| IidmError::Malformed(format!("Invalid IIDM XML: {}", error)))?;
+            from_xml(xml)
+        }
+        _ => from_json(&document),
+    }
+}
+
+/// Writes a network as a compressed IIDM JSON document of the given IIDM version, archived
+/// as `<network id>.json`
+pub fn to_compressed_json(
+    network: &Network,
+    version: IidmVersion,
+    compression: Compression,
+) -> Result<Vec<u8>, IidmError> {
+    let json = serde_json::to_vec(&to_json(network, version)?)?;
+    Ok(compress(
+        &json,
+        compression,
+        &format!("{}.json", network.id),
+    )?)
+}
+
+/// Writes a network as a compressed IIDM XML document of the given IIDM version, archived
+/// as `<network id>.xiidm`
+pub fn to_compressed_xml(
+    network: &Network,
+    version: IidmVersion,
+    compression: Compression,
+) -> Result<Vec<u8>, IidmError> {
+    let xml = to_xml(network, version)?;
+    Ok(compress(
+        xml.as_bytes(),
+        compression,
+        &format!("{}.xiidm", network.id),
+    )?)
+}
+
+// Streaming writers, e.g. Java's `ZipOutputStream`, set the bit 3 of the flags of the local
+// header and write the sizes of the entry after its data. Headers cut by a short read are read
+// as having one, the archive being read whole.
+fn has_data_descriptor(header: &[u8]) -> bool {
+    header.get(6).is_none_or(|flags| flags & 0x08 != 0)
+}
+
+fn is_document(name: &str) -> bool {
+    DOCUMENT_EXTENSIONS
+        .iter()
+        .any(|extension| name.ends_with(extension))
+}
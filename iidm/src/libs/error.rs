@@ -0,0 +1,22 @@
+use std::io;
+
+use super::version::IidmVersion;
+
+/// Errors of the reading and writing of IIDM JSON and XML documents
+#[derive(Debug, thiserror::Error)]
+pub enum IidmError {
+    #[error(
+        "Unsupported IIDM version {0}, supported versions are {oldest} to {latest}",
+        oldest = IidmVersion::OLDEST,
+        latest = IidmVersion::LATEST
+    )]
+    UnsupportedVersion(String),
+    #[error("Missing IIDM version")]
+    MissingVersion,
+    #[error("Malformed IIDM document: {0}")]
+    Malformed(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,23 @@
+//! IEEE Common Data Format (`.cdf`), used by the classic IEEE 14, 30, 57 and 118 bus test
+//! systems.
+//!
+//! Each bus of the case becomes a substation with a single voltage level, transformers
+//! being held by the substation of their tap bus. Values in per-unit of the case base power
+//! and of the base voltage of the buses are converted to SI units.
+
+mod reader;
+
+pub use reader::from_ieee_cdf;
+
+/// Error while reading an IEEE CDF case, with the number of the faulty line
+#[derive(Debug, thiserror::Error)]
+pub enum IeeeCdfError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error("Line {line}: unknown bus {bus}")]
+    UnknownBus { line: usize, bus: i64 },
+    #[error("Missing {0}")]
+    Missing(String),
+    #[error("Invalid network: {0}")]
+    Network(#[from] serde_json::Error),
+}
@@ -0,0 +1,444 @@
+//! Reader of IEEE CDF cases
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate};
+use serde_json::{json, Value};
+
+use super::IeeeCdfError;
+use crate::libs::bus_branch::{nominal_v, substations, BranchEnd, Groups, PerUnitBranch};
+use crate::libs::version::IidmVersion;
+use crate::Network;
+
+const DEFAULT_ACTIVE_LIMIT: f64 = 9999.0;
+const DEFAULT_REACTIVE_LIMIT: f64 = 9999.0;
+
+/// Reads a network from an IEEE CDF case.
+///
+/// Only the title, bus and branch cards are read, loss zones, interchanges and tie lines
+/// being ignored. Transformers are read at their final turns ratio and phase shift, their
+/// tap controls being ignored.
+pub fn from_ieee_cdf(id: &str, cdf: &str) -> Result<Network, IeeeCdfError> {
+    let case = Case::parse(cdf)?;
+    let network = case.to_json(id)?;
+    Ok(serde_json::from_value(network)?)
+}
+
+/// Card of the case, whose fields are at fixed columns
+struct Card<'a> {
+    line: usize,
+    text: &'a str,
+}
+
+impl Card<'_> {
+    fn error(&self, message: String) -> IeeeCdfError {
+        IeeeCdfError::Syntax {
+            line: self.line,
+            message,
+        }
+    }
+
+    // Columns are numbered from 1 and inclusive, as in the format description. Trailing
+    // fields may be left out of the card.
+    fn text(&self, start: usize, end: usize) -> &str {
+        let end = end.min(self.text.len());
+        self.text.get(start - 1..end).unwrap_or_default().trim()
+    }
+
+    fn number(&self, start: usize, end: usize, name: &str) -> Result<f64, IeeeCdfError> {
+        match self.text(start, end) {
+            "" => Ok(0.0),
+            value => value
+                .parse()
+                .map_err(|_| self.error(format!("invalid {} '{}'", name, value))),
+        }
+    }
+
+    fn integer(&self, start: usize, end: usize, name: &str) -> Result<i64, IeeeCdfError> {
+        match self.text(start, end) {
+            "" => Ok(0),
+            value => value
+                .parse()
+                .map_err(|_| self.error(format!("invalid {} '{}'", name, value))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Bus,
+    Branch,
+    Other,
+}
+
+#[derive(Default)]
+struct Case<'a> {
+    title: Option<Card<'a>>,
+    buses: Vec<Card<'a>>,
+    branches: Vec<Card<'a>>,
+    has_buses: bool,
+}
+
+impl<'a> Case<'a> {
+    fn parse(cdf: &'a str) -> Result<Self, IeeeCdfError> {
+        let mut case = Self::default();
+        let mut section = None;
+        for (index, text) in cdf.lines().enumerate() {
+            let card = Card {
+                line: index + 1,
+                text,
+            };
+            if case.title.is_none() {
+                case.title = Some(card);
+                continue;
+            }
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            match section {
+                None => {
+                    if trimmed.starts_with("END OF DATA") {
+                        break;
+                    } else if trimmed.starts_with("BUS DATA FOLLOWS") {
+                        section = Some(Section::Bus);
+                        case.has_buses = true;
+                    } else if trimmed.starts_with("BRANCH DATA FOLLOWS") {
+                        section = Some(Section::Branch);
+                    } else if trimmed.contains("FOLLOWS") {
+                        section = Some(Section::Other);
+                    } else {
+                        return Err(card.error(format!("unexpected card '{}'", trimmed)));
+                    }
+                }
+                // Sections end with -999, -99 or -9
+                Some(_) if trimmed.starts_with("-9") => section = None,
+                Some(Section::Bus) => case.buses.push(card),
+                Some(Section::Branch) => case.branches.push(card),
+                Some(Section::Other) => {}
+            }
+        }
+        Ok(case)
+    }
+
+    fn to_json(&self, id: &str) -> Result<Value, IeeeCdfError> {
+        let title = self
+            .title
+            .as_ref()
+            .ok_or_else(|| IeeeCdfError::Missing("title card".to_string()))?;
+        if !self.has_buses {
+            return Err(IeeeCdfError::Missing("bus data".to_string()));
+        }
+        let base_mva = match title.number(32, 37, "MVA base")? {
+            base_mva if base_mva > 0.0 => base_mva,
+            _ => return Err(title.error("invalid MVA base".to_string())),
+        };
+        // Dates are written MM/DD/YY
+        let case_date = NaiveDate::parse_from_str(title.text(2, 9), "%m/%d/%y")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+            .unwrap_or(DateTime::UNIX_EPOCH);
+
+        let buses = self
+            .buses
+            .iter()
+            .map(Bus::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut network = Builder {
+            base_mva,
+            indexes: buses
+                .iter()
+                .enumerate()
+                .map(|(index, bus)| (bus.number, index))
+                .collect(),
+            buses: &buses,
+            voltage_levels: Vec::new(),
+            substations: Groups::new(buses.len()),
+            lines: Vec::new(),
+            transformers: Vec::new(),
+            shunts: Vec::new(),
+        };
+        for bus in &buses {
+            network.bus(bus);
+        }
+        for card in &self.branches {
+            network.branch(&Branch::parse(card)?)?;
+        }
+
+        Ok(json!({
+            "version": IidmVersion::LATEST.to_string(),
+            "id": id,
+            "caseDate": case_date.fixed_offset(),
+            "forecastDistance": 0,
+            "sourceFormat": "IEEE-CDF",
+            "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+            "substations": network.substations(),
+            "lines": network.lines,
+            "shuntCompensators": network.shunts,
+        }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BusType {
+    Load,
+    GenerationLimits,
+    VoltageLimits,
+    Swing,
+}
+
+struct Bus {
+    number: i64,
+    name: String,
+    bus_type: BusType,
+    voltage_magnitude: f64,
+    voltage_angle: f64,
+    active_load: f64,
+    reactive_load: f64,
+    active_generation: f64,
+    reactive_generation: f64,
+    base_kv: f64,
+    desired_voltage: f64,
+    maximum_reactive_power: f64,
+    minimum_reactive_power: f64,
+    shunt_conductance: f64,
+    shunt_susceptance: f64,
+}
+
+impl Bus {
+    fn parse(card: &Card) -> Result<Self, IeeeCdfError> {
+        let bus_type = match card.integer(25, 26, "bus type")? {
+            0 => BusType::Load,
+            1 => BusType::GenerationLimits,
+            2 => BusType::VoltageLimits,
+            3 => BusType::Swing,
+            other => return Err(card.error(format!("invalid bus type {}", other))),
+        };
+        Ok(Self {
+            number: card.integer(1, 4, "bus number")?,
+            name: card.text(6, 17).to_string(),
+            bus_type,
+            voltage_magnitude: card.number(28, 33, "final voltage")?,
+            voltage_angle: card.number(34, 40, "final angle")?,
+            active_load: card.number(41, 49, "load MW")?,
+            reactive_load: card.number(50, 59, "load MVAR")?,
+            active_generation: card.number(60, 67, "generation MW")?,
+            reactive_generation: card.number(68, 75, "generation MVAR")?,
+            base_kv: card.number(77, 83, "base KV")?,
+            desired_voltage: card.number(85, 90, "desired volts")?,
+            maximum_reactive_power: card.number(91, 98, "maximum MVAR")?,
+            minimum_reactive_power: card.number(99, 106, "minimum MVAR")?,
+            shunt_conductance: card.number(107, 114, "shunt conductance")?,
+            shunt_susceptance: card.number(115, 122, "shunt susceptance")?,
+        })
+    }
+
+    fn nominal_v(&self) -> f64 {
+        nominal_v(self.base_kv)
+    }
+
+    fn id(&self) -> String {
+        format!("BUS-{}", self.number)
+    }
+
+    fn voltage_level_id(&self) -> String {
+        format!("VL-{}", self.number)
+    }
+
+    fn end(&self) -> BranchEnd {
+        BranchEnd {
+            voltage_level_id: self.voltage_level_id(),
+            bus_id: self.id(),
+            nominal_v: self.nominal_v(),
+        }
+    }
+
+    // Synchronous condensers are voltage controlled buses without active generation
+    fn has_generator(&self) -> bool {
+        matches!(self.bus_type, BusType::VoltageLimits | BusType::Swing)
+            || self.active_generation != 0.0
+            || self.reactive_generation != 0.0
+    }
+}
+
+struct Branch {
+    line: usize,
+    tap_bus: i64,
+    z_bus: i64,
+    circuit: String,
+    branch_type: i64,
+    r: f64,
+    x: f64,
+    b: f64,
+    rating: f64,
+    ratio: f64,
+    angle: f64,
+}
+
+impl Branch {
+    fn parse(card: &Card) -> Result<Self, IeeeCdfError> {
+        let circuit = match card.text(17, 17) {
+            "" | "0" => "1",
+            circuit => circuit,
+        };
+        Ok(Self {
+            line: card.line,
+            tap_bus: card.integer(1, 4, "tap bus number")?,
+            z_bus: card.integer(6, 9, "Z bus number")?,
+            circuit: circuit.to_string(),
+            branch_type: card.integer(19, 19, "branch type")?,
+            r: card.number(20, 29, "branch resistance")?,
+            x: card.number(30, 40, "branch reactance")?,
+            b: card.number(41, 50, "line charging")?,
+            rating: card.number(51, 55, "line MVA rating")?,
+            ratio: card.number(77, 82, "transformer final turns ratio")?,
+            angle: card.number(84, 90, "transformer final angle")?,
+        })
+    }
+}
+
+struct Builder<'a> {
+    base_mva: f64,
+    buses: &'a [Bus],
+    indexes: HashMap<i64, usize>,
+    // Voltage levels in the order of the buses
+    voltage_levels: Vec<Value>,
+    // Buses linked by transformers, which are in the same substation
+    substations: Groups,
+    lines: Vec<Value>,
+    // Transformers with the index of the bus whose substation holds them
+    transformers: Vec<(usize, Value)>,
+    shunts: Vec<Value>,
+}
+
+impl Builder<'_> {
+    fn find(&self, number: i64, line: usize) -> Result<usize, IeeeCdfError> {
+        self.indexes
+            .get(&number)
+            .copied()
+            .ok_or(IeeeCdfError::UnknownBus { line, bus: number })
+    }
+
+    fn bus(&mut self, bus: &Bus) {
+        let nominal_v = bus.nominal_v();
+        let mut loads = Vec::new();
+        if bus.active_load != 0.0 || bus.reactive_load != 0.0 {
+            loads.push(json!({
+                "id": format!("LOAD-{}", bus.number),
+                "loadType": "UNDEFINED",
+                "p0": bus.active_load,
+                "q0": bus.reactive_load,
+                "bus": bus.id(),
+                "connectableBus": bus.id(),
+            }));
+        }
+
+        // Reactive limits are only given for voltage controlled buses
+        let mut generators = Vec::new();
+        if bus.has_generator() {
+            let voltage_regulator_on =
+                matches!(bus.bus_type, BusType::VoltageLimits | BusType::Swing);
+            let (minimum_q, maximum_q) = if voltage_regulator_on
+                && bus.minimum_reactive_power < bus.maximum_reactive_power
+            {
+                (bus.minimum_reactive_power, bus.maximum_reactive_power)
+            } else {
+                (-DEFAULT_REACTIVE_LIMIT, DEFAULT_REACTIVE_LIMIT)
+            };
+            let target_v = if bus.desired_voltage > 0.0 {
+                bus.desired_voltage
+            } else {
+                bus.voltage_magnitude
+            };
+            generators.push(json!({
+                "id": format!("GEN-{}", bus.number),
+                "energySource": "OTHER",
+                "minP": 0.0,
+                "maxP": DEFAULT_ACTIVE_LIMIT,
+                "voltageRegulatorOn": voltage_regulator_on,
+                "targetP": bus.active_generation,
+                "targetV": target_v * nominal_v,
+                "targetQ": bus.reactive_generation,
+                "bus": bus.id(),
+                "connectableBus": bus.id(),
+                "minMaxReactiveLimits": {"minQ": minimum_q, "maxQ": maximum_q},
+            }));
+        }
+
+        // Shunt admittances are in per-unit of the case base power
+        if bus.shunt_conductance != 0.0 || bus.shunt_susceptance != 0.0 {
+            let base_admittance = self.base_mva / nominal_v.powi(2);
+            self.shunts.push(json!({
+                "id": format!("SHUNT-{}", bus.number),
+                "sectionCount": 1,
+                "shuntLinearModel": {
+                    "bPerSection": bus.shunt_susceptance * base_admittance,
+                    "gPerSection": bus.shunt_conductance * base_admittance,
+                    "maximumSectionCount": 1,
+                },
+                "bus": bus.id(),
+                "connectableBus": bus.id(),
+                "voltageLevelId": bus.voltage_level_id(),
+            }));
+        }
+
+        let mut object = json!({
+            "id": bus.id(),
+            "v": bus.voltage_magnitude * nominal_v,
+            "angle": bus.voltage_angle,
+        });
+        if !bus.name.is_empty() {
+            object["name"] = json!(bus.name);
+        }
+        self.voltage_levels.push(json!({
+            "id": bus.voltage_level_id(),
+            "nominalV": nominal_v,
+            "topologyKind": "BUS_BREAKER",
+            "generators": generators,
+            "loads": loads,
+            "busBreakerTopology": {"buses": [object], "switches": []},
+        }));
+    }
+
+    // Impedances are in per-unit of the base voltage of the impedance side
+    fn branch(&mut self, branch: &Branch) -> Result<(), IeeeCdfError> {
+        let tap_index = self.find(branch.tap_bus, branch.line)?;
+        let z_index = self.find(branch.z_bus, branch.line)?;
+        let (tap, z) = (&self.buses[tap_index], &self.buses[z_index]);
+        let per_unit = PerUnitBranch {
+            from: tap.end(),
+            to: z.end(),
+            r: branch.r,
+            x: branch.x,
+            b: branch.b,
+            rating: branch.rating,
+            ratio: branch.ratio,
+            shift: branch.angle,
+            in_service: true,
+        };
+        let id = format!("{}-{}-{}", tap.number, z.number, branch.circuit);
+        if branch.branch_type == 0 {
+            self.lines
+                .push(per_unit.line(format!("LINE-{}", id), self.base_mva));
+        } else {
+            let transformer =
+                per_unit.two_windings_transformer(format!("TWT-{}", id), self.base_mva);
+            self.substations.join(tap_index, z_index);
+            self.transformers.push((tap_index, transformer));
+        }
+        Ok(())
+    }
+
+    // Substations are named after the first bus they hold
+    fn substations(&mut self) -> Vec<Value> {
+        let buses = self.buses;
+        substations(
+            std::mem::take(&mut self.voltage_levels),
+            std::mem::take(&mut self.transformers),
+            &mut self.substations,
+            |index| format!("SUB-{}", buses[index].number),
+        )
+    }
+}
@@ -1,6 +1,157 @@
+use schemars::schema::{RootSchema, Schema, SingleOrVec};
 use serde::{de::Error, Deserialize};
 use serde_json::Value;
 
+use super::compression::decompress;
+use super::error::IidmError;
+use super::version::{downgrade, upgrade, IidmVersion};
+use crate::Network;
+
+/// Reads a network from an IIDM JSON document, possibly compressed, upgrading documents of
+/// older IIDM versions
+pub fn from_json(json: &[u8]) -> Result<Network, IidmError> {
+    let json = decompress(json)?;
+    let mut value: Value = serde_json::from_slice(&json)?;
+    upgrade(&mut value)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Converts a network to the IIDM JSON layout of the given IIDM version
+pub fn to_json(network: &Network, version: IidmVersion) -> Result<Value, IidmError> {
+    let mut value = serde_json::to_value(network)?;
+    downgrade(&mut value, version);
+    Ok(value)
+}
+
+/// Keys of the objects described by the schema of a type, alternatives included. These are
+/// the JSON keys of an updater field flattened in its entity, e.g. `minMaxReactiveLimits`.
+pub fn schema_keys<T: schemars::JsonSchema>() -> Vec<String> {
+    let root = schemars::schema_for!(T);
+    let mut keys = Vec::new();
+    collect_keys(&Schema::Object(root.schema.clone()), &root, &mut keys);
+    keys
+}
+
+fn collect_keys(schema: &Schema, root: &RootSchema, keys: &mut Vec<String>) {
+    let Schema::Object(object) = resolve(schema, root) else {
+        return;
+    };
+    if let Some(validation) = &object.object {
+        for key in validation.properties.keys() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+    if let Some(subschemas) = &object.subschemas {
+        let alternatives = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+        for subschema in alternatives.into_iter().flatten().flatten() {
+            collect_keys(subschema, root, keys);
+        }
+    }
+}
+
+/// Checks the keys of the objects nested in the attributes of a JSON object against the schema
+/// of a type, so that a misspelled nested key is reported instead of being kept as an
+/// unmodelled attribute. Attributes unknown to the schema are left to the caller, and objects
+/// described without properties, such as maps, accept any key.
+pub fn check_nested_keys<T: schemars::JsonSchema>(value: &Value) -> Result<(), serde_json::Error> {
+    let root = schemars::schema_for!(T);
+    let schema = Schema::Object(root.schema.clone());
+    let Some(object) = value.as_object() else {
+        return Ok(());
+    };
+    for (key, value) in object {
+        if let Some(property) = property(&schema, &root, key) {
+            check_keys(value, property, &root, key)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_keys(
+    value: &Value,
+    schema: &Schema,
+    root: &RootSchema,
+    path: &str,
+) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Object(object) => {
+            let mut keys = Vec::new();
+            collect_keys(schema, root, &mut keys);
+            if keys.is_empty() {
+                return Ok(());
+            }
+            for (key, value) in object {
+                let path = format!("{}.{}", path, key);
+                let property = property(schema, root, key).ok_or_else(|| {
+                    serde_json::Error::custom(format!("Unexpected field: {}", path))
+                })?;
+                check_keys(value, property, root, &path)?;
+            }
+            Ok(())
+        }
+        Value::Array(values) => match items(schema, root) {
+            Some(items) => values.iter().enumerate().try_for_each(|(index, value)| {
+                check_keys(value, items, root, &format!("{}[{}]", path, index))
+            }),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+// Schema of a property, looked up in the alternatives of the schema too
+fn property<'a>(schema: &'a Schema, root: &'a RootSchema, key: &str) -> Option<&'a Schema> {
+    let Schema::Object(object) = resolve(schema, root) else {
+        return None;
+    };
+    if let Some(property) = object
+        .object
+        .as_ref()
+        .and_then(|validation| validation.properties.get(key))
+    {
+        return Some(property);
+    }
+    let subschemas = object.subschemas.as_ref()?;
+    [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .find_map(|subschema| property(subschema, root, key))
+}
+
+// Schema of the items of an array, looked up in the alternatives of the schema too
+fn items<'a>(schema: &'a Schema, root: &'a RootSchema) -> Option<&'a Schema> {
+    let Schema::Object(object) = resolve(schema, root) else {
+        return None;
+    };
+    if let Some(SingleOrVec::Single(items)) =
+        object.array.as_ref().and_then(|array| array.items.as_ref())
+    {
+        return Some(items);
+    }
+    let subschemas = object.subschemas.as_ref()?;
+    [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .find_map(|subschema| items(subschema, root))
+}
+
+// Definition referenced by a schema, or the schema itself
+pub(crate) fn resolve<'a>(schema: &'a Schema, root: &'a RootSchema) -> &'a Schema {
+    match schema {
+        Schema::Object(object) => object
+            .reference
+            .as_ref()
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+            .and_then(|name| root.definitions.get(name))
+            .map_or(schema, |definition| resolve(definition, root)),
+        Schema::Bool(_) => schema,
+    }
+}
+
 pub fn validate_json<T>(json: &str) -> Result<T, serde_json::Error>
 where
     T: crate::extensions::JsonSchema + for<'de> Deserialize<'de> + schemars::JsonSchema,
@@ -25,15 +176,29 @@ where
         }
     }
 
+    // Check the keys of nested objects, which may otherwise end up in unknown attributes
+    check_nested_keys::<T>(&value)?;
+
     // Get the JSON schema for T
     let schema = schemars::schema_for!(T);
     let schema_value = serde_json::to_value(&schema).map_err(|e| {
         serde_json::Error::custom(format!("Failed to convert schema to value: {}", e))
     })?;
 
+    // Keys of flattened fields are left out of the schema, they are checked by their own
+    // deserializer
+    let properties = schema
+        .schema
+        .object
+        .as_ref()
+        .map(|object| &object.properties);
+    let mut instance = value.clone();
+    if let (Some(instance), Some(properties)) = (instance.as_object_mut(), properties) {
+        instance.retain(|key, _| properties.contains_key(key));
+    }
+
     // Correct use of jsonschema
-    let instance = &value;
-    let result = jsonschema::validate(&schema_value, instance);
+    let result = jsonschema::validate(&schema_value, &instance);
 
     if let Err(errors) = result {
         return Err(serde_json::Error::custom(format!(
@@ -0,0 +1,24 @@
+//! MATPOWER case format (`.m`), used by the standard IEEE test systems.
+//!
+//! Each bus of the case becomes a voltage level with a single bus, voltage levels linked by
+//! transformers being grouped in the same substation. Values in per-unit of the case base
+//! power and of the base voltage of the buses are converted to SI units.
+
+mod reader;
+mod writer;
+
+pub use reader::from_matpower;
+pub use writer::{to_matpower, MatpowerExport, MatpowerMapping};
+
+/// Error while reading a MATPOWER case, with the number of the faulty line
+#[derive(Debug, thiserror::Error)]
+pub enum MatpowerError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error("Line {line}: unknown bus {bus}")]
+    UnknownBus { line: usize, bus: i64 },
+    #[error("Missing {0}")]
+    Missing(String),
+    #[error("Invalid network: {0}")]
+    Network(#[from] serde_json::Error),
+}
@@ -0,0 +1,488 @@
+//! Reader of MATPOWER cases
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use super::MatpowerError;
+use crate::libs::bus_branch::{nominal_v, substations, BranchEnd, Groups, PerUnitBranch};
+use crate::libs::version::IidmVersion;
+use crate::Network;
+
+/// Reads a network from a MATPOWER case, the `mpc` structure of a `.m` file.
+///
+/// Only `mpc.baseMVA`, `mpc.bus`, `mpc.gen` and `mpc.branch` are read, costs and other
+/// fields being ignored.
+pub fn from_matpower(id: &str, matpower: &str) -> Result<Network, MatpowerError> {
+    let case = Case::parse(matpower)?;
+    let network = case.to_json(id)?;
+    Ok(serde_json::from_value(network)?)
+}
+
+/// Row of a matrix of the case, with the line it was read at
+struct Row {
+    line: usize,
+    values: Vec<f64>,
+}
+
+impl Row {
+    fn error(&self, message: String) -> MatpowerError {
+        MatpowerError::Syntax {
+            line: self.line,
+            message,
+        }
+    }
+
+    // Columns are numbered from 1, as in the MATPOWER documentation
+    fn get(&self, column: usize, name: &str) -> Result<f64, MatpowerError> {
+        self.values
+            .get(column - 1)
+            .copied()
+            .ok_or_else(|| self.error(format!("missing column {} ({})", column, name)))
+    }
+
+    fn bus(&self, column: usize, name: &str) -> Result<i64, MatpowerError> {
+        let value = self.get(column, name)?;
+        if value.fract() != 0.0 {
+            return Err(self.error(format!("invalid {} {}", name, value)));
+        }
+        Ok(value as i64)
+    }
+}
+
+/// Matrix being read, cell arrays such as bus names are skipped
+struct Matrix {
+    name: String,
+    rows: Vec<Row>,
+    is_cell: bool,
+}
+
+#[derive(Default)]
+struct Case {
+    base_mva: Option<f64>,
+    matrices: HashMap<String, Vec<Row>>,
+}
+
+impl Case {
+    fn parse(matpower: &str) -> Result<Self, MatpowerError> {
+        let mut case = Self::default();
+        let mut matrix: Option<Matrix> = None;
+
+        for (index, line) in matpower.lines().enumerate() {
+            let number = index + 1;
+            let mut text = line.split('%').next().unwrap_or_default().trim();
+
+            if matrix.is_none() {
+                let Some((name, value)) = text
+                    .strip_prefix("mpc.")
+                    .and_then(|assignment| assignment.split_once('='))
+                else {
+                    // Function declaration, blank lines and other statements
+                    continue;
+                };
+                let name = name.trim().to_string();
+                let value = value.trim();
+                if let Some(rest) = value.strip_prefix('[') {
+                    matrix = Some(Matrix {
+                        name,
+                        rows: Vec::new(),
+                        is_cell: false,
+                    });
+                    text = rest;
+                } else if let Some(rest) = value.strip_prefix('{') {
+                    matrix = Some(Matrix {
+                        name,
+                        rows: Vec::new(),
+                        is_cell: true,
+                    });
+                    text = rest;
+                } else {
+                    if name == "baseMVA" {
+                        let value = value.trim_end_matches(';').trim();
+                        let base_mva = value.parse().map_err(|_| MatpowerError::Syntax {
+                            line: number,
+                            message: format!("invalid baseMVA '{}'", value),
+                        })?;
+                        case.base_mva = Some(base_mva);
+                    }
+                    continue;
+                }
+            }
+
+            let Some(current) = matrix.as_mut() else {
+                continue;
+            };
+            let (content, closed) = match text.find([']', '}']) {
+                Some(end) => (&text[..end], true),
+                None => (text, false),
+            };
+            if !current.is_cell {
+                // Rows end with a semicolon or with the line
+                for row in content.split(';') {
+                    let values = row
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .filter(|value| !value.is_empty())
+                        .map(|value| {
+                            value.parse().map_err(|_| MatpowerError::Syntax {
+                                line: number,
+                                message: format!(
+                                    "invalid number '{}' in mpc.{}",
+                                    value, current.name
+                                ),
+                            })
+                        })
+                        .collect::<Result<Vec<f64>, _>>()?;
+                    if !values.is_empty() {
+                        current.rows.push(Row {
+                            line: number,
+                            values,
+                        });
+                    }
+                }
+            }
+            if closed {
+                if let Some(matrix) = matrix.take() {
+                    case.matrices.insert(matrix.name, matrix.rows);
+                }
+            }
+        }
+
+        if let Some(matrix) = matrix {
+            return Err(MatpowerError::Syntax {
+                line: matpower.lines().count(),
+                message: format!("unterminated mpc.{}", matrix.name),
+            });
+        }
+        Ok(case)
+    }
+
+    fn matrix(&self, name: &str) -> Result<&[Row], MatpowerError> {
+        self.matrices
+            .get(name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| MatpowerError::Missing(format!("mpc.{}", name)))
+    }
+
+    fn to_json(&self, id: &str) -> Result<Value, MatpowerError> {
+        let base_mva = self
+            .base_mva
+            .ok_or_else(|| MatpowerError::Missing("mpc.baseMVA".to_string()))?;
+        let buses = self
+            .matrix("bus")?
+            .iter()
+            .map(Bus::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let generators = self
+            .matrix("gen")?
+            .iter()
+            .map(Generator::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let branches = self
+            .matrix("branch")?
+            .iter()
+            .map(Branch::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut network = Builder {
+            base_mva,
+            buses: &buses,
+            substations: Groups::new(buses.len()),
+            voltage_levels: Vec::new(),
+            lines: Vec::new(),
+            transformers: Vec::new(),
+            shunts: Vec::new(),
+            parallels: HashMap::new(),
+        };
+        for branch in &branches {
+            network.branch(branch)?;
+        }
+        for bus in &buses {
+            network.bus(bus);
+        }
+        let mut generator_counts: HashMap<i64, usize> = HashMap::new();
+        for generator in &generators {
+            let count = generator_counts.entry(generator.bus).or_default();
+            *count += 1;
+            network.generator(generator, *count)?;
+        }
+
+        Ok(json!({
+            "version": IidmVersion::LATEST.to_string(),
+            "id": id,
+            "caseDate": DateTime::UNIX_EPOCH.fixed_offset(),
+            "forecastDistance": 0,
+            "sourceFormat": "MATPOWER",
+            "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+            "substations": network.substations(),
+            "lines": network.lines,
+            "shuntCompensators": network.shunts,
+        }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BusType {
+    Pq,
+    Pv,
+    Reference,
+    Isolated,
+}
+
+struct Bus {
+    number: i64,
+    bus_type: BusType,
+    active_demand: f64,
+    reactive_demand: f64,
+    shunt_conductance: f64,
+    shunt_susceptance: f64,
+    voltage_magnitude: f64,
+    voltage_angle: f64,
+    base_kv: f64,
+}
+
+impl Bus {
+    fn parse(row: &Row) -> Result<Self, MatpowerError> {
+        let bus_type = match row.get(2, "BUS_TYPE")? {
+            1.0 => BusType::Pq,
+            2.0 => BusType::Pv,
+            3.0 => BusType::Reference,
+            4.0 => BusType::Isolated,
+            other => return Err(row.error(format!("invalid bus type {}", other))),
+        };
+        Ok(Self {
+            number: row.bus(1, "BUS_I")?,
+            bus_type,
+            active_demand: row.get(3, "PD")?,
+            reactive_demand: row.get(4, "QD")?,
+            shunt_conductance: row.get(5, "GS")?,
+            shunt_susceptance: row.get(6, "BS")?,
+            voltage_magnitude: row.get(8, "VM")?,
+            voltage_angle: row.get(9, "VA")?,
+            base_kv: row.get(10, "BASE_KV")?,
+        })
+    }
+
+    fn nominal_v(&self) -> f64 {
+        nominal_v(self.base_kv)
+    }
+
+    fn id(&self) -> String {
+        format!("BUS-{}", self.number)
+    }
+
+    fn voltage_level_id(&self) -> String {
+        format!("VL-{}", self.number)
+    }
+
+    fn end(&self) -> BranchEnd {
+        BranchEnd {
+            voltage_level_id: self.voltage_level_id(),
+            bus_id: self.id(),
+            nominal_v: self.nominal_v(),
+        }
+    }
+}
+
+struct Generator {
+    line: usize,
+    bus: i64,
+    active_power: f64,
+    reactive_power: f64,
+    maximum_reactive_power: f64,
+    minimum_reactive_power: f64,
+    voltage_setpoint: f64,
+    in_service: bool,
+    maximum_active_power: f64,
+    minimum_active_power: f64,
+}
+
+impl Generator {
+    fn parse(row: &Row) -> Result<Self, MatpowerError> {
+        Ok(Self {
+            line: row.line,
+            bus: row.bus(1, "GEN_BUS")?,
+            active_power: row.get(2, "PG")?,
+            reactive_power: row.get(3, "QG")?,
+            maximum_reactive_power: row.get(4, "QMAX")?,
+            minimum_reactive_power: row.get(5, "QMIN")?,
+            voltage_setpoint: row.get(6, "VG")?,
+            in_service: row.get(8, "GEN_STATUS")? > 0.0,
+            maximum_active_power: row.get(9, "PMAX")?,
+            minimum_active_power: row.get(10, "PMIN")?,
+        })
+    }
+}
+
+struct Branch {
+    line: usize,
+    from: i64,
+    to: i64,
+    r: f64,
+    x: f64,
+    b: f64,
+    rate_a: f64,
+    ratio: f64,
+    shift: f64,
+    in_service: bool,
+}
+
+impl Branch {
+    fn parse(row: &Row) -> Result<Self, MatpowerError> {
+        Ok(Self {
+            line: row.line,
+            from: row.bus(1, "F_BUS")?,
+            to: row.bus(2, "T_BUS")?,
+            r: row.get(3, "BR_R")?,
+            x: row.get(4, "BR_X")?,
+            b: row.get(5, "BR_B")?,
+            rate_a: row.get(6, "RATE_A")?,
+            ratio: row.get(9, "TAP")?,
+            shift: row.get(10, "SHIFT")?,
+            in_service: row.get(11, "BR_STATUS")? > 0.0,
+        })
+    }
+
+    // A null ratio is a line, unless the branch links two base voltages
+    fn is_transformer(&self, from: &Bus, to: &Bus) -> bool {
+        self.ratio != 0.0 || self.shift != 0.0 || from.nominal_v() != to.nominal_v()
+    }
+}
+
+struct Builder<'a> {
+    base_mva: f64,
+    buses: &'a [Bus],
+    // Buses linked by transformers, which are in the same substation
+    substations: Groups,
+    voltage_levels: Vec<Value>,
+    lines: Vec<Value>,
+    // Transformers with the index of the bus whose substation holds them
+    transformers: Vec<(usize, Value)>,
+    shunts: Vec<Value>,
+    // Number of branches between two buses, to name the parallel ones
+    parallels: HashMap<(i64, i64), usize>,
+}
+
+impl Builder<'_> {
+    fn find(&self, number: i64, line: usize) -> Result<usize, MatpowerError> {
+        self.buses
+            .iter()
+            .position(|bus| bus.number == number)
+            .ok_or(MatpowerError::UnknownBus { line, bus: number })
+    }
+
+    fn bus(&mut self, bus: &Bus) {
+        let connected = bus.bus_type != BusType::Isolated;
+        let nominal_v = bus.nominal_v();
+        let mut loads = Vec::new();
+        if bus.active_demand != 0.0 || bus.reactive_demand != 0.0 {
+            loads.push(json!({
+                "id": format!("LOAD-{}", bus.number),
+                "loadType": "UNDEFINED",
+                "p0": bus.active_demand,
+                "q0": bus.reactive_demand,
+                "bus": if connected { bus.id() } else { String::new() },
+                "connectableBus": bus.id(),
+            }));
+        }
+
+        // Shunt admittances are given in MW and MVAr consumed at 1 p.u. voltage
+        if bus.shunt_conductance != 0.0 || bus.shunt_susceptance != 0.0 {
+            self.shunts.push(json!({
+                "id": format!("SHUNT-{}", bus.number),
+                "sectionCount": 1,
+                "shuntLinearModel": {
+                    "bPerSection": bus.shunt_susceptance / nominal_v.powi(2),
+                    "gPerSection": bus.shunt_conductance / nominal_v.powi(2),
+                    "maximumSectionCount": 1,
+                },
+                "bus": if connected { bus.id() } else { String::new() },
+                "connectableBus": bus.id(),
+                "voltageLevelId": bus.voltage_level_id(),
+            }));
+        }
+
+        self.voltage_levels.push(json!({
+            "id": bus.voltage_level_id(),
+            "nominalV": nominal_v,
+            "topologyKind": "BUS_BREAKER",
+            "generators": [],
+            "loads": loads,
+            "busBreakerTopology": {
+                "buses": [{
+                    "id": bus.id(),
+                    "v": bus.voltage_magnitude * nominal_v,
+                    "angle": bus.voltage_angle,
+                }],
+                "switches": [],
+            },
+        }));
+    }
+
+    fn generator(&mut self, generator: &Generator, count: usize) -> Result<(), MatpowerError> {
+        let index = self.find(generator.bus, generator.line)?;
+        let bus = &self.buses[index];
+        let connected = generator.in_service && bus.bus_type != BusType::Isolated;
+        let generator = json!({
+            "id": format!("GEN-{}-{}", bus.number, count),
+            "energySource": "OTHER",
+            "minP": generator.minimum_active_power,
+            "maxP": generator.maximum_active_power,
+            "voltageRegulatorOn": matches!(bus.bus_type, BusType::Pv | BusType::Reference),
+            "targetP": generator.active_power,
+            "targetV": generator.voltage_setpoint * bus.nominal_v(),
+            "targetQ": generator.reactive_power,
+            "bus": if connected { bus.id() } else { String::new() },
+            "connectableBus": bus.id(),
+            "minMaxReactiveLimits": {
+                "minQ": generator.minimum_reactive_power,
+                "maxQ": generator.maximum_reactive_power,
+            },
+        });
+        if let Some(generators) = self.voltage_levels[index]["generators"].as_array_mut() {
+            generators.push(generator);
+        }
+        Ok(())
+    }
+
+    fn branch(&mut self, branch: &Branch) -> Result<(), MatpowerError> {
+        let from_index = self.find(branch.from, branch.line)?;
+        let to_index = self.find(branch.to, branch.line)?;
+        let (from, to) = (&self.buses[from_index], &self.buses[to_index]);
+
+        let count = self.parallels.entry((from.number, to.number)).or_default();
+        *count += 1;
+        let per_unit = PerUnitBranch {
+            from: from.end(),
+            to: to.end(),
+            r: branch.r,
+            x: branch.x,
+            b: branch.b,
+            rating: branch.rate_a,
+            ratio: branch.ratio,
+            shift: branch.shift,
+            in_service: branch.in_service,
+        };
+        if branch.is_transformer(from, to) {
+            let id = format!("TWT-{}-{}-{}", from.number, to.number, count);
+            let transformer = per_unit.two_windings_transformer(id, self.base_mva);
+            self.substations.join(from_index, to_index);
+            self.transformers.push((from_index, transformer));
+        } else {
+            let id = format!("LINE-{}-{}-{}", from.number, to.number, count);
+            self.lines.push(per_unit.line(id, self.base_mva));
+        }
+        Ok(())
+    }
+
+    // Substations are named after the first bus they hold
+    fn substations(&mut self) -> Vec<Value> {
+        let buses = self.buses;
+        substations(
+            std::mem::take(&mut self.voltage_levels),
+            std::mem::take(&mut self.transformers),
+            &mut self.substations,
+            |index| format!("SUB-{}", buses[index].number),
+        )
+    }
+}
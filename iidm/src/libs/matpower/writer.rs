@@ -0,0 +1,734 @@
+//! Writer of MATPOWER cases
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::libs::bus_branch::{reactive_bounds, unsupported_equipments};
+use crate::{
+    DanglingLine, Line, Network, OperationalLimitsHolder, Side, TopologyKind,
+    TwoWindingsTransformer, VoltageLevel,
+};
+
+/// Base power of the exported cases, in MVA
+const BASE_MVA: f64 = 100.0;
+
+/// Reactive limits of generators without limits, in MVAr
+const DEFAULT_REACTIVE_LIMIT: f64 = 9999.0;
+
+/// MATPOWER case written from a network, with the mapping of its rows to the IIDM ids
+pub struct MatpowerExport {
+    pub case: String,
+    pub mapping: MatpowerMapping,
+    pub warnings: Vec<String>,
+}
+
+/// IIDM ids of the buses, generators and branches of a MATPOWER case, written next to the
+/// case to read the results of MATPOWER tools back
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MatpowerMapping {
+    /// Buses merged in each MATPOWER bus, by bus number. Dangling lines and tie lines are
+    /// connected to a bus of their own, mapped to their id
+    pub buses: BTreeMap<usize, Vec<String>>,
+    /// Id of the generator of each row of `mpc.gen`
+    pub generators: Vec<String>,
+    /// Id of the line, transformer or dangling line of each row of `mpc.branch`
+    pub branches: Vec<String>,
+}
+
+/// Writes the bus view of a network as a MATPOWER case.
+///
+/// Buses are numbered in the order of the network, the ones linked by closed switches being
+/// merged. Nodes of node-breaker voltage levels are merged the same way, through closed
+/// switches and internal connections, the buses without busbar section being isolated. Values are converted to per-unit of a base power
+/// of 100 MVA and of the nominal voltage of the voltage levels. Equipments without MATPOWER
+/// equivalent are skipped and reported in the warnings.
+pub fn to_matpower(network: &Network) -> MatpowerExport {
+    let mut writer = Writer::default();
+    writer.buses(network);
+    writer.injections(network);
+    writer.lines(network);
+    writer.transformers(network);
+    writer.dangling_lines(network);
+    writer.skipped(network);
+    writer.reference_bus();
+    MatpowerExport {
+        case: writer.write(&network.id),
+        mapping: writer.mapping,
+        warnings: writer.warnings,
+    }
+}
+
+struct BusRow {
+    bus_type: u8,
+    active_demand: f64,
+    reactive_demand: f64,
+    shunt_conductance: f64,
+    shunt_susceptance: f64,
+    voltage_magnitude: f64,
+    voltage_angle: f64,
+    base_kv: f64,
+}
+
+impl BusRow {
+    fn new(base_kv: f64) -> Self {
+        Self {
+            bus_type: 1,
+            active_demand: 0.0,
+            reactive_demand: 0.0,
+            shunt_conductance: 0.0,
+            shunt_susceptance: 0.0,
+            voltage_magnitude: 1.0,
+            voltage_angle: 0.0,
+            base_kv,
+        }
+    }
+}
+
+struct GeneratorRow {
+    bus: usize,
+    active_power: f64,
+    reactive_power: f64,
+    maximum_reactive_power: f64,
+    minimum_reactive_power: f64,
+    voltage_setpoint: f64,
+    in_service: bool,
+    maximum_active_power: f64,
+    minimum_active_power: f64,
+    regulating: bool,
+}
+
+struct BranchRow {
+    from: usize,
+    to: usize,
+    r: f64,
+    x: f64,
+    b: f64,
+    rate: f64,
+    ratio: f64,
+    shift: f64,
+    in_service: bool,
+}
+
+/// End of a branch, the number of its bus and whether it is connected
+type Terminal = (usize, bool);
+
+#[derive(Default)]
+struct Writer {
+    warnings: Vec<String>,
+    mapping: MatpowerMapping,
+    // MATPOWER bus number of each IIDM bus
+    numbers: BTreeMap<String, usize>,
+    // Terminal of each node of the node-breaker voltage levels, by voltage level id
+    nodes: HashMap<String, HashMap<i32, Terminal>>,
+    buses: Vec<BusRow>,
+    generators: Vec<GeneratorRow>,
+    branches: Vec<BranchRow>,
+}
+
+impl Writer {
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    fn add_bus(&mut self, row: BusRow, ids: Vec<String>) -> usize {
+        self.buses.push(row);
+        let number = self.buses.len();
+        for id in &ids {
+            self.numbers.insert(id.clone(), number);
+        }
+        self.mapping.buses.insert(number, ids);
+        number
+    }
+
+    fn buses(&mut self, network: &Network) {
+        let referenced = network.referenced_nodes();
+        for voltage_level in network
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.voltage_levels)
+        {
+            match voltage_level.topology_kind {
+                TopologyKind::BusBreaker => self.bus_breaker_buses(voltage_level),
+                TopologyKind::NodeBreaker => self.node_breaker_buses(
+                    network,
+                    voltage_level,
+                    referenced.get(&voltage_level.id),
+                ),
+            }
+        }
+    }
+
+    fn bus_breaker_buses(&mut self, voltage_level: &VoltageLevel) {
+        let Some(topology) = &voltage_level.bus_breaker_topology else {
+            return;
+        };
+
+        // Buses linked by closed switches are a single MATPOWER bus
+        let mut groups: Vec<Vec<&str>> = topology
+            .buses
+            .iter()
+            .map(|bus| vec![bus.id.as_str()])
+            .collect();
+        for switch in topology.switches.iter().filter(|switch| !switch.open) {
+            let position = |id: &str| groups.iter().position(|group| group.contains(&id));
+            if let (Some(a), Some(b)) = (position(&switch.bus1), position(&switch.bus2)) {
+                if a != b {
+                    let merged = groups.remove(a.max(b));
+                    groups[a.min(b)].extend(merged);
+                }
+            }
+        }
+
+        for group in groups {
+            let mut row = BusRow::new(voltage_level.nominal_v);
+            let first = topology.buses.iter().find(|bus| bus.id == group[0]);
+            if let Some(v) = first.and_then(|bus| bus.v) {
+                row.voltage_magnitude = v / voltage_level.nominal_v;
+            }
+            if let Some(angle) = first.and_then(|bus| bus.angle) {
+                row.voltage_angle = angle;
+            }
+            self.add_bus(row, group.into_iter().map(str::to_string).collect());
+        }
+    }
+
+    // Nodes linked by closed switches and internal connections are a single MATPOWER bus,
+    // named after its busbar sections. Buses without busbar section are isolated, unless the
+    // voltage level has none
+    fn node_breaker_buses(
+        &mut self,
+        network: &Network,
+        voltage_level: &VoltageLevel,
+        referenced: Option<&HashSet<i32>>,
+    ) {
+        let topology = voltage_level.node_breaker_topology.as_ref();
+        let switches = topology
+            .into_iter()
+            .flat_map(|topology| &topology.switches)
+            .chain(
+                network
+                    .switches
+                    .iter()
+                    .filter(|switch| switch.voltage_level_id == voltage_level.id),
+            );
+        let links: Vec<(i32, i32)> = switches
+            .filter(|switch| !switch.open)
+            .filter_map(|switch| Some((switch.node1?, switch.node2?)))
+            .chain(
+                topology
+                    .into_iter()
+                    .flat_map(|topology| &topology.internal_connections)
+                    .map(|connection| (connection.node1, connection.node2)),
+            )
+            .collect();
+
+        let mut nodes: BTreeSet<i32> = voltage_level
+            .nodes()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        nodes.extend(links.iter().flat_map(|(node1, node2)| [*node1, *node2]));
+        nodes.extend(referenced.into_iter().flatten());
+        let mut groups: Vec<Vec<i32>> = nodes.into_iter().map(|node| vec![node]).collect();
+        for (node1, node2) in links {
+            let position = |node: i32| groups.iter().position(|group| group.contains(&node));
+            if let (Some(a), Some(b)) = (position(node1), position(node2)) {
+                if a != b {
+                    let merged = groups.remove(a.max(b));
+                    groups[a.min(b)].extend(merged);
+                }
+            }
+        }
+
+        let busbar_sections: Vec<_> = voltage_level
+            .busbar_sections
+            .iter()
+            .flatten()
+            .filter(|section| section.node.is_some())
+            .collect();
+        for group in groups {
+            let sections: Vec<_> = busbar_sections
+                .iter()
+                .filter(|section| section.node.is_some_and(|node| group.contains(&node)))
+                .collect();
+            let connected = !sections.is_empty() || busbar_sections.is_empty();
+            let mut row = BusRow::new(voltage_level.nominal_v);
+            if !connected {
+                row.bus_type = 4;
+            }
+            if let Some(v) = sections.first().and_then(|section| section.v) {
+                row.voltage_magnitude = v / voltage_level.nominal_v;
+            }
+            if let Some(angle) = sections.first().and_then(|section| section.angle) {
+                row.voltage_angle = angle;
+            }
+            let ids = if sections.is_empty() {
+                vec![format!("{}_{}", voltage_level.id, group[0])]
+            } else {
+                sections.iter().map(|section| section.id.clone()).collect()
+            };
+            let number = self.add_bus(row, ids);
+            let nodes = self.nodes.entry(voltage_level.id.clone()).or_default();
+            for node in group {
+                nodes.insert(node, (number, connected));
+            }
+        }
+    }
+
+    fn terminal(
+        &self,
+        voltage_level: &str,
+        node: Option<i32>,
+        bus: &str,
+        connectable_bus: &str,
+    ) -> Option<Terminal> {
+        if let Some(node) = node {
+            return self.nodes.get(voltage_level)?.get(&node).copied();
+        }
+        let connected = !bus.is_empty();
+        let bus = if connected { bus } else { connectable_bus };
+        self.numbers.get(bus).map(|number| (*number, connected))
+    }
+
+    fn injections(&mut self, network: &Network) {
+        for voltage_level in network
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.voltage_levels)
+        {
+            for load in voltage_level.loads.iter().flatten() {
+                if let Some((number, true)) =
+                    self.terminal(&voltage_level.id, load.node, &load.bus, "")
+                {
+                    let row = &mut self.buses[number - 1];
+                    row.active_demand += load.p0;
+                    row.reactive_demand += load.q0;
+                }
+            }
+
+            for generator in voltage_level.generators.iter().flatten() {
+                let Some((bus, connected)) = self.terminal(
+                    &voltage_level.id,
+                    generator.node,
+                    &generator.bus,
+                    &generator.connectable_bus,
+                ) else {
+                    continue;
+                };
+                let (minimum_reactive_power, maximum_reactive_power) =
+                    reactive_bounds(generator, &mut self.warnings)
+                        .unwrap_or((-DEFAULT_REACTIVE_LIMIT, DEFAULT_REACTIVE_LIMIT));
+                self.generators.push(GeneratorRow {
+                    bus,
+                    active_power: generator.target_p,
+                    reactive_power: generator.target_q,
+                    maximum_reactive_power,
+                    minimum_reactive_power,
+                    voltage_setpoint: generator.target_v / voltage_level.nominal_v,
+                    in_service: connected,
+                    maximum_active_power: generator.max_p,
+                    minimum_active_power: generator.min_p,
+                    regulating: generator.voltage_regulator_on,
+                });
+                self.mapping.generators.push(generator.id.clone());
+            }
+        }
+
+        // Shunt admittances are written as the power consumed at 1 p.u. voltage
+        for shunt in &network.shunt_compensators {
+            let Some((number, true)) =
+                self.terminal(&shunt.voltage_level_id, shunt.node, &shunt.bus, "")
+            else {
+                continue;
+            };
+            let Some((b, g)) = shunt.admittance() else {
+                self.warn(format!(
+                    "Section count of shunt compensator {} is invalid",
+                    shunt.id
+                ));
+                continue;
+            };
+            let row = &mut self.buses[number - 1];
+            row.shunt_susceptance += b * row.base_kv.powi(2);
+            row.shunt_conductance += g * row.base_kv.powi(2);
+        }
+    }
+
+    fn base_kv(&self, number: usize) -> f64 {
+        self.buses[number - 1].base_kv
+    }
+
+    // Ratings are apparent powers in MVA, computed from the permanent current limits
+    fn rate(holder: &impl OperationalLimitsHolder, sides: &[(Side, f64)]) -> f64 {
+        sides
+            .iter()
+            .filter_map(|(side, base_kv)| {
+                holder
+                    .selected_operational_limits_group(*side)
+                    .and_then(|group| group.current_limits.as_ref())
+                    .map(|limits| limits.permanent_limit * 3f64.sqrt() * base_kv / 1000.0)
+            })
+            .reduce(f64::min)
+            .unwrap_or_default()
+    }
+
+    fn lines(&mut self, network: &Network) {
+        for line in &network.lines {
+            let Some((from, to)) = self.branch_terminals(
+                &line.id,
+                (
+                    &line.voltage_level_id1,
+                    line.node1,
+                    &line.bus1,
+                    &line.connectable_bus1,
+                ),
+                (
+                    &line.voltage_level_id2,
+                    line.node2,
+                    &line.bus2,
+                    &line.connectable_bus2,
+                ),
+            ) else {
+                continue;
+            };
+            if line.g1 != 0.0 || line.g2 != 0.0 {
+                self.warn(format!("Conductances of line {} are not exported", line.id));
+            }
+            self.line(line, from, to);
+        }
+    }
+
+    fn line(&mut self, line: &Line, from: Terminal, to: Terminal) {
+        let (from_kv, to_kv) = (self.base_kv(from.0), self.base_kv(to.0));
+        let base_impedance = to_kv.powi(2) / BASE_MVA;
+        self.branches.push(BranchRow {
+            from: from.0,
+            to: to.0,
+            r: line.r / base_impedance,
+            x: line.x / base_impedance,
+            b: (line.b1 + line.b2) * base_impedance,
+            rate: Self::rate(line, &[(Side::One, from_kv), (Side::Two, to_kv)]),
+            ratio: 0.0,
+            shift: 0.0,
+            in_service: from.1 && to.1,
+        });
+        self.mapping.branches.push(line.id.clone());
+    }
+
+    fn branch_terminals(
+        &mut self,
+        id: &str,
+        end1: (&str, Option<i32>, &str, &str),
+        end2: (&str, Option<i32>, &str, &str),
+    ) -> Option<(Terminal, Terminal)> {
+        match (
+            self.terminal(end1.0, end1.1, end1.2, end1.3),
+            self.terminal(end2.0, end2.1, end2.2, end2.3),
+        ) {
+            (Some(from), Some(to)) => Some((from, to)),
+            _ => {
+                self.warn(format!("Branch {} is not connected to exported buses", id));
+                None
+            }
+        }
+    }
+
+    fn transformers(&mut self, network: &Network) {
+        for transformer in network
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.two_windings_transformers)
+        {
+            let Some((from, to)) = self.branch_terminals(
+                &transformer.id,
+                (
+                    &transformer.voltage_level_id1,
+                    transformer.node1,
+                    &transformer.bus1,
+                    &transformer.connectable_bus1,
+                ),
+                (
+                    &transformer.voltage_level_id2,
+                    transformer.node2,
+                    &transformer.bus2,
+                    &transformer.connectable_bus2,
+                ),
+            ) else {
+                continue;
+            };
+            self.transformer(transformer, from, to);
+        }
+    }
+
+    // The MATPOWER ratio is the ratio of the per-unit voltages of both sides, with the
+    // impedances in per-unit of the second side
+    fn transformer(&mut self, transformer: &TwoWindingsTransformer, from: Terminal, to: Terminal) {
+        let (from_kv, to_kv) = (self.base_kv(from.0), self.base_kv(to.0));
+        let base_impedance = to_kv.powi(2) / BASE_MVA;
+
+        let mut rho = transformer.rated_u2 / transformer.rated_u1;
+        let mut alpha = 0.0;
+        if let Some(tap_changer) = &transformer.ratio_tap_changer {
+            let step = usize::try_from(tap_changer.tap_position - tap_changer.low_tap_position)
+                .ok()
+                .and_then(|step| tap_changer.steps.get(step));
+            match step {
+                Some(step) => rho *= step.rho,
+                None => self.warn(format!(
+                    "Tap position {} of the ratio tap changer of transformer {} has no step, \
+                     the nominal ratio is exported",
+                    tap_changer.tap_position, transformer.id
+                )),
+            }
+        }
+        if let Some(tap_changer) = &transformer.phase_tap_changer {
+            let step = usize::try_from(tap_changer.tap_position - tap_changer.low_tap_position)
+                .ok()
+                .and_then(|step| tap_changer.steps.get(step));
+            match step {
+                Some(step) => {
+                    rho *= step.rho;
+                    alpha = step.alpha;
+                }
+                None => self.warn(format!(
+                    "Tap position {} of the phase tap changer of transformer {} has no step, \
+                     no phase shift is exported",
+                    tap_changer.tap_position, transformer.id
+                )),
+            }
+        }
+        if transformer.g != 0.0 {
+            self.warn(format!(
+                "Conductance of transformer {} is not exported",
+                transformer.id
+            ));
+        }
+
+        self.branches.push(BranchRow {
+            from: from.0,
+            to: to.0,
+            r: transformer.r / base_impedance,
+            x: transformer.x / base_impedance,
+            b: transformer.b * base_impedance,
+            rate: Self::rate(transformer, &[(Side::One, from_kv), (Side::Two, to_kv)]),
+            ratio: to_kv / (rho * from_kv),
+            // IIDM angles are added to the first side voltage, MATPOWER ones are its angle
+            shift: -alpha,
+            in_service: from.1 && to.1,
+        });
+        self.mapping.branches.push(transformer.id.clone());
+    }
+
+    // Boundaries of dangling lines are buses consuming their constant power
+    fn dangling_lines(&mut self, network: &Network) {
+        for dangling_line in &network.dangling_lines {
+            let Some(terminal) = self.terminal(
+                &dangling_line.voltage_level_id,
+                dangling_line.node,
+                &dangling_line.bus,
+                &dangling_line.connectable_bus,
+            ) else {
+                self.warn(format!(
+                    "Dangling line {} is not connected to an exported bus",
+                    dangling_line.id
+                ));
+                continue;
+            };
+            let boundary = self.boundary(&dangling_line.id, terminal.0, [dangling_line]);
+            self.dangling_line(dangling_line, terminal, boundary);
+        }
+
+        for tie_line in &network.tie_lines {
+            let halves = [&tie_line.dangling_line1, &tie_line.dangling_line2];
+            let terminals = halves.map(|half| {
+                self.terminal(
+                    &half.voltage_level_id,
+                    half.node,
+                    &half.bus,
+                    &half.connectable_bus,
+                )
+            });
+            let [Some(terminal1), Some(terminal2)] = terminals else {
+                self.warn(format!(
+                    "Tie line {} is not connected to exported buses",
+                    tie_line.id
+                ));
+                continue;
+            };
+            let boundary = self.boundary(&tie_line.id, terminal1.0, halves);
+            self.dangling_line(&tie_line.dangling_line1, terminal1, boundary);
+            self.dangling_line(&tie_line.dangling_line2, terminal2, boundary);
+        }
+    }
+
+    fn boundary<'a>(
+        &mut self,
+        id: &str,
+        bus: usize,
+        halves: impl IntoIterator<Item = &'a DanglingLine>,
+    ) -> usize {
+        let mut row = BusRow::new(self.base_kv(bus));
+        for half in halves {
+            row.active_demand += half.p0;
+            row.reactive_demand += half.q0;
+        }
+        self.add_bus(row, vec![id.to_string()])
+    }
+
+    fn dangling_line(&mut self, dangling_line: &DanglingLine, terminal: Terminal, boundary: usize) {
+        let base_kv = self.base_kv(terminal.0);
+        let base_impedance = base_kv.powi(2) / BASE_MVA;
+        if dangling_line.g != 0.0 {
+            self.warn(format!(
+                "Conductance of dangling line {} is not exported",
+                dangling_line.id
+            ));
+        }
+        self.branches.push(BranchRow {
+            from: terminal.0,
+            to: boundary,
+            r: dangling_line.r / base_impedance,
+            x: dangling_line.x / base_impedance,
+            b: dangling_line.b * base_impedance,
+            rate: Self::rate(dangling_line, &[(Side::One, base_kv)]),
+            ratio: 0.0,
+            shift: 0.0,
+            in_service: terminal.1,
+        });
+        self.mapping.branches.push(dangling_line.id.clone());
+    }
+
+    fn skipped(&mut self, network: &Network) {
+        let warnings: Vec<String> = unsupported_equipments(network)
+            .map(|equipment| {
+                format!(
+                    "{} has no MATPOWER equivalent, it is not exported",
+                    equipment
+                )
+            })
+            .collect();
+        self.warnings.extend(warnings);
+    }
+
+    // Buses of regulating generators are PV buses, the one of the largest generator being
+    // the reference bus
+    fn reference_bus(&mut self) {
+        let regulating = self
+            .generators
+            .iter()
+            .filter(|generator| generator.regulating && generator.in_service);
+        for generator in regulating.clone() {
+            self.buses[generator.bus - 1].bus_type = 2;
+        }
+        let reference = regulating
+            .max_by(|a, b| a.maximum_active_power.total_cmp(&b.maximum_active_power))
+            .map(|generator| generator.bus);
+        match reference {
+            Some(bus) => self.buses[bus - 1].bus_type = 3,
+            None => self.warn("No regulating generator to choose a reference bus".to_string()),
+        }
+    }
+
+    fn write(&self, id: &str) -> String {
+        let mut case = String::new();
+        let _ = writeln!(case, "function mpc = {}", function_name(id));
+        let _ = writeln!(case, "%% MATPOWER case of network {}", id);
+        case.push_str("mpc.version = '2';\n\n");
+        case.push_str("%% system MVA base\n");
+        let _ = writeln!(case, "mpc.baseMVA = {};\n", BASE_MVA);
+
+        case.push_str("%% bus data\n");
+        case.push_str("%\tbus_i\ttype\tPd\tQd\tGs\tBs\tarea\tVm\tVa\tbaseKV\tzone\tVmax\tVmin\n");
+        let buses = self.buses.iter().enumerate().map(|(index, bus)| {
+            vec![
+                (index + 1) as f64,
+                f64::from(bus.bus_type),
+                bus.active_demand,
+                bus.reactive_demand,
+                bus.shunt_conductance,
+                bus.shunt_susceptance,
+                1.0,
+                bus.voltage_magnitude,
+                bus.voltage_angle,
+                bus.base_kv,
+                1.0,
+                1.1,
+                0.9,
+            ]
+        });
+        write_matrix(&mut case, "bus", buses);
+
+        case.push_str("%% generator data\n");
+        case.push_str("%\tbus\tPg\tQg\tQmax\tQmin\tVg\tmBase\tstatus\tPmax\tPmin\n");
+        let generators = self.generators.iter().map(|generator| {
+            vec![
+                generator.bus as f64,
+                generator.active_power,
+                generator.reactive_power,
+                generator.maximum_reactive_power,
+                generator.minimum_reactive_power,
+                generator.voltage_setpoint,
+                BASE_MVA,
+                status(generator.in_service),
+                generator.maximum_active_power,
+                generator.minimum_active_power,
+            ]
+        });
+        write_matrix(&mut case, "gen", generators);
+
+        case.push_str("%% branch data\n");
+        case.push_str(
+            "%\tfbus\ttbus\tr\tx\tb\trateA\trateB\trateC\tratio\tangle\tstatus\tangmin\tangmax\n",
+        );
+        let branches = self.branches.iter().map(|branch| {
+            vec![
+                branch.from as f64,
+                branch.to as f64,
+                branch.r,
+                branch.x,
+                branch.b,
+                branch.rate,
+                branch.rate,
+                branch.rate,
+                branch.ratio,
+                branch.shift,
+                status(branch.in_service),
+                -360.0,
+                360.0,
+            ]
+        });
+        write_matrix(&mut case, "branch", branches);
+        case
+    }
+}
+
+fn status(in_service: bool) -> f64 {
+    if in_service {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn write_matrix(case: &mut String, name: &str, rows: impl Iterator<Item = Vec<f64>>) {
+    let _ = writeln!(case, "mpc.{} = [", name);
+    for row in rows {
+        let values: Vec<String> = row.iter().map(|value| (value + 0.0).to_string()).collect();
+        let _ = writeln!(case, "\t{};", values.join("\t"));
+    }
+    case.push_str("];\n\n");
+}
+
+// Case files are MATLAB functions, named after the network
+fn function_name(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => format!("case_{}", name),
+    }
+}
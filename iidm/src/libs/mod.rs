@@ -1 +1,14 @@
+pub(crate) mod bus_branch;
+pub mod cgmes;
+pub mod compression;
+pub mod error;
+pub mod ieee_cdf;
 pub mod json;
+pub mod matpower;
+pub mod psse;
+pub mod snapshot;
+pub mod stream;
+pub mod tabular;
+pub mod ucte;
+pub mod version;
+pub mod xml;
@@ -0,0 +1,21 @@
+//! PSS/E RAW format (`.raw`), versions 33 and 35.
+//!
+//! Each bus of the case becomes a bus of its own voltage level, buses linked by system
+//! switching devices sharing a voltage level and voltage levels linked by transformers
+//! sharing a substation. Areas and zones become areas of the network. Records of the data
+//! sections without IIDM equivalent are skipped and reported in the warnings of the import.
+
+mod reader;
+
+pub use reader::{from_psse, PsseImport};
+
+/// Error while reading a PSS/E RAW file, with the number of the faulty line
+#[derive(Debug, thiserror::Error)]
+pub enum PsseError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error("Line {line}: unknown bus {bus}")]
+    UnknownBus { line: usize, bus: i64 },
+    #[error("Invalid network: {0}")]
+    Network(#[from] serde_json::Error),
+}
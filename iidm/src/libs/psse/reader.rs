@@ -0,0 +1,1287 @@
+//! Reader of PSS/E RAW cases
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use super::PsseError;
+use crate::libs::bus_branch::{current_limits, nominal_v, push, Groups};
+use crate::libs::version::IidmVersion;
+use crate::Network;
+
+/// Network read from a PSS/E RAW case, with the records that could not be represented
+pub struct PsseImport {
+    pub network: Network,
+    pub warnings: Vec<String>,
+}
+
+/// Reads a network from a PSS/E RAW case of version 33 or 35.
+///
+/// The system-wide data of version 35 is ignored. DC lines, FACTS devices, multi-section
+/// lines, owners and the other data sections without IIDM equivalent are skipped, as well as
+/// the transformer controls that are not ratio or phase controls.
+pub fn from_psse(id: &str, raw: &str) -> Result<PsseImport, PsseError> {
+    let case = Case::parse(raw)?;
+    let mut warnings = case.skipped();
+    let network = case.to_json(id, &mut warnings)?;
+    Ok(PsseImport {
+        network: serde_json::from_value(network)?,
+        warnings,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Section {
+    SystemWide,
+    Bus,
+    Load,
+    FixedShunt,
+    Generator,
+    Branch,
+    SwitchingDevice,
+    Transformer,
+    Area,
+    TwoTerminalDc,
+    VscDc,
+    ImpedanceCorrection,
+    MultiTerminalDc,
+    MultiSectionLine,
+    Zone,
+    InterAreaTransfer,
+    Owner,
+    Facts,
+    SwitchedShunt,
+    Gne,
+    InductionMachine,
+    Substation,
+}
+
+impl Section {
+    fn name(self) -> &'static str {
+        match self {
+            Section::SystemWide => "System-wide",
+            Section::Bus => "Bus",
+            Section::Load => "Load",
+            Section::FixedShunt => "Fixed shunt",
+            Section::Generator => "Generator",
+            Section::Branch => "Branch",
+            Section::SwitchingDevice => "System switching device",
+            Section::Transformer => "Transformer",
+            Section::Area => "Area",
+            Section::TwoTerminalDc => "Two-terminal DC line",
+            Section::VscDc => "VSC DC line",
+            Section::ImpedanceCorrection => "Impedance correction table",
+            Section::MultiTerminalDc => "Multi-terminal DC line",
+            Section::MultiSectionLine => "Multi-section line",
+            Section::Zone => "Zone",
+            Section::InterAreaTransfer => "Inter-area transfer",
+            Section::Owner => "Owner",
+            Section::Facts => "FACTS device",
+            Section::SwitchedShunt => "Switched shunt",
+            Section::Gne => "GNE device",
+            Section::InductionMachine => "Induction machine",
+            Section::Substation => "Substation",
+        }
+    }
+
+    fn is_supported(self) -> bool {
+        matches!(
+            self,
+            Section::SystemWide
+                | Section::Bus
+                | Section::Load
+                | Section::FixedShunt
+                | Section::Generator
+                | Section::Branch
+                | Section::SwitchingDevice
+                | Section::Transformer
+                | Section::Area
+                | Section::Zone
+                | Section::SwitchedShunt
+        )
+    }
+}
+
+/// Data sections of a version and positions of the fields that moved between versions,
+/// numbered from 0
+struct Layout {
+    sections: &'static [Section],
+    generator_status: usize,
+    generator_maximum_p: usize,
+    branch_rating: usize,
+    branch_admittances: usize,
+    branch_status: usize,
+    winding_control: usize,
+    winding_limits: usize,
+    switched_shunt_id: Option<usize>,
+    switched_shunt_status: usize,
+    switched_shunt_initial: usize,
+    // Blocks are (N, B) pairs in version 33 and (S, N, B) triples in version 35
+    switched_shunt_blocks: usize,
+    switched_shunt_block_size: usize,
+}
+
+const LAYOUT_33: Layout = Layout {
+    sections: &[
+        Section::Bus,
+        Section::Load,
+        Section::FixedShunt,
+        Section::Generator,
+        Section::Branch,
+        Section::Transformer,
+        Section::Area,
+        Section::TwoTerminalDc,
+        Section::VscDc,
+        Section::ImpedanceCorrection,
+        Section::MultiTerminalDc,
+        Section::MultiSectionLine,
+        Section::Zone,
+        Section::InterAreaTransfer,
+        Section::Owner,
+        Section::Facts,
+        Section::SwitchedShunt,
+        Section::Gne,
+        Section::InductionMachine,
+    ],
+    generator_status: 14,
+    generator_maximum_p: 16,
+    branch_rating: 6,
+    branch_admittances: 9,
+    branch_status: 13,
+    winding_control: 6,
+    winding_limits: 8,
+    switched_shunt_id: None,
+    switched_shunt_status: 3,
+    switched_shunt_initial: 9,
+    switched_shunt_blocks: 10,
+    switched_shunt_block_size: 2,
+};
+
+const LAYOUT_35: Layout = Layout {
+    sections: &[
+        Section::SystemWide,
+        Section::Bus,
+        Section::Load,
+        Section::FixedShunt,
+        Section::Generator,
+        Section::Branch,
+        Section::SwitchingDevice,
+        Section::Transformer,
+        Section::Area,
+        Section::TwoTerminalDc,
+        Section::VscDc,
+        Section::ImpedanceCorrection,
+        Section::MultiTerminalDc,
+        Section::MultiSectionLine,
+        Section::Zone,
+        Section::InterAreaTransfer,
+        Section::Owner,
+        Section::Facts,
+        Section::SwitchedShunt,
+        Section::Gne,
+        Section::InductionMachine,
+        Section::Substation,
+    ],
+    generator_status: 15,
+    generator_maximum_p: 17,
+    branch_rating: 7,
+    branch_admittances: 19,
+    branch_status: 23,
+    winding_control: 15,
+    winding_limits: 18,
+    switched_shunt_id: Some(1),
+    switched_shunt_status: 4,
+    switched_shunt_initial: 11,
+    switched_shunt_blocks: 12,
+    switched_shunt_block_size: 3,
+};
+
+/// Record of a data section, with the line it was read at. Fields are separated by commas
+/// or blanks, an empty field between two commas taking its default value.
+struct Record {
+    line: usize,
+    fields: Vec<Option<String>>,
+}
+
+impl Record {
+    fn parse(line: usize, text: &str) -> Self {
+        let mut fields = Vec::new();
+        let mut field: Option<String> = None;
+        let mut quote: Option<char> = None;
+        // Whether the last field was ended by a blank, a following comma not starting a new one
+        let mut ended = false;
+        for c in text.chars() {
+            match quote {
+                Some(end) if c == end => quote = None,
+                Some(_) => field.get_or_insert_with(String::new).push(c),
+                None => match c {
+                    '\'' | '"' => {
+                        quote = Some(c);
+                        field.get_or_insert_with(String::new);
+                    }
+                    // Comments start with a slash
+                    '/' => break,
+                    ',' => {
+                        if field.is_some() || !ended {
+                            fields.push(field.take());
+                        }
+                        ended = false;
+                    }
+                    c if c.is_whitespace() => {
+                        if let Some(value) = field.take() {
+                            fields.push(Some(value));
+                            ended = true;
+                        }
+                    }
+                    c => {
+                        field.get_or_insert_with(String::new).push(c);
+                        ended = false;
+                    }
+                },
+            }
+        }
+        if field.is_some() {
+            fields.push(field);
+        }
+        Self { line, fields }
+    }
+
+    fn error(&self, message: String) -> PsseError {
+        PsseError::Syntax {
+            line: self.line,
+            message,
+        }
+    }
+
+    fn text(&self, index: usize) -> &str {
+        self.fields
+            .get(index)
+            .and_then(Option::as_deref)
+            .map(str::trim)
+            .unwrap_or_default()
+    }
+
+    fn optional(&self, index: usize, name: &str) -> Result<Option<f64>, PsseError> {
+        match self.fields.get(index).and_then(Option::as_deref) {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| self.error(format!("invalid {} '{}'", name, value))),
+            None => Ok(None),
+        }
+    }
+
+    fn number(&self, index: usize, name: &str, default: f64) -> Result<f64, PsseError> {
+        Ok(self.optional(index, name)?.unwrap_or(default))
+    }
+
+    fn integer(&self, index: usize, name: &str, default: i64) -> Result<i64, PsseError> {
+        match self.fields.get(index).and_then(Option::as_deref) {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map_err(|_| self.error(format!("invalid {} '{}'", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    // Negative bus numbers mark the metered end of branches
+    fn bus(&self, index: usize, name: &str) -> Result<i64, PsseError> {
+        match self.fields.get(index).and_then(Option::as_deref) {
+            Some(_) => Ok(self.integer(index, name, 0)?.abs()),
+            None => Err(self.error(format!("missing {}", name))),
+        }
+    }
+
+    // Identifiers default to '1'
+    fn identifier(&self, index: usize) -> &str {
+        match self.text(index) {
+            "" => "1",
+            identifier => identifier,
+        }
+    }
+}
+
+struct Case {
+    layout: &'static Layout,
+    base_mva: f64,
+    sections: HashMap<Section, Vec<Record>>,
+}
+
+impl Case {
+    fn parse(raw: &str) -> Result<Self, PsseError> {
+        let mut lines = raw
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line));
+        let header = Record::parse(1, lines.next().map(|(_, line)| line).unwrap_or_default());
+        let base_mva = header.number(1, "SBASE", 100.0)?;
+        let layout = match header.integer(2, "REV", 0)? {
+            33 => &LAYOUT_33,
+            35 => &LAYOUT_35,
+            version => {
+                return Err(header.error(format!("unsupported PSS/E version {}", version)));
+            }
+        };
+        // Two lines of case headings
+        lines.next();
+        lines.next();
+
+        let mut sections: HashMap<Section, Vec<Record>> = HashMap::new();
+        let mut position = 0;
+        // Transformer records span several lines, whose first field may be a zero
+        let mut continuation = 0;
+        for (number, line) in lines {
+            let text = line.trim();
+            // Version 35 annotates the data sections with the names of their fields
+            if text.is_empty() || text.starts_with("@!") {
+                continue;
+            }
+            let record = Record::parse(number, text);
+            let Some(&section) = layout.sections.get(position) else {
+                if record.text(0) == "Q" {
+                    break;
+                }
+                return Err(record.error("record after the last data section".to_string()));
+            };
+            if continuation > 0 {
+                continuation -= 1;
+            } else {
+                match record.text(0) {
+                    "Q" => break,
+                    // Substation data nests node, switching device and terminal blocks ended
+                    // by zeros too, it is the last section
+                    "0" if section == Section::Substation => continue,
+                    "0" => {
+                        position += 1;
+                        continue;
+                    }
+                    _ => {}
+                }
+                if section == Section::Transformer {
+                    continuation = match record.text(2) {
+                        "" | "0" => 3,
+                        _ => 4,
+                    };
+                }
+            }
+            sections.entry(section).or_default().push(record);
+        }
+
+        Ok(Self {
+            layout,
+            base_mva,
+            sections,
+        })
+    }
+
+    fn records(&self, section: Section) -> &[Record] {
+        self.sections
+            .get(&section)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn skipped(&self) -> Vec<String> {
+        self.layout
+            .sections
+            .iter()
+            .filter(|section| !section.is_supported())
+            .filter_map(|section| match self.records(*section).len() {
+                0 => None,
+                count => Some(format!(
+                    "{} data is not supported, {} lines skipped",
+                    section.name(),
+                    count
+                )),
+            })
+            .collect()
+    }
+
+    fn to_json(&self, id: &str, warnings: &mut Vec<String>) -> Result<Value, PsseError> {
+        let buses = self
+            .records(Section::Bus)
+            .iter()
+            .map(Bus::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut network = Builder {
+            layout: self.layout,
+            base_mva: self.base_mva,
+            indexes: buses
+                .iter()
+                .enumerate()
+                .map(|(index, bus)| (bus.number, index))
+                .collect(),
+            buses: &buses,
+            voltage_levels: Groups::new(buses.len()),
+            substations: Groups::new(buses.len()),
+            loads: vec![Vec::new(); buses.len()],
+            generators: vec![Vec::new(); buses.len()],
+            switches: Vec::new(),
+            lines: Vec::new(),
+            transformers: Vec::new(),
+            three_windings_transformers: Vec::new(),
+            shunts: Vec::new(),
+            areas: Vec::new(),
+            remote_regulations: Vec::new(),
+            warnings,
+        };
+
+        // Voltage levels and substations are known before the equipments refer to them
+        for record in self.records(Section::SwitchingDevice) {
+            let from = network.find(record.bus(0, "I")?, record.line)?;
+            let to = network.find(record.bus(1, "J")?, record.line)?;
+            network.voltage_levels.join(from, to);
+            network.substations.join(from, to);
+        }
+        let transformers = self.transformers()?;
+        for records in &transformers {
+            let first = &records[0];
+            let mut numbers = vec![first.bus(0, "I")?, first.bus(1, "J")?];
+            if records.len() == 5 {
+                numbers.push(first.bus(2, "K")?);
+            }
+            let indexes = numbers
+                .into_iter()
+                .map(|number| network.find(number, first.line))
+                .collect::<Result<Vec<_>, _>>()?;
+            for index in &indexes[1..] {
+                network.substations.join(indexes[0], *index);
+            }
+        }
+
+        for record in self.records(Section::Load) {
+            network.load(record)?;
+        }
+        for record in self.records(Section::FixedShunt) {
+            network.fixed_shunt(record)?;
+        }
+        for record in self.records(Section::Generator) {
+            network.generator(record)?;
+        }
+        for record in self.records(Section::Branch) {
+            network.branch(record)?;
+        }
+        for record in self.records(Section::SwitchingDevice) {
+            network.switch(record)?;
+        }
+        for records in &transformers {
+            network.transformer(records)?;
+        }
+        for record in self.records(Section::SwitchedShunt) {
+            network.switched_shunt(record)?;
+        }
+        for record in self.records(Section::Area) {
+            network.area(record, false)?;
+        }
+        for record in self.records(Section::Zone) {
+            network.area(record, true)?;
+        }
+        network.regulating_terminals();
+
+        Ok(json!({
+            "version": IidmVersion::LATEST.to_string(),
+            "id": id,
+            "caseDate": DateTime::UNIX_EPOCH.fixed_offset(),
+            "forecastDistance": 0,
+            "sourceFormat": "PSS/E",
+            "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+            "substations": network.substations(),
+            "lines": network.lines,
+            "shuntCompensators": network.shunts,
+            "threeWindingsTransformers": network.three_windings_transformers,
+            "areas": network.areas,
+        }))
+    }
+
+    /// Records of the transformers, four lines for two windings and five for three
+    fn transformers(&self) -> Result<Vec<&[Record]>, PsseError> {
+        let mut transformers = Vec::new();
+        let mut records = self.records(Section::Transformer);
+        while let Some(first) = records.first() {
+            let length = match first.integer(2, "K", 0)? {
+                0 => 4,
+                _ => 5,
+            };
+            if records.len() < length {
+                return Err(first.error("incomplete transformer record".to_string()));
+            }
+            let (transformer, rest) = records.split_at(length);
+            transformers.push(transformer);
+            records = rest;
+        }
+        Ok(transformers)
+    }
+}
+
+struct Bus {
+    number: i64,
+    name: String,
+    base_kv: f64,
+    bus_type: i64,
+    area: i64,
+    zone: i64,
+    voltage_magnitude: f64,
+    voltage_angle: f64,
+}
+
+impl Bus {
+    fn parse(record: &Record) -> Result<Self, PsseError> {
+        Ok(Self {
+            number: record.bus(0, "I")?,
+            name: record.text(1).to_string(),
+            base_kv: record.number(2, "BASKV", 0.0)?,
+            bus_type: record.integer(3, "IDE", 1)?,
+            area: record.integer(4, "AREA", 1)?,
+            zone: record.integer(5, "ZONE", 1)?,
+            voltage_magnitude: record.number(7, "VM", 1.0)?,
+            voltage_angle: record.number(8, "VA", 0.0)?,
+        })
+    }
+
+    fn nominal_v(&self) -> f64 {
+        nominal_v(self.base_kv)
+    }
+
+    fn id(&self) -> String {
+        format!("BUS-{}", self.number)
+    }
+
+    // Equipments of isolated buses are disconnected
+    fn terminal(&self, in_service: bool) -> String {
+        if in_service && self.bus_type != 4 {
+            self.id()
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// Winding data of a transformer, the third line of its record and the following ones for
+/// three windings
+#[derive(Default)]
+struct Winding {
+    ratio: Option<f64>,
+    nominal_v: f64,
+    angle: f64,
+    rating: f64,
+    control: i64,
+    controlled_bus: i64,
+    maximum: f64,
+    minimum: f64,
+    maximum_target: f64,
+    minimum_target: f64,
+    positions: i64,
+}
+
+impl Winding {
+    fn parse(record: &Record, layout: &Layout) -> Result<Self, PsseError> {
+        let limits = layout.winding_limits;
+        Ok(Self {
+            ratio: record.optional(0, "WINDV")?,
+            nominal_v: record.number(1, "NOMV", 0.0)?,
+            angle: record.number(2, "ANG", 0.0)?,
+            rating: record.number(3, "RATE1", 0.0)?,
+            control: record.integer(layout.winding_control, "COD", 0)?,
+            controlled_bus: record.integer(layout.winding_control + 1, "CONT", 0)?.abs(),
+            maximum: record.number(limits, "RMA", 1.1)?,
+            minimum: record.number(limits + 1, "RMI", 0.9)?,
+            maximum_target: record.number(limits + 2, "VMA", 1.1)?,
+            minimum_target: record.number(limits + 3, "VMI", 0.9)?,
+            positions: record.integer(limits + 4, "NTP", 33)?,
+        })
+    }
+
+    /// Voltage of the winding in kV, its ratio being given according to the CW code
+    fn rated_u(&self, code: i64, bus: &Bus) -> f64 {
+        let nominal_v = if self.nominal_v > 0.0 {
+            self.nominal_v
+        } else {
+            bus.nominal_v()
+        };
+        match code {
+            2 => self.ratio.unwrap_or(nominal_v),
+            3 => self.ratio.unwrap_or(1.0) * nominal_v,
+            _ => self.ratio.unwrap_or(1.0) * bus.nominal_v(),
+        }
+    }
+}
+
+struct Builder<'a> {
+    layout: &'static Layout,
+    base_mva: f64,
+    buses: &'a [Bus],
+    indexes: HashMap<i64, usize>,
+    voltage_levels: Groups,
+    substations: Groups,
+    // Loads and generators of each bus
+    loads: Vec<Vec<Value>>,
+    generators: Vec<Vec<Value>>,
+    // Switches and transformers with the index of a bus of their voltage level or substation
+    switches: Vec<(usize, Value)>,
+    transformers: Vec<(usize, Value)>,
+    lines: Vec<Value>,
+    three_windings_transformers: Vec<Value>,
+    shunts: Vec<Value>,
+    areas: Vec<Value>,
+    // Generators regulating remote buses, by bus index and position in the generators of the
+    // bus, with the index of the regulated bus and the voltage set point in per-unit
+    remote_regulations: Vec<(usize, usize, usize, f64)>,
+    warnings: &'a mut Vec<String>,
+}
+
+impl Builder<'_> {
+    fn find(&self, number: i64, line: usize) -> Result<usize, PsseError> {
+        self.indexes
+            .get(&number)
+            .copied()
+            .ok_or(PsseError::UnknownBus { line, bus: number })
+    }
+
+    // Voltage levels are named after the first bus they hold
+    fn voltage_level_id(&mut self, index: usize) -> String {
+        let root = self.voltage_levels.root(index);
+        format!("VL-{}", self.buses[root].number)
+    }
+
+    fn load(&mut self, record: &Record) -> Result<(), PsseError> {
+        let index = self.find(record.bus(0, "I")?, record.line)?;
+        let bus = &self.buses[index];
+        // Constant current and admittance loads are taken at 1 p.u. voltage, admittance
+        // reactive powers being negative for inductive loads
+        let p0 = record.number(5, "PL", 0.0)?
+            + record.number(7, "IP", 0.0)?
+            + record.number(9, "YP", 0.0)?;
+        let q0 = record.number(6, "QL", 0.0)? + record.number(8, "IQ", 0.0)?
+            - record.number(10, "YQ", 0.0)?;
+        self.loads[index].push(json!({
+            "id": format!("LOAD-{}-{}", bus.number, record.identifier(1)),
+            "loadType": "UNDEFINED",
+            "p0": p0,
+            "q0": q0,
+            "bus": bus.terminal(record.integer(2, "STATUS", 1)? > 0),
+            "connectableBus": bus.id(),
+        }));
+        Ok(())
+    }
+
+    // Shunt admittances are given in MW and MVAr consumed at 1 p.u. voltage
+    fn fixed_shunt(&mut self, record: &Record) -> Result<(), PsseError> {
+        let index = self.find(record.bus(0, "I")?, record.line)?;
+        let voltage_level_id = self.voltage_level_id(index);
+        let bus = &self.buses[index];
+        let nominal_v = bus.nominal_v();
+        self.shunts.push(json!({
+            "id": format!("SHUNT-{}-{}", bus.number, record.identifier(1)),
+            "sectionCount": 1,
+            "shuntLinearModel": {
+                "bPerSection": record.number(4, "BL", 0.0)? / nominal_v.powi(2),
+                "gPerSection": record.number(3, "GL", 0.0)? / nominal_v.powi(2),
+                "maximumSectionCount": 1,
+            },
+            "bus": bus.terminal(record.integer(2, "STATUS", 1)? > 0),
+            "connectableBus": bus.id(),
+            "voltageLevelId": voltage_level_id,
+        }));
+        Ok(())
+    }
+
+    fn generator(&mut self, record: &Record) -> Result<(), PsseError> {
+        let index = self.find(record.bus(0, "I")?, record.line)?;
+        let bus = &self.buses[index];
+        let id = format!("GEN-{}-{}", bus.number, record.identifier(1));
+        let in_service = record.integer(self.layout.generator_status, "STAT", 1)? > 0;
+        let maximum_p = self.layout.generator_maximum_p;
+
+        // Remote regulations are resolved once the equipments of the regulated bus are known
+        let regulated_bus = record.integer(7, "IREG", 0)?.abs();
+        let target_v = record.number(6, "VS", 1.0)?;
+        if regulated_bus != 0 && regulated_bus != bus.number {
+            let regulated = self.find(regulated_bus, record.line)?;
+            let position = self.generators[index].len();
+            self.remote_regulations
+                .push((index, position, regulated, target_v));
+        }
+        self.generators[index].push(json!({
+            "id": id,
+            "energySource": "OTHER",
+            "minP": record.number(maximum_p + 1, "PB", -9999.0)?,
+            "maxP": record.number(maximum_p, "PT", 9999.0)?,
+            "voltageRegulatorOn": matches!(bus.bus_type, 2 | 3),
+            "targetP": record.number(2, "PG", 0.0)?,
+            "targetV": target_v * bus.nominal_v(),
+            "targetQ": record.number(3, "QG", 0.0)?,
+            "bus": bus.terminal(in_service),
+            "connectableBus": bus.id(),
+            "minMaxReactiveLimits": {
+                "minQ": record.number(5, "QB", -9999.0)?,
+                "maxQ": record.number(4, "QT", 9999.0)?,
+            },
+        }));
+        Ok(())
+    }
+
+    fn branch(&mut self, record: &Record) -> Result<(), PsseError> {
+        let from_index = self.find(record.bus(0, "I")?, record.line)?;
+        let to_index = self.find(record.bus(1, "J")?, record.line)?;
+        let voltage_level_id1 = self.voltage_level_id(from_index);
+        let voltage_level_id2 = self.voltage_level_id(to_index);
+        let (from, to) = (&self.buses[from_index], &self.buses[to_index]);
+        let in_service = record.integer(self.layout.branch_status, "ST", 1)? > 0;
+        // Impedances are in per-unit of the base voltage of the second side
+        let base_impedance = to.nominal_v().powi(2) / self.base_mva;
+        let charging = record.number(5, "B", 0.0)? / 2.0;
+        let admittances = self.layout.branch_admittances;
+
+        let mut line = json!({
+            "id": format!("LINE-{}-{}-{}", from.number, to.number, record.identifier(2)),
+            "r": record.number(3, "R", 0.0)? * base_impedance,
+            "x": record.number(4, "X", 0.0)? * base_impedance,
+            "g1": record.number(admittances, "GI", 0.0)? / base_impedance,
+            "b1": (charging + record.number(admittances + 1, "BI", 0.0)?) / base_impedance,
+            "g2": record.number(admittances + 2, "GJ", 0.0)? / base_impedance,
+            "b2": (charging + record.number(admittances + 3, "BJ", 0.0)?) / base_impedance,
+            "voltageLevelId1": voltage_level_id1,
+            "bus1": from.terminal(in_service),
+            "connectableBus1": from.id(),
+            "voltageLevelId2": voltage_level_id2,
+            "bus2": to.terminal(in_service),
+            "connectableBus2": to.id(),
+        });
+        let rating = record.number(self.layout.branch_rating, "RATE1", 0.0)?;
+        current_limits(&mut line, "1", rating, from.nominal_v());
+        current_limits(&mut line, "2", rating, to.nominal_v());
+        self.lines.push(line);
+        Ok(())
+    }
+
+    fn switch(&mut self, record: &Record) -> Result<(), PsseError> {
+        let from_index = self.find(record.bus(0, "I")?, record.line)?;
+        let to_index = self.find(record.bus(1, "J")?, record.line)?;
+        let voltage_level_id = self.voltage_level_id(from_index);
+        let (from, to) = (&self.buses[from_index], &self.buses[to_index]);
+        self.switches.push((
+            from_index,
+            json!({
+                "id": format!("SW-{}-{}-{}", from.number, to.number, record.identifier(2)),
+                "kind": "BREAKER",
+                "open": record.integer(16, "STAT", 1)? == 0,
+                "retained": false,
+                "bus1": from.id(),
+                "bus2": to.id(),
+                "voltageLevelId": voltage_level_id,
+            }),
+        ));
+        Ok(())
+    }
+
+    fn transformer(&mut self, records: &[Record]) -> Result<(), PsseError> {
+        let first = &records[0];
+        let winding_code = first.integer(4, "CW", 1)?;
+        let impedance_code = first.integer(5, "CZ", 1)?;
+        let in_service = first.integer(11, "STAT", 1)?;
+        let circuit = first.identifier(3);
+        let impedances = &records[1];
+        let is_three_windings = records.len() == 5;
+
+        let mut indexes = vec![
+            self.find(first.bus(0, "I")?, first.line)?,
+            self.find(first.bus(1, "J")?, first.line)?,
+        ];
+        let windings = if is_three_windings {
+            indexes.push(self.find(first.bus(2, "K")?, first.line)?);
+            [&records[2], &records[3], &records[4]]
+                .into_iter()
+                .map(|record| Winding::parse(record, self.layout))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            let mut windings = vec![Winding::parse(&records[2], self.layout)?];
+            // The last line only holds the ratio and the nominal voltage of the second winding
+            let last = &records[3];
+            windings.push(Winding {
+                ratio: last.optional(0, "WINDV2")?,
+                nominal_v: last.number(1, "NOMV2", 0.0)?,
+                ..Default::default()
+            });
+            windings
+        };
+        let rated_u = windings
+            .iter()
+            .zip(&indexes)
+            .map(|(winding, index)| winding.rated_u(winding_code, &self.buses[*index]))
+            .collect::<Vec<_>>();
+        let numbers = indexes
+            .iter()
+            .map(|index| self.buses[*index].number)
+            .collect::<Vec<_>>();
+        let id = format!(
+            "TWT-{}-{}",
+            numbers
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join("-"),
+            circuit
+        );
+
+        // Impedances in per-unit of the case base power
+        let mut pairs = Vec::new();
+        for pair in 0..if is_three_windings { 3 } else { 1 } {
+            let base_mva = impedances.number(3 * pair + 2, "SBASE", self.base_mva)?;
+            pairs.push(self.impedance(
+                impedance_code,
+                impedances.number(3 * pair, "R", 0.0)?,
+                impedances.number(3 * pair + 1, "X", 0.0)?,
+                if base_mva > 0.0 {
+                    base_mva
+                } else {
+                    self.base_mva
+                },
+            ));
+        }
+        let (g, b) = self.magnetizing(
+            first.integer(6, "CM", 1)?,
+            first.number(7, "MAG1", 0.0)?,
+            first.number(8, "MAG2", 0.0)?,
+            impedances.number(2, "SBASE1-2", self.base_mva)?,
+        );
+
+        let sides = if is_three_windings {
+            vec!["ONE", "TWO", "THREE"]
+        } else {
+            vec!["ONE", "TWO"]
+        };
+        let mut tap_changers = Vec::new();
+        for (winding, side) in windings.iter().zip(&sides) {
+            tap_changers.push(self.tap_changers(&id, winding, side, &numbers, &sides));
+        }
+        let mut tap_changers = tap_changers.into_iter();
+
+        if is_three_windings {
+            // Pair impedances are converted to a star, at the voltage of the first winding
+            let base_impedance = rated_u[0].powi(2) / self.base_mva;
+            let [(r12, x12), (r23, x23), (r31, x31)] = [pairs[0], pairs[1], pairs[2]];
+            let legs = [
+                ((r12 + r31 - r23) / 2.0, (x12 + x31 - x23) / 2.0),
+                ((r12 + r23 - r31) / 2.0, (x12 + x23 - x31) / 2.0),
+                ((r23 + r31 - r12) / 2.0, (x23 + x31 - x12) / 2.0),
+            ];
+            // Status 2, 3 and 4 take the second, third and first winding out of service
+            let connected = [
+                !matches!(in_service, 0 | 4),
+                !matches!(in_service, 0 | 2),
+                !matches!(in_service, 0 | 3),
+            ];
+            let mut transformer = json!({
+                "id": id,
+                "ratedU0": rated_u[0],
+            });
+            for leg in 0..3 {
+                let side = (leg + 1).to_string();
+                let index = indexes[leg];
+                let voltage_level_id = self.voltage_level_id(index);
+                let bus = &self.buses[index];
+                let (magnetizing_g, magnetizing_b) = if leg == 0 { (g, b) } else { (0.0, 0.0) };
+                transformer[format!("r{}", side)] = json!(legs[leg].0 * base_impedance);
+                transformer[format!("x{}", side)] = json!(legs[leg].1 * base_impedance);
+                transformer[format!("g{}", side)] = json!(magnetizing_g / base_impedance);
+                transformer[format!("b{}", side)] = json!(magnetizing_b / base_impedance);
+                transformer[format!("ratedU{}", side)] = json!(rated_u[leg]);
+                transformer[format!("voltageLevelId{}", side)] = json!(voltage_level_id);
+                transformer[format!("bus{}", side)] = json!(bus.terminal(connected[leg]));
+                transformer[format!("connectableBus{}", side)] = json!(bus.id());
+                let (ratio_tap_changer, phase_tap_changer) =
+                    tap_changers.next().unwrap_or_default();
+                if let Some(tap_changer) = ratio_tap_changer {
+                    transformer[format!("ratioTapChanger{}", side)] = tap_changer;
+                }
+                if let Some(tap_changer) = phase_tap_changer {
+                    transformer[format!("phaseTapChanger{}", side)] = tap_changer;
+                }
+                current_limits(
+                    &mut transformer,
+                    &side,
+                    windings[leg].rating,
+                    bus.nominal_v(),
+                );
+            }
+            self.three_windings_transformers.push(transformer);
+        } else {
+            // Impedances are in per-unit of the voltage of the second winding
+            let base_impedance = rated_u[1].powi(2) / self.base_mva;
+            let voltage_level_id1 = self.voltage_level_id(indexes[0]);
+            let voltage_level_id2 = self.voltage_level_id(indexes[1]);
+            let (from, to) = (&self.buses[indexes[0]], &self.buses[indexes[1]]);
+            let mut transformer = json!({
+                "id": id,
+                "r": pairs[0].0 * base_impedance,
+                "x": pairs[0].1 * base_impedance,
+                "g": g / base_impedance,
+                "b": b / base_impedance,
+                "ratedU1": rated_u[0],
+                "ratedU2": rated_u[1],
+                "voltageLevelId1": voltage_level_id1,
+                "bus1": from.terminal(in_service > 0),
+                "connectableBus1": from.id(),
+                "voltageLevelId2": voltage_level_id2,
+                "bus2": to.terminal(in_service > 0),
+                "connectableBus2": to.id(),
+            });
+            let (ratio_tap_changer, phase_tap_changer) = tap_changers.next().unwrap_or_default();
+            if let Some(tap_changer) = ratio_tap_changer {
+                transformer["ratioTapChanger"] = tap_changer;
+            }
+            if let Some(tap_changer) = phase_tap_changer {
+                transformer["phaseTapChanger"] = tap_changer;
+            }
+            current_limits(&mut transformer, "1", windings[0].rating, from.nominal_v());
+            current_limits(&mut transformer, "2", windings[0].rating, to.nominal_v());
+            self.transformers.push((indexes[0], transformer));
+        }
+        Ok(())
+    }
+
+    /// Impedance in per-unit of the case base power, from the CZ code of a transformer:
+    /// per-unit of the case base, per-unit of the winding base, or load losses in W and
+    /// impedance magnitude in per-unit of the winding base
+    fn impedance(&self, code: i64, r: f64, x: f64, base_mva: f64) -> (f64, f64) {
+        let scale = self.base_mva / base_mva;
+        match code {
+            2 => (r * scale, x * scale),
+            3 => {
+                let r = r / 1e6 / base_mva;
+                let x = (x.powi(2) - r.powi(2)).max(0.0).sqrt();
+                (r * scale, x * scale)
+            }
+            _ => (r, x),
+        }
+    }
+
+    /// Magnetizing admittance in per-unit of the case base power, from the CM code of a
+    /// transformer: per-unit of the case base, or no-load losses in W and exciting current
+    /// in per-unit of the winding base
+    fn magnetizing(&self, code: i64, mag1: f64, mag2: f64, base_mva: f64) -> (f64, f64) {
+        match code {
+            2 => {
+                let g = mag1 / 1e6 / self.base_mva;
+                let y = mag2 * base_mva / self.base_mva;
+                (g, -(y.powi(2) - g.powi(2)).max(0.0).sqrt())
+            }
+            _ => (mag1, mag2),
+        }
+    }
+
+    /// Ratio and phase tap changers of a winding, the positions of a controlled winding
+    /// being spread evenly between its limits
+    fn tap_changers(
+        &mut self,
+        id: &str,
+        winding: &Winding,
+        side: &str,
+        numbers: &[i64],
+        sides: &[&str],
+    ) -> (Option<Value>, Option<Value>) {
+        let (mut ratio_tap_changer, mut phase_tap_changer) = (None, None);
+        let positions = winding.positions.max(2) as usize;
+        let steps = (0..positions)
+            .map(|position| {
+                winding.minimum
+                    + (winding.maximum - winding.minimum) * position as f64 / (positions - 1) as f64
+            })
+            .collect::<Vec<_>>();
+        let closest = |value: f64| {
+            (0..steps.len())
+                .min_by(|a, b| {
+                    (steps[*a] - value)
+                        .abs()
+                        .total_cmp(&(steps[*b] - value).abs())
+                })
+                .unwrap_or_default()
+        };
+        // Controls of remote buses are kept on the winding
+        let regulated_side = match numbers
+            .iter()
+            .position(|number| *number == winding.controlled_bus)
+        {
+            Some(position) => sides[position],
+            None => side,
+        };
+        let target = (winding.maximum_target + winding.minimum_target) / 2.0;
+        let deadband = winding.maximum_target - winding.minimum_target;
+
+        match winding.control.abs() {
+            0 => {}
+            1 | 2 => {
+                let ratio = winding.ratio.unwrap_or(1.0);
+                let (mode, value, deadband) = if winding.control.abs() == 1 {
+                    let nominal_v = self
+                        .indexes
+                        .get(&winding.controlled_bus)
+                        .map(|index| self.buses[*index].nominal_v())
+                        .unwrap_or(1.0);
+                    ("VOLTAGE", target * nominal_v, deadband * nominal_v)
+                } else {
+                    ("REACTIVE_POWER", target, deadband)
+                };
+                ratio_tap_changer = Some(json!({
+                    "regulating": winding.control > 0 && winding.controlled_bus != 0,
+                    "lowTapPosition": 0,
+                    "tapPosition": closest(ratio),
+                    "targetDeadband": deadband,
+                    "loadTapChangingCapabilities": true,
+                    "regulationMode": mode,
+                    "regulationValue": value,
+                    "terminalRef": {"id": id, "side": regulated_side},
+                    "steps": steps
+                        .iter()
+                        .map(|step| json!({"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": ratio / step}))
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            3 => {
+                // PSS/E angles are the shift of the winding voltage, IIDM ones the angle
+                // added to it
+                phase_tap_changer = Some(json!({
+                    "regulating": winding.control > 0,
+                    "lowTapPosition": 0,
+                    "tapPosition": closest(winding.angle),
+                    "regulationMode": "ACTIVE_POWER_CONTROL",
+                    "regulationValue": target,
+                    "targetDeadband": deadband,
+                    "terminalRef": {"id": id, "side": side},
+                    "steps": steps
+                        .iter()
+                        .map(|step| json!({"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 1.0, "alpha": -step}))
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            control => self.warnings.push(format!(
+                "Control mode {} of transformer {} is not supported",
+                control, id
+            )),
+        }
+        if phase_tap_changer.is_none() && winding.angle != 0.0 {
+            phase_tap_changer = Some(json!({
+                "regulating": false,
+                "lowTapPosition": 0,
+                "tapPosition": 0,
+                "regulationMode": "FIXED_TAP",
+                "regulationValue": 0.0,
+                "targetDeadband": 0.0,
+                "terminalRef": {"id": id, "side": side},
+                "steps": [{"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 1.0, "alpha": -winding.angle}],
+            }));
+        }
+        (ratio_tap_changer, phase_tap_changer)
+    }
+
+    /// Generators regulating a remote bus regulate the terminal of its first load, generator,
+    /// shunt, line or transformer, in per-unit of its voltage. Buses without any of them are
+    /// regulated locally
+    fn regulating_terminals(&mut self) {
+        for (index, position, regulated, target_v) in std::mem::take(&mut self.remote_regulations) {
+            let bus = &self.buses[regulated];
+            let bus_id = bus.id();
+            let injection = self.loads[regulated]
+                .iter()
+                .chain(&self.generators[regulated])
+                .chain(
+                    self.shunts
+                        .iter()
+                        .filter(|shunt| shunt["connectableBus"] == bus_id.as_str()),
+                )
+                .map(|injection| json!({"id": injection["id"]}));
+            let branches = self
+                .lines
+                .iter()
+                .chain(self.transformers.iter().map(|(_, transformer)| transformer))
+                .flat_map(|branch| [(branch, "1", "ONE"), (branch, "2", "TWO")])
+                .filter(|(branch, number, _)| {
+                    branch[format!("connectableBus{}", number)] == bus_id.as_str()
+                })
+                .map(|(branch, _, side)| json!({"id": branch["id"], "side": side}));
+            let terminal = injection.chain(branches).next();
+
+            let generator = &mut self.generators[index][position];
+            match terminal {
+                Some(terminal) => {
+                    generator["regulatingTerminal"] = terminal;
+                    generator["targetV"] = json!(target_v * bus.nominal_v());
+                }
+                None => self.warnings.push(format!(
+                    "Bus {} regulated by generator {} has no equipment, it is regulated locally",
+                    bus.number,
+                    generator["id"].as_str().unwrap_or_default()
+                )),
+            }
+        }
+    }
+
+    /// Switched shunts become non-linear shunts, whose sections are the steps of their
+    /// blocks in MVAr at 1 p.u. voltage
+    fn switched_shunt(&mut self, record: &Record) -> Result<(), PsseError> {
+        let index = self.find(record.bus(0, "I")?, record.line)?;
+        let voltage_level_id = self.voltage_level_id(index);
+        let bus = &self.buses[index];
+        let layout = self.layout;
+        let identifier = layout
+            .switched_shunt_id
+            .map(|position| record.identifier(position))
+            .unwrap_or("1");
+        let initial = record.number(layout.switched_shunt_initial, "BINIT", 0.0)?;
+
+        let mut susceptances = vec![0.0];
+        let mut position = layout.switched_shunt_blocks;
+        let size = layout.switched_shunt_block_size;
+        while position + size <= record.fields.len() {
+            let steps = record.integer(position + size - 2, "N", 0)?;
+            let susceptance = record.number(position + size - 1, "B", 0.0)?;
+            for _ in 0..steps.max(0) {
+                let total = susceptances.last().copied().unwrap_or_default() + susceptance;
+                susceptances.push(total);
+            }
+            position += size;
+        }
+        let section_count = (0..susceptances.len())
+            .min_by(|a, b| {
+                (susceptances[*a] - initial)
+                    .abs()
+                    .total_cmp(&(susceptances[*b] - initial).abs())
+            })
+            .unwrap_or_default();
+        let nominal_v = bus.nominal_v();
+        self.shunts.push(json!({
+            "id": format!("SWSHUNT-{}-{}", bus.number, identifier),
+            "sectionCount": section_count,
+            "shuntNonLinearModel": {
+                "sections": susceptances[1..]
+                    .iter()
+                    .map(|b| json!({"b": b / nominal_v.powi(2), "g": 0.0}))
+                    .collect::<Vec<_>>(),
+            },
+            "bus": bus.terminal(record.integer(layout.switched_shunt_status, "STAT", 1)? > 0),
+            "connectableBus": bus.id(),
+            "voltageLevelId": voltage_level_id,
+        }));
+        Ok(())
+    }
+
+    /// Areas are control areas with their desired interchange, zones being areas too
+    fn area(&mut self, record: &Record, is_zone: bool) -> Result<(), PsseError> {
+        let number = record.integer(0, "I", 0)?;
+        let mut voltage_level_ids: Vec<String> = Vec::new();
+        for index in 0..self.buses.len() {
+            let bus = &self.buses[index];
+            let member = if is_zone { bus.zone } else { bus.area };
+            if member == number {
+                let voltage_level_id = self.voltage_level_id(index);
+                if !voltage_level_ids.contains(&voltage_level_id) {
+                    voltage_level_ids.push(voltage_level_id);
+                }
+            }
+        }
+        let mut area = if is_zone {
+            json!({"id": format!("ZONE-{}", number), "areaType": "Zone"})
+        } else {
+            json!({
+                "id": format!("AREA-{}", number),
+                "areaType": "ControlArea",
+                "interchangeTarget": record.number(2, "PDES", 0.0)?,
+            })
+        };
+        let name = record.text(if is_zone { 1 } else { 4 });
+        if !name.is_empty() {
+            area["name"] = json!(name);
+        }
+        area["voltageLevelIds"] = json!(voltage_level_ids);
+        area["areaBoundaries"] = json!([]);
+        self.areas.push(area);
+        Ok(())
+    }
+
+    // Substations are named after the first bus they hold
+    fn substations(&mut self) -> Vec<Value> {
+        let mut voltage_levels: Vec<(usize, Value)> = Vec::new();
+        for index in 0..self.buses.len() {
+            let root = self.voltage_levels.root(index);
+            let bus = &self.buses[index];
+            let position = match voltage_levels.iter().position(|(other, _)| *other == root) {
+                Some(position) => position,
+                None => {
+                    voltage_levels.push((
+                        root,
+                        json!({
+                            "id": format!("VL-{}", bus.number),
+                            "nominalV": bus.nominal_v(),
+                            "topologyKind": "BUS_BREAKER",
+                            "generators": [],
+                            "loads": [],
+                            "busBreakerTopology": {"buses": [], "switches": []},
+                        }),
+                    ));
+                    voltage_levels.len() - 1
+                }
+            };
+            let voltage_level = &mut voltage_levels[position].1;
+            let mut object = json!({
+                "id": bus.id(),
+                "v": bus.voltage_magnitude * bus.nominal_v(),
+                "angle": bus.voltage_angle,
+            });
+            if !bus.name.is_empty() {
+                object["name"] = json!(bus.name);
+            }
+            push(&mut voltage_level["busBreakerTopology"], "buses", object);
+            for load in std::mem::take(&mut self.loads[index]) {
+                push(voltage_level, "loads", load);
+            }
+            for generator in std::mem::take(&mut self.generators[index]) {
+                push(voltage_level, "generators", generator);
+            }
+        }
+        for (index, switch) in std::mem::take(&mut self.switches) {
+            let root = self.voltage_levels.root(index);
+            if let Some((_, voltage_level)) =
+                voltage_levels.iter_mut().find(|(other, _)| *other == root)
+            {
+                push(&mut voltage_level["busBreakerTopology"], "switches", switch);
+            }
+        }
+
+        let mut substations: Vec<(usize, Value)> = Vec::new();
+        for (index, voltage_level) in voltage_levels {
+            let root = self.substations.root(index);
+            let position = match substations.iter().position(|(other, _)| *other == root) {
+                Some(position) => position,
+                None => {
+                    substations.push((
+                        root,
+                        json!({
+                            "id": format!("SUB-{}", self.buses[root].number),
+                            "country": "",
+                            "tso": "",
+                            "geographicalTags": [],
+                            "voltageLevels": [],
+                            "twoWindingsTransformers": [],
+                        }),
+                    ));
+                    substations.len() - 1
+                }
+            };
+            push(&mut substations[position].1, "voltageLevels", voltage_level);
+        }
+        for (index, transformer) in std::mem::take(&mut self.transformers) {
+            let root = self.substations.root(index);
+            if let Some((_, substation)) = substations.iter_mut().find(|(other, _)| *other == root)
+            {
+                push(substation, "twoWindingsTransformers", transformer);
+            }
+        }
+        substations
+            .into_iter()
+            .map(|(_, substation)| substation)
+            .collect()
+    }
+}
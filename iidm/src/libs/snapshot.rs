@@ -0,0 +1,163 @@
+//! Binary snapshots of networks, for saving and loading them faster than IIDM documents.
+//!
+//! A snapshot is a header followed by the network encoded in MessagePack. The header holds
+//! the snapshot format version, the IIDM version of the network model and a hash of its JSON
+//! schema, all little-endian:
+//!
+//! | bytes | content                         |
+//! |-------|---------------------------------|
+//! | 0-7   | `IIDMSNAP`                      |
+//! | 8-9   | snapshot format version         |
+//! | 10-13 | IIDM major and minor versions   |
+//! | 14-21 | network schema hash             |
+//!
+//! Snapshots are not migrated: those of another format version, of another IIDM model version
+//! or of another network schema are rejected, the network has to be read again from its IIDM
+//! document. The schema hash catches the changes of the model within an IIDM version, to which
+//! the MessagePack payload is not resilient.
+
+use std::io::{self, Read, Write};
+use std::sync::OnceLock;
+
+use super::version::IidmVersion;
+use crate::Network;
+
+const MAGIC: &[u8; 8] = b"IIDMSNAP";
+
+/// Version of the snapshot encoding, increased on every incompatible change of the header or
+/// of the payload encoding
+pub const SNAPSHOT_VERSION: u16 = 2;
+
+/// FNV-1a hash of the JSON schema of the network model, stable across builds and platforms
+pub fn schema_hash() -> u64 {
+    static HASH: OnceLock<u64> = OnceLock::new();
+    *HASH.get_or_init(|| {
+        let schema = serde_json::to_vec(&schemars::schema_for!(Network)).unwrap_or_default();
+        schema.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("Not a network snapshot")]
+    NotSnapshot,
+    #[error("Unsupported snapshot format version {found}, expected {expected}")]
+    FormatVersion { found: u16, expected: u16 },
+    #[error("Snapshot of IIDM {found} is incompatible with the IIDM {expected} network model")]
+    IidmVersion {
+        found: IidmVersion,
+        expected: IidmVersion,
+    },
+    #[error("Snapshot of network schema {found:016x} is incompatible with the network schema {expected:016x}")]
+    Schema { found: u64, expected: u64 },
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Encoding error: {0}")]
+    Encode(#[from] rmp_serde::encode::Error),
+    #[error("Decoding error: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub format_version: u16,
+    pub iidm_version: IidmVersion,
+    pub schema_hash: u64,
+}
+
+impl SnapshotHeader {
+    /// Header of the snapshots written by this version of the library
+    pub fn current() -> Self {
+        Self {
+            format_version: SNAPSHOT_VERSION,
+            iidm_version: IidmVersion::LATEST,
+            schema_hash: schema_hash(),
+        }
+    }
+
+    /// Reads a header, without checking its versions nor its schema hash
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, SnapshotError> {
+        let mut magic = [0; MAGIC.len()];
+        match reader.read_exact(&mut magic) {
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(SnapshotError::NotSnapshot)
+            }
+            result => result?,
+        }
+        if &magic != MAGIC {
+            return Err(SnapshotError::NotSnapshot);
+        }
+        let mut versions = [0; 6];
+        reader.read_exact(&mut versions)?;
+        let version = |index: usize| u16::from_le_bytes([versions[index], versions[index + 1]]);
+        let mut schema_hash = [0; 8];
+        reader.read_exact(&mut schema_hash)?;
+        Ok(Self {
+            format_version: version(0),
+            iidm_version: IidmVersion::new(version(2).into(), version(4).into()),
+            schema_hash: u64::from_le_bytes(schema_hash),
+        })
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&self.format_version.to_le_bytes())?;
+        for version in [self.iidm_version.major, self.iidm_version.minor] {
+            // IIDM versions are small numbers
+            writer.write_all(&(version as u16).to_le_bytes())?;
+        }
+        writer.write_all(&self.schema_hash.to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Checks that snapshots with this header can be read by this version of the library
+    pub fn check_compatibility(&self) -> Result<(), SnapshotError> {
+        if self.format_version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::FormatVersion {
+                found: self.format_version,
+                expected: SNAPSHOT_VERSION,
+            });
+        }
+        if self.iidm_version != IidmVersion::LATEST {
+            return Err(SnapshotError::IidmVersion {
+                found: self.iidm_version,
+                expected: IidmVersion::LATEST,
+            });
+        }
+        if self.schema_hash != schema_hash() {
+            return Err(SnapshotError::Schema {
+                found: self.schema_hash,
+                expected: schema_hash(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Writes a network snapshot
+pub fn write_snapshot<W: Write>(network: &Network, mut writer: W) -> Result<(), SnapshotError> {
+    SnapshotHeader::current().write(&mut writer)?;
+    // Structures are encoded as maps, the attributes skipped when empty being absent
+    rmp_serde::encode::write_named(&mut writer, network)?;
+    Ok(())
+}
+
+/// Reads a network snapshot, rejecting snapshots of incompatible versions before decoding them
+pub fn read_snapshot<R: Read>(mut reader: R) -> Result<Network, SnapshotError> {
+    SnapshotHeader::read(&mut reader)?.check_compatibility()?;
+    Ok(rmp_serde::from_read(reader)?)
+}
+
+/// Encodes a network into an in-memory snapshot
+pub fn to_snapshot(network: &Network) -> Result<Vec<u8>, SnapshotError> {
+    let mut snapshot = Vec::new();
+    write_snapshot(network, &mut snapshot)?;
+    Ok(snapshot)
+}
+
+/// Decodes a network from an in-memory snapshot, rejecting it if it is incompatible
+pub fn from_snapshot(snapshot: &[u8]) -> Result<Network, SnapshotError> {
+    read_snapshot(snapshot)
+}
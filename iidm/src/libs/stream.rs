@@ -0,0 +1,310 @@
+//! Streaming import of IIDM JSON documents, for documents too large to be held in memory.
+//!
+//! The substations, branches and other collections of the network are read one element at a
+//! time, each element being deserialized as soon as it is complete, so that neither the
+//! document nor its JSON tree is held in memory. Documents of older IIDM versions are upgraded
+//! element by element, which requires their `version` to precede the collections, as IIDM
+//! exporters write it.
+//!
+//! Networks are registered once the whole document is read, so that a document failing to be
+//! read registers nothing, and so that the world is not borrowed while a slow stream is read.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+use bevy_ecs::{schedule::Schedule, world::World};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+use serde_json::{Map, Value};
+
+use super::compression::read_decompressed;
+use super::error::IidmError;
+use super::version::upgrade;
+use super::xml::from_xml;
+use crate::{
+    Area, DanglingLine, ExtensionHolder, HvdcLine, Identifiable, Line, Network, ShuntCompensator,
+    StaticVarCompensator, Substation, Switch, ThreeWindingsTransformer, TieLine,
+};
+
+/// Network collections streamed element by element, by their IIDM JSON name
+const COLLECTIONS: [&str; 11] = [
+    "substations",
+    "lines",
+    "threeWindingsTransformers",
+    "switches",
+    "shuntCompensators",
+    "staticVarCompensators",
+    "danglingLines",
+    "tieLines",
+    "hvdcLines",
+    "areas",
+    "extensions",
+];
+
+/// Network read from a stream and registered
+#[derive(Debug)]
+pub struct StreamImport {
+    pub network: Network,
+    /// Number of registered elements by collection, e.g. `substations`
+    pub registered: BTreeMap<String, usize>,
+}
+
+/// Reads a network from an IIDM JSON document, possibly compressed, its elements being
+/// deserialized as they are read.
+pub fn read_json_stream<R: Read>(reader: R) -> Result<Network, IidmError> {
+    read_decompressed(reader, read_json_document)?
+}
+
+/// Reads a network from an IIDM JSON or XML document, possibly compressed. JSON documents are
+/// streamed as by [`read_json_stream`], XML documents are read whole.
+pub fn read_iidm_stream<R: Read>(reader: R) -> Result<Network, IidmError> {
+    read_decompressed(reader, |document| {
+        let mut document = BufReader::new(document);
+        if first_byte(&mut document)? != Some(b'<') {
+            return read_json_document(&mut document);
+        }
+        let mut xml = String::new();
+        document.read_to_string(&mut xml)?;
+        from_xml(&xml)
+    })?
+}
+
+/// Registers a network, counting its elements by collection
+pub fn register_network(
+    network: Network,
+    world: &mut World,
+    schedule: &mut Schedule,
+) -> StreamImport {
+    network.register(world, schedule);
+    let registered = [
+        ("substations", network.substations.len()),
+        ("lines", network.lines.len()),
+        (
+            "threeWindingsTransformers",
+            network.three_windings_transformers.len(),
+        ),
+        ("switches", network.switches.len()),
+        ("shuntCompensators", network.shunt_compensators.len()),
+        (
+            "staticVarCompensators",
+            network.static_var_compensators.len(),
+        ),
+        ("danglingLines", network.dangling_lines.len()),
+        ("tieLines", network.tie_lines.len()),
+        ("hvdcLines", network.hvdc_lines.len()),
+        ("areas", network.areas.len()),
+        ("extensions", network.extensions.len()),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(collection, count)| (collection.to_string(), count))
+    .collect();
+    StreamImport {
+        network,
+        registered,
+    }
+}
+
+/// Reads a network from an IIDM JSON document, possibly compressed, and registers it once
+/// the whole document is read, nothing being registered on error.
+pub fn register_json<R: Read>(
+    reader: R,
+    world: &mut World,
+    schedule: &mut Schedule,
+) -> Result<StreamImport, IidmError> {
+    let network = read_json_stream(reader)?;
+    Ok(register_network(network, world, schedule))
+}
+
+/// Reads a network from an IIDM JSON or XML document, possibly compressed, as by
+/// [`read_iidm_stream`], and registers it once the whole document is read.
+pub fn register_iidm<R: Read>(
+    reader: R,
+    world: &mut World,
+    schedule: &mut Schedule,
+) -> Result<StreamImport, IidmError> {
+    let network = read_iidm_stream(reader)?;
+    Ok(register_network(network, world, schedule))
+}
+
+fn read_json_document(document: &mut dyn Read) -> Result<Network, IidmError> {
+    let mut collector = Collector {
+        version: None,
+        collections: Collections::default(),
+        error: None,
+    };
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(document));
+    let header = (&mut deserializer).deserialize_map(NetworkVisitor(&mut collector));
+    // Element errors are reported as such rather than as the syntax error they raise
+    if let Some(error) = collector.error.take() {
+        return Err(error);
+    }
+    let header = header?;
+    deserializer.end()?;
+    collector.finish(header)
+}
+
+/// First byte of a document that is not whitespace, which is left unread
+fn first_byte(document: &mut impl BufRead) -> io::Result<Option<u8>> {
+    loop {
+        let buffer = document.fill_buf()?;
+        let Some(&byte) = buffer.first() else {
+            return Ok(None);
+        };
+        if !byte.is_ascii_whitespace() {
+            return Ok(Some(byte));
+        }
+        let whitespace = buffer
+            .iter()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        document.consume(whitespace);
+    }
+}
+
+/// Elements of the collections read so far, each upgraded and deserialized as it is read
+struct Collector {
+    version: Option<String>,
+    collections: Collections,
+    error: Option<IidmError>,
+}
+
+#[derive(Default)]
+struct Collections {
+    substations: Vec<Substation>,
+    lines: Vec<Line>,
+    three_windings_transformers: Vec<ThreeWindingsTransformer>,
+    switches: Vec<Switch>,
+    shunt_compensators: Vec<ShuntCompensator>,
+    static_var_compensators: Vec<StaticVarCompensator>,
+    dangling_lines: Vec<DanglingLine>,
+    tie_lines: Vec<TieLine>,
+    hvdc_lines: Vec<HvdcLine>,
+    areas: Vec<Area>,
+    extensions: Vec<ExtensionHolder>,
+}
+
+impl Collector {
+    fn collect(&mut self, collection: &str, element: Value) -> Result<(), IidmError> {
+        let version = self.version.clone().ok_or_else(|| {
+            IidmError::Malformed("The IIDM version must precede the network elements".to_string())
+        })?;
+
+        // Elements are upgraded within a network holding only them
+        let mut network = Map::new();
+        network.insert("version".to_string(), Value::String(version));
+        network.insert(collection.to_string(), Value::Array(vec![element]));
+        let mut network = Value::Object(network);
+        upgrade(&mut network)?;
+        let element = network[collection][0].take();
+
+        let collections = &mut self.collections;
+        match collection {
+            "substations" => collections.substations.push(element_of(element)?),
+            "lines" => collections.lines.push(element_of(element)?),
+            "threeWindingsTransformers" => collections
+                .three_windings_transformers
+                .push(element_of(element)?),
+            "switches" => collections.switches.push(element_of(element)?),
+            "shuntCompensators" => collections.shunt_compensators.push(element_of(element)?),
+            "staticVarCompensators" => collections
+                .static_var_compensators
+                .push(element_of(element)?),
+            "danglingLines" => collections.dangling_lines.push(element_of(element)?),
+            "tieLines" => collections.tie_lines.push(element_of(element)?),
+            "hvdcLines" => collections.hvdc_lines.push(element_of(element)?),
+            "areas" => collections.areas.push(element_of(element)?),
+            _ => collections.extensions.push(element_of(element)?),
+        }
+        Ok(())
+    }
+
+    fn finish(self, header: Map<String, Value>) -> Result<Network, IidmError> {
+        let mut header = Value::Object(header);
+        upgrade(&mut header)?;
+        let network: Network = serde_json::from_value(header)?;
+        let collections = self.collections;
+        Ok(Network {
+            substations: collections.substations,
+            lines: collections.lines,
+            three_windings_transformers: collections.three_windings_transformers,
+            switches: collections.switches,
+            shunt_compensators: collections.shunt_compensators,
+            static_var_compensators: collections.static_var_compensators,
+            dangling_lines: collections.dangling_lines,
+            tie_lines: collections.tie_lines,
+            hvdc_lines: collections.hvdc_lines,
+            areas: collections.areas,
+            extensions: collections.extensions,
+            ..network
+        })
+    }
+}
+
+fn element_of<T: DeserializeOwned>(element: Value) -> Result<T, IidmError> {
+    Ok(serde_json::from_value(element)?)
+}
+
+/// Reads the attributes of the network, streaming its collections to the collector
+struct NetworkVisitor<'c>(&'c mut Collector);
+
+impl<'de> Visitor<'de> for NetworkVisitor<'_> {
+    type Value = Map<String, Value>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an IIDM network object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut header = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if let Some(collection) = COLLECTIONS.iter().find(|collection| **collection == key) {
+                map.next_value_seed(CollectionSeed {
+                    collector: &mut *self.0,
+                    collection,
+                })?;
+            } else {
+                let value: Value = map.next_value()?;
+                if key == "version" {
+                    self.0.version = value.as_str().map(str::to_string);
+                }
+                header.insert(key, value);
+            }
+        }
+        Ok(header)
+    }
+}
+
+/// Collects the elements of a collection one at a time
+struct CollectionSeed<'c> {
+    collector: &'c mut Collector,
+    collection: &'static str,
+}
+
+impl<'de> DeserializeSeed<'de> for CollectionSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CollectionSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an array of {}", self.collection)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(element) = seq.next_element::<Value>()? {
+            if let Err(error) = self.collector.collect(self.collection, element) {
+                let message = error.to_string();
+                self.collector.error = Some(error);
+                return Err(de::Error::custom(message));
+            }
+        }
+        Ok(())
+    }
+}
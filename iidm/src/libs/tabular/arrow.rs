@@ -0,0 +1,292 @@
+//! Arrow record batches and Parquet files of the tables, for analytics with Polars, DuckDB
+//! and the like.
+//!
+//! Columns are named after the IIDM JSON attributes, `id` and the container columns coming
+//! first. Nested objects, e.g. tap changers, are struct columns and arrays, e.g. tap steps or
+//! limits groups, are list columns. Values matching several schemas are written in JSON.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, ListArray, RecordBatch,
+    StringArray, StructArray,
+};
+use arrow_buffer::OffsetBuffer;
+use arrow_schema::{DataType, Field, Fields, Schema as ArrowSchema};
+use parquet::arrow::ArrowWriter;
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+use super::{is_null, resolve, tables, Table, TabularError};
+use crate::Network;
+
+/// Arrow schema of a table
+pub fn arrow_schema(table: &Table) -> ArrowSchema {
+    let mut fields: Vec<Field> = table
+        .containers
+        .iter()
+        .map(|container| Field::new(*container, DataType::Utf8, true))
+        .collect();
+    let root = &table.schema;
+    let attributes = struct_fields(&root.schema, root, None, table.excluded).unwrap_or_default();
+    for field in attributes.iter() {
+        if table.containers.contains(&field.name().as_str()) {
+            continue;
+        }
+        // The identifier comes first
+        let index = if field.name() == "id" {
+            0
+        } else {
+            fields.len()
+        };
+        fields.insert(index, field.as_ref().clone());
+    }
+    ArrowSchema::new(fields)
+}
+
+/// Converts a table to an Arrow record batch, failing on values out of the range of their
+/// 32-bit integer columns rather than turning them into nulls
+pub fn to_record_batch(table: &Table) -> Result<RecordBatch, TabularError> {
+    let schema = arrow_schema(table);
+    let mut columns = Vec::new();
+    for field in schema.fields() {
+        let column = match table
+            .containers
+            .iter()
+            .position(|container| container == field.name())
+        {
+            Some(index) => Arc::new(
+                table
+                    .rows
+                    .iter()
+                    .map(|row| row.containers[index].as_deref())
+                    .collect::<StringArray>(),
+            ) as ArrayRef,
+            None => {
+                let values: Vec<_> = table
+                    .rows
+                    .iter()
+                    .map(|row| row.value.get(field.name()))
+                    .collect();
+                array(field.data_type(), &values)?
+            }
+        };
+        columns.push(column);
+    }
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
+/// Writes a table as a Parquet file
+pub fn write_parquet<W: Write + Send>(table: &Table, writer: W) -> Result<(), TabularError> {
+    let batch = to_record_batch(table)?;
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Writes the tables of a network as Parquet files in a directory, named after their
+/// collection, e.g. `generators.parquet`, returning the paths of the written files
+pub fn write_parquet_files(
+    network: &Network,
+    directory: &Path,
+) -> Result<Vec<PathBuf>, TabularError> {
+    std::fs::create_dir_all(directory)?;
+    let mut paths = Vec::new();
+    for table in tables(network)? {
+        let path = directory.join(format!("{}.parquet", table.name));
+        write_parquet(&table, std::fs::File::create(&path)?)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+// Arrow type of a schema, `None` for objects whose properties are all excluded
+fn data_type(
+    schema: &Schema,
+    root: &RootSchema,
+    path: &str,
+    excluded: &[&str],
+) -> Option<DataType> {
+    let Schema::Object(object) = resolve(schema, root) else {
+        return Some(DataType::Utf8);
+    };
+    if has_properties(object, root) {
+        let fields = struct_fields(object, root, Some(path), excluded)?;
+        return Some(DataType::Struct(fields));
+    }
+    if let Some(subschemas) = &object.subschemas {
+        let alternatives: Vec<&Schema> = [&subschemas.any_of, &subschemas.one_of]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|subschema| !is_null(subschema))
+            .collect();
+        return match alternatives.as_slice() {
+            [alternative] => data_type(alternative, root, path, excluded),
+            _ => Some(DataType::Utf8),
+        };
+    }
+    let instance_type = match &object.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => Some(**instance_type),
+        Some(SingleOrVec::Vec(instance_types)) => instance_types
+            .iter()
+            .copied()
+            .find(|instance_type| *instance_type != InstanceType::Null),
+        None => None,
+    };
+    Some(match instance_type {
+        Some(InstanceType::Integer) if object.format.as_deref() == Some("int32") => DataType::Int32,
+        Some(InstanceType::Integer) => DataType::Int64,
+        Some(InstanceType::Number) => DataType::Float64,
+        Some(InstanceType::Boolean) => DataType::Boolean,
+        Some(InstanceType::Array) => {
+            let items = object.array.as_ref().and_then(|array| match &array.items {
+                Some(SingleOrVec::Single(items)) => data_type(items, root, path, excluded),
+                _ => None,
+            });
+            match items {
+                Some(items) => DataType::new_list(items, true),
+                None => DataType::Utf8,
+            }
+        }
+        _ => DataType::Utf8,
+    })
+}
+
+// Fields of the properties of an object and of its alternatives, which serde flattens in it
+fn struct_fields(
+    object: &SchemaObject,
+    root: &RootSchema,
+    path: Option<&str>,
+    excluded: &[&str],
+) -> Option<Fields> {
+    let mut properties = Vec::new();
+    collect_properties(object, root, &mut properties);
+    let fields: Vec<Field> = properties
+        .into_iter()
+        .filter_map(|(name, schema)| {
+            let path = match path {
+                Some(path) => format!("{}.{}", path, name),
+                None => name.clone(),
+            };
+            if excluded.contains(&path.as_str()) {
+                return None;
+            }
+            let data_type = data_type(schema, root, &path, excluded)?;
+            Some(Field::new(name, data_type, true))
+        })
+        .collect();
+    (!fields.is_empty()).then(|| fields.into())
+}
+
+fn collect_properties<'a>(
+    object: &'a SchemaObject,
+    root: &'a RootSchema,
+    properties: &mut Vec<(&'a String, &'a Schema)>,
+) {
+    if let Some(validation) = &object.object {
+        for (name, schema) in &validation.properties {
+            if !properties.iter().any(|(property, _)| *property == name) {
+                properties.push((name, schema));
+            }
+        }
+    }
+    if let Some(subschemas) = &object.subschemas {
+        let alternatives = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+        for subschema in alternatives.into_iter().flatten().flatten() {
+            if let Schema::Object(alternative) = resolve(subschema, root) {
+                collect_properties(alternative, root, properties);
+            }
+        }
+    }
+}
+
+fn has_properties(object: &SchemaObject, root: &RootSchema) -> bool {
+    let mut properties = Vec::new();
+    collect_properties(object, root, &mut properties);
+    !properties.is_empty()
+}
+
+// Arrow array of the values of a column, missing values being nulls
+fn array(data_type: &DataType, values: &[Option<&Value>]) -> Result<ArrayRef, TabularError> {
+    Ok(match data_type {
+        DataType::Int32 => Arc::new(Int32Array::from(
+            values
+                .iter()
+                .map(|value| {
+                    value
+                        .and_then(Value::as_i64)
+                        .map(|v| i32::try_from(v).map_err(|_| TabularError::Int32Overflow(v)))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        DataType::Int64 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.and_then(Value::as_i64))
+                .collect::<Int64Array>(),
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.and_then(Value::as_f64))
+                .collect::<Float64Array>(),
+        ),
+        DataType::Boolean => Arc::new(
+            values
+                .iter()
+                .map(|value| value.and_then(Value::as_bool))
+                .collect::<BooleanArray>(),
+        ),
+        DataType::List(field) => {
+            let mut lengths = Vec::new();
+            let mut items = Vec::new();
+            let mut validity = Vec::new();
+            for value in values {
+                let list = value.and_then(Value::as_array);
+                lengths.push(list.map_or(0, Vec::len));
+                items.extend(list.into_iter().flatten().map(Some));
+                validity.push(list.is_some());
+            }
+            Arc::new(ListArray::try_new(
+                field.clone(),
+                OffsetBuffer::from_lengths(lengths),
+                array(field.data_type(), &items)?,
+                Some(validity.into()),
+            )?)
+        }
+        DataType::Struct(fields) => {
+            let mut children = Vec::new();
+            for field in fields {
+                let values: Vec<_> = values
+                    .iter()
+                    .map(|value| value.and_then(|value| value.get(field.name())))
+                    .collect();
+                children.push(array(field.data_type(), &values)?);
+            }
+            let validity: Vec<bool> = values
+                .iter()
+                .map(|value| value.is_some_and(|value| !value.is_null()))
+                .collect();
+            Arc::new(StructArray::try_new(
+                fields.clone(),
+                children,
+                Some(validity.into()),
+            )?)
+        }
+        _ => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    None | Some(Value::Null) => None,
+                    Some(Value::String(text)) => Some(text.clone()),
+                    Some(value) => Some(value.to_string()),
+                })
+                .collect::<StringArray>(),
+        ),
+    })
+}
@@ -0,0 +1,59 @@
+//! CSV files of the tables, one per equipment type, named after its collection, e.g.
+//! `generators.csv`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{tables, Table, TabularError};
+use crate::Network;
+
+/// Writes a table with its header: `id`, the container columns, then the attribute columns.
+/// Missing values are empty cells, arrays and unflattened objects are written in JSON.
+pub fn write_csv<W: Write>(table: &Table, writer: W) -> Result<(), TabularError> {
+    let mut writer = ::csv::Writer::from_writer(writer);
+    let columns = table.columns();
+    let (id, attributes) = match columns.split_first() {
+        Some((id, attributes)) if id == "id" => (Some(id), attributes),
+        _ => (None, columns.as_slice()),
+    };
+
+    let mut header: Vec<&str> = id.into_iter().map(String::as_str).collect();
+    header.extend(table.containers);
+    header.extend(attributes.iter().map(String::as_str));
+    writer.write_record(&header)?;
+
+    for row in &table.rows {
+        let mut record: Vec<String> = id.into_iter().map(|id| cell(row.get(id))).collect();
+        record.extend(
+            row.containers
+                .iter()
+                .map(|id| id.clone().unwrap_or_default()),
+        );
+        record.extend(attributes.iter().map(|column| cell(row.get(column))));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the tables of a network in a directory, returning the paths of the written files
+pub fn write_csv_files(network: &Network, directory: &Path) -> Result<Vec<PathBuf>, TabularError> {
+    std::fs::create_dir_all(directory)?;
+    let mut paths = Vec::new();
+    for table in tables(network)? {
+        let path = directory.join(format!("{}.csv", table.name));
+        write_csv(&table, std::fs::File::create(&path)?)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+    }
+}
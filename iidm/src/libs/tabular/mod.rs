@@ -0,0 +1,368 @@
+//! Tabular views of networks, one table per equipment type, for spreadsheets and analytics.
+//!
+//! The columns of a table are derived from the JSON schema of its equipment type, so that new
+//! attributes appear without changes here. Equipments are kept in their IIDM JSON layout,
+//! along with the identifiers of their containers, e.g. `substationId` and `voltageLevelId`
+//! for generators. Nested equipment collections, e.g. the voltage levels of a substation,
+//! have their own tables and are left out of their container's table.
+//!
+//! Only the attributes of the schema have columns: the unknown fields of equipments, e.g. the
+//! attributes of newer IIDM versions kept when reading a document, are left out of the tables.
+//! They are still in the `value` of the rows.
+
+mod arrow;
+mod csv;
+
+pub use self::arrow::{arrow_schema, to_record_batch, write_parquet, write_parquet_files};
+pub use self::csv::{write_csv, write_csv_files};
+
+use std::collections::HashMap;
+use std::io;
+
+use schemars::schema::{InstanceType, RootSchema, Schema, SingleOrVec};
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::json::resolve;
+use crate::{
+    Area, Battery, Bus, BusbarSection, DanglingLine, Generator, HvdcLine, Line, Load, Network,
+    ShuntCompensator, StaticVarCompensator, Substation, Switch, ThreeWindingsTransformer, TieLine,
+    TwoWindingsTransformer, VoltageLevel,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TabularError {
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("CSV error: {0}")]
+    Csv(#[from] ::csv::Error),
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+    #[error("Integer {0} is out of the range of its 32-bit integer column")]
+    Int32Overflow(i64),
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Equipments of one type
+#[derive(Debug)]
+pub struct Table {
+    /// Name of the IIDM JSON collection of the equipments, e.g. `generators`
+    pub name: &'static str,
+    /// Schema of the equipment type
+    pub schema: RootSchema,
+    /// Names of the container columns, e.g. `substationId`
+    pub containers: &'static [&'static str],
+    /// Paths of the nested collections left out of the table, e.g. `voltageLevels`
+    pub excluded: &'static [&'static str],
+    pub rows: Vec<Row>,
+}
+
+#[derive(Debug)]
+pub struct Row {
+    /// Identifiers of the containers, in the order of the container columns
+    pub containers: Vec<Option<String>>,
+    /// Equipment in its IIDM JSON layout, without the excluded collections
+    pub value: Value,
+}
+
+impl Table {
+    fn new<T: JsonSchema>(
+        name: &'static str,
+        containers: &'static [&'static str],
+        excluded: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            schema: schema_for!(T),
+            containers,
+            excluded,
+            rows: Vec::new(),
+        }
+    }
+
+    fn push<T: Serialize>(
+        &mut self,
+        equipment: &T,
+        containers: Vec<Option<String>>,
+    ) -> Result<(), serde_json::Error> {
+        let mut value = serde_json::to_value(equipment)?;
+        for path in self.excluded {
+            let (parent, key) = match path.rsplit_once('.') {
+                Some((parent, key)) => (lookup_mut(&mut value, parent), key),
+                None => (Some(&mut value), *path),
+            };
+            if let Some(Value::Object(parent)) = parent {
+                parent.remove(key);
+            }
+        }
+        self.rows.push(Row { containers, value });
+        Ok(())
+    }
+
+    /// Attribute columns with nested objects flattened, named by their dotted path, e.g.
+    /// `ratioTapChanger.tapPosition`. The `id` column comes first, arrays are not flattened.
+    /// Attributes without schema, kept in the unknown fields, have no column.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        flatten(
+            &Schema::Object(self.schema.schema.clone()),
+            &self.schema,
+            None,
+            &mut columns,
+        );
+        // Attributes naming a container, e.g. the `voltageLevelId` of switches, are given by
+        // the container columns
+        columns.retain(|column| {
+            !self.containers.contains(&column.as_str())
+                && !self.excluded.iter().any(|path| {
+                    column == path
+                        || column
+                            .strip_prefix(path)
+                            .is_some_and(|c| c.starts_with('.'))
+                })
+        });
+        if let Some(index) = columns.iter().position(|column| column == "id") {
+            let id = columns.remove(index);
+            columns.insert(0, id);
+        }
+        columns
+    }
+}
+
+impl Row {
+    /// Value of a dotted path column, `None` when the equipment doesn't have it
+    pub fn get(&self, column: &str) -> Option<&Value> {
+        column
+            .split('.')
+            .try_fold(&self.value, |value, key| value.get(key))
+    }
+}
+
+/// Tables of the equipments of a network, always in the same order, empty tables included
+pub fn tables(network: &Network) -> Result<Vec<Table>, serde_json::Error> {
+    const LOCATED: &[&str] = &["substationId", "voltageLevelId"];
+    const BRANCH: &[&str] = &["substationId1", "substationId2"];
+
+    let mut substations = Table::new::<Substation>(
+        "substations",
+        &[],
+        &["voltageLevels", "twoWindingsTransformers"],
+    );
+    let mut voltage_levels = Table::new::<VoltageLevel>(
+        "voltageLevels",
+        &["substationId"],
+        &[
+            "generators",
+            "loads",
+            "batteries",
+            "busbar_sections",
+            "busBreakerTopology.buses",
+            "busBreakerTopology.switches",
+            "nodeBreakerTopology.switches",
+        ],
+    );
+    let mut generators = Table::new::<Generator>("generators", LOCATED, &[]);
+    let mut loads = Table::new::<Load>("loads", LOCATED, &[]);
+    let mut batteries = Table::new::<Battery>("batteries", LOCATED, &[]);
+    let mut busbar_sections = Table::new::<BusbarSection>("busbarSections", LOCATED, &[]);
+    let mut buses = Table::new::<Bus>("buses", LOCATED, &[]);
+    let mut switches = Table::new::<Switch>("switches", LOCATED, &[]);
+    let mut two_windings_transformers =
+        Table::new::<TwoWindingsTransformer>("twoWindingsTransformers", &["substationId"], &[]);
+    let mut three_windings_transformers =
+        Table::new::<ThreeWindingsTransformer>("threeWindingsTransformers", &["substationId"], &[]);
+    let mut lines = Table::new::<Line>("lines", BRANCH, &[]);
+    let mut shunt_compensators = Table::new::<ShuntCompensator>("shuntCompensators", LOCATED, &[]);
+    let mut static_var_compensators =
+        Table::new::<StaticVarCompensator>("staticVarCompensators", LOCATED, &[]);
+    let mut dangling_lines = Table::new::<DanglingLine>("danglingLines", LOCATED, &[]);
+    let mut tie_lines = Table::new::<TieLine>("tieLines", BRANCH, &[]);
+    let mut hvdc_lines = Table::new::<HvdcLine>("hvdcLines", BRANCH, &[]);
+    let mut areas = Table::new::<Area>("areas", &[], &[]);
+
+    let substation_ids: HashMap<&str, &str> = network
+        .substations
+        .iter()
+        .flat_map(|substation| {
+            substation
+                .voltage_levels
+                .iter()
+                .map(|voltage_level| (voltage_level.id.as_str(), substation.id.as_str()))
+        })
+        .collect();
+    let substation_of = |voltage_level_id: Option<&str>| {
+        voltage_level_id
+            .and_then(|id| substation_ids.get(id))
+            .map(|id| id.to_string())
+    };
+    // Injections outside of voltage levels are located by their `voltageLevelId`
+    let located = |voltage_level_id: Option<&str>| {
+        let voltage_level_id = voltage_level_id.filter(|id| !id.is_empty());
+        vec![
+            substation_of(voltage_level_id),
+            voltage_level_id.map(str::to_string),
+        ]
+    };
+
+    for substation in &network.substations {
+        substations.push(substation, Vec::new())?;
+        let substation_id = || Some(substation.id.clone());
+        for transformer in &substation.two_windings_transformers {
+            two_windings_transformers.push(transformer, vec![substation_id()])?;
+        }
+        for voltage_level in &substation.voltage_levels {
+            voltage_levels.push(voltage_level, vec![substation_id()])?;
+            let containers = || vec![substation_id(), Some(voltage_level.id.clone())];
+            for generator in voltage_level.generators.iter().flatten() {
+                generators.push(generator, containers())?;
+            }
+            for load in voltage_level.loads.iter().flatten() {
+                loads.push(load, containers())?;
+            }
+            for battery in voltage_level.batteries.iter().flatten() {
+                batteries.push(battery, containers())?;
+            }
+            for busbar_section in voltage_level.busbar_sections.iter().flatten() {
+                busbar_sections.push(busbar_section, containers())?;
+            }
+            if let Some(topology) = &voltage_level.bus_breaker_topology {
+                for bus in &topology.buses {
+                    buses.push(bus, containers())?;
+                }
+                for switch in &topology.switches {
+                    switches.push(switch, containers())?;
+                }
+            }
+            if let Some(topology) = &voltage_level.node_breaker_topology {
+                for switch in &topology.switches {
+                    switches.push(switch, containers())?;
+                }
+            }
+        }
+    }
+
+    for switch in &network.switches {
+        let containers = vec![
+            substation_of(Some(&switch.voltage_level_id)),
+            Some(switch.voltage_level_id.clone()),
+        ];
+        switches.push(switch, containers)?;
+    }
+    for transformer in &network.three_windings_transformers {
+        let containers = vec![substation_of(Some(&transformer.leg1.voltage_level_id))];
+        three_windings_transformers.push(transformer, containers)?;
+    }
+    for line in &network.lines {
+        let containers = vec![
+            substation_of(Some(&line.voltage_level_id1)),
+            substation_of(Some(&line.voltage_level_id2)),
+        ];
+        lines.push(line, containers)?;
+    }
+    for shunt_compensator in &network.shunt_compensators {
+        shunt_compensators.push(
+            shunt_compensator,
+            located(Some(&shunt_compensator.voltage_level_id)),
+        )?;
+    }
+    for static_var_compensator in &network.static_var_compensators {
+        static_var_compensators.push(
+            static_var_compensator,
+            located(Some(&static_var_compensator.voltage_level_id)),
+        )?;
+    }
+    for dangling_line in &network.dangling_lines {
+        dangling_lines.push(
+            dangling_line,
+            located(Some(&dangling_line.voltage_level_id)),
+        )?;
+    }
+    for tie_line in &network.tie_lines {
+        let containers = vec![
+            located(Some(&tie_line.dangling_line1.voltage_level_id)).swap_remove(0),
+            located(Some(&tie_line.dangling_line2.voltage_level_id)).swap_remove(0),
+        ];
+        tie_lines.push(tie_line, containers)?;
+    }
+    for hvdc_line in &network.hvdc_lines {
+        let containers = vec![
+            substation_of(Some(&hvdc_line.converter_station1.voltage_level_id)),
+            substation_of(Some(&hvdc_line.converter_station2.voltage_level_id)),
+        ];
+        hvdc_lines.push(hvdc_line, containers)?;
+    }
+    for area in &network.areas {
+        areas.push(area, Vec::new())?;
+    }
+
+    Ok(vec![
+        substations,
+        voltage_levels,
+        generators,
+        loads,
+        batteries,
+        busbar_sections,
+        buses,
+        switches,
+        two_windings_transformers,
+        three_windings_transformers,
+        lines,
+        shunt_compensators,
+        static_var_compensators,
+        dangling_lines,
+        tie_lines,
+        hvdc_lines,
+        areas,
+    ])
+}
+
+// Columns of a schema at a path, objects being flattened into the columns of their properties
+// and alternatives into the columns of all of them
+fn flatten(schema: &Schema, root: &RootSchema, path: Option<&str>, columns: &mut Vec<String>) {
+    let mut nested = false;
+    if let Schema::Object(object) = resolve(schema, root) {
+        if let Some(validation) = &object.object {
+            for (name, property) in &validation.properties {
+                let path = match path {
+                    Some(path) => format!("{}.{}", path, name),
+                    None => name.clone(),
+                };
+                flatten(property, root, Some(&path), columns);
+                nested = true;
+            }
+        }
+        if let Some(subschemas) = &object.subschemas {
+            let alternatives = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+            for subschema in alternatives.into_iter().flatten().flatten() {
+                if !is_null(subschema) {
+                    flatten(subschema, root, path, columns);
+                    nested = true;
+                }
+            }
+        }
+    }
+    if let Some(path) = path.filter(|_| !nested) {
+        if !columns.iter().any(|column| column == path) {
+            columns.push(path.to_string());
+        }
+    }
+}
+
+fn is_null(schema: &Schema) -> bool {
+    match schema {
+        Schema::Object(object) => matches!(
+            &object.instance_type,
+            Some(SingleOrVec::Single(instance_type)) if **instance_type == InstanceType::Null
+        ),
+        Schema::Bool(_) => false,
+    }
+}
+
+fn lookup_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.')
+        .try_fold(value, |value, key| value.get_mut(key))
+}
@@ -0,0 +1,901 @@
+//! Reader of UCTE-DEF files
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime};
+use serde_json::{json, Map, Value};
+
+use super::{is_x_node, nominal_voltage, UcteError};
+use crate::libs::version::{IidmVersion, DEFAULT_LIMITS_GROUP};
+use crate::Network;
+
+/// Reads a network from a UCTE-DEF file.
+///
+/// The id of the network is usually the name of the file, the case date is read from its
+/// `yyyyMMdd_HHmm` prefix when it follows the UCTE naming convention.
+pub fn from_ucte(id: &str, ucte: &str) -> Result<Network, UcteError> {
+    let file = UcteFile::parse(ucte)?;
+    let network = file.to_json(id)?;
+    Ok(serde_json::from_value(network)?)
+}
+
+/// Line of a UCTE-DEF file, whose fields are at fixed columns
+struct Record {
+    number: usize,
+    chars: Vec<char>,
+}
+
+impl Record {
+    fn new(number: usize, line: &str) -> Self {
+        Self {
+            number,
+            chars: line.chars().collect(),
+        }
+    }
+
+    fn error(&self, message: String) -> UcteError {
+        UcteError::Syntax {
+            line: self.number,
+            message,
+        }
+    }
+
+    // Columns are numbered from 1 and inclusive, as in the UCTE-DEF specification
+    fn text(&self, start: usize, end: usize) -> String {
+        self.chars
+            .iter()
+            .skip(start - 1)
+            .take(end + 1 - start)
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    fn optional_number(
+        &self,
+        start: usize,
+        end: usize,
+        name: &str,
+    ) -> Result<Option<f64>, UcteError> {
+        let text = self.text(start, end);
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| self.error(format!("invalid {} '{}'", name, text)))
+    }
+
+    fn number(&self, start: usize, end: usize, name: &str) -> Result<f64, UcteError> {
+        self.optional_number(start, end, name)?
+            .ok_or_else(|| self.error(format!("missing {}", name)))
+    }
+
+    fn optional_integer(
+        &self,
+        start: usize,
+        end: usize,
+        name: &str,
+    ) -> Result<Option<i32>, UcteError> {
+        let text = self.text(start, end);
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| self.error(format!("invalid {} '{}'", name, text)))
+    }
+
+    fn integer(&self, start: usize, end: usize, name: &str) -> Result<i32, UcteError> {
+        self.optional_integer(start, end, name)?
+            .ok_or_else(|| self.error(format!("missing {}", name)))
+    }
+
+    fn node_code(&self, start: usize, name: &str) -> Result<String, UcteError> {
+        let code: String = self.chars.iter().skip(start - 1).take(8).collect();
+        if code.chars().count() != 8 || code.trim().is_empty() {
+            return Err(self.error(format!("invalid {} '{}'", name, code.trim())));
+        }
+        Ok(code)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Comments,
+    Nodes,
+    Lines,
+    Transformers,
+    Regulations,
+    // Special transformer descriptions and exchange powers are not modelled
+    Ignored,
+}
+
+struct Node {
+    code: String,
+    geographical_name: String,
+    country: String,
+    node_type: i32,
+    voltage: Option<f64>,
+    active_load: f64,
+    reactive_load: f64,
+    active_generation: f64,
+    reactive_generation: f64,
+    minimum_generation: Option<f64>,
+    maximum_generation: Option<f64>,
+    minimum_reactive_generation: Option<f64>,
+    maximum_reactive_generation: Option<f64>,
+    plant_type: String,
+}
+
+impl Node {
+    fn parse(record: &Record, zone: Option<&str>) -> Result<Self, UcteError> {
+        let country = zone
+            .ok_or_else(|| record.error("node outside of a country zone".to_string()))?
+            .to_string();
+        let code = record.node_code(1, "node code")?;
+        if !is_x_node(&code) && code.chars().nth(6).and_then(nominal_voltage).is_none() {
+            return Err(record.error(format!("invalid voltage level code in node {}", code)));
+        }
+        // Values are optional for nodes without load nor generation
+        let value = |start, end, name| -> Result<f64, UcteError> {
+            Ok(record.optional_number(start, end, name)?.unwrap_or(0.0))
+        };
+
+        Ok(Self {
+            geographical_name: record.text(10, 21),
+            country,
+            node_type: record.integer(25, 25, "node type")?,
+            voltage: record.optional_number(27, 32, "voltage")?,
+            active_load: value(34, 40, "active load")?,
+            reactive_load: value(42, 48, "reactive load")?,
+            active_generation: value(50, 56, "active generation")?,
+            reactive_generation: value(58, 64, "reactive generation")?,
+            minimum_generation: record.optional_number(66, 72, "minimum generation")?,
+            maximum_generation: record.optional_number(74, 80, "maximum generation")?,
+            minimum_reactive_generation: record.optional_number(
+                82,
+                88,
+                "minimum reactive generation",
+            )?,
+            maximum_reactive_generation: record.optional_number(
+                90,
+                96,
+                "maximum reactive generation",
+            )?,
+            plant_type: record.text(128, 128),
+            code,
+        })
+    }
+
+    fn substation_id(&self) -> String {
+        self.code.chars().take(6).collect()
+    }
+
+    fn voltage_level_id(&self) -> String {
+        self.code.chars().take(7).collect()
+    }
+
+    fn nominal_v(&self) -> f64 {
+        self.code
+            .chars()
+            .nth(6)
+            .and_then(nominal_voltage)
+            .unwrap_or_default()
+    }
+
+    // PV and slack nodes regulate their voltage
+    fn regulates_voltage(&self) -> bool {
+        matches!(self.node_type, 2 | 3)
+    }
+
+    fn has_generator(&self) -> bool {
+        self.active_generation != 0.0
+            || self.reactive_generation != 0.0
+            || self.minimum_generation.is_some()
+            || self.maximum_generation.is_some()
+            || self.regulates_voltage()
+    }
+
+    fn energy_source(&self) -> &'static str {
+        match self.plant_type.as_str() {
+            "H" => "HYDRO",
+            "N" => "NUCLEAR",
+            "W" => "WIND",
+            "L" | "C" | "G" | "O" => "THERMAL",
+            _ => "OTHER",
+        }
+    }
+}
+
+/// Line or transformer, identified by its two nodes and its order code
+struct Branch {
+    number: usize,
+    node1: String,
+    node2: String,
+    order_code: String,
+    status: i32,
+    r: f64,
+    x: f64,
+    b: f64,
+    current_limit: Option<f64>,
+    element_name: String,
+}
+
+impl Branch {
+    fn parse(record: &Record) -> Result<Self, UcteError> {
+        Ok(Self {
+            number: record.number,
+            node1: record.node_code(1, "node 1")?,
+            node2: record.node_code(10, "node 2")?,
+            order_code: record.text(19, 19),
+            status: record.integer(21, 21, "status")?,
+            r: 0.0,
+            x: 0.0,
+            b: 0.0,
+            current_limit: None,
+            element_name: String::new(),
+        })
+    }
+
+    fn id(&self) -> String {
+        format!("{} {} {}", self.node1, self.node2, self.order_code)
+    }
+
+    // Status 7, 8 and 9 are elements out of operation, busbar couplers for status 7
+    fn in_operation(&self) -> bool {
+        !matches!(self.status, 7..=9)
+    }
+
+    // Status 2 and 7 are busbar couplers
+    fn is_coupler(&self) -> bool {
+        matches!(self.status, 2 | 7)
+    }
+}
+
+struct Line {
+    branch: Branch,
+}
+
+impl Line {
+    fn parse(record: &Record) -> Result<Self, UcteError> {
+        let mut branch = Branch::parse(record)?;
+        branch.r = record.number(23, 28, "resistance")?;
+        branch.x = record.number(30, 35, "reactance")?;
+        branch.b = record.number(37, 44, "susceptance")?;
+        branch.current_limit = record.optional_number(46, 51, "current limit")?;
+        branch.element_name = record.text(53, 64);
+        Ok(Self { branch })
+    }
+}
+
+struct Transformer {
+    branch: Branch,
+    rated_u1: f64,
+    rated_u2: f64,
+    nominal_power: f64,
+    g: f64,
+}
+
+impl Transformer {
+    fn parse(record: &Record) -> Result<Self, UcteError> {
+        let mut branch = Branch::parse(record)?;
+        branch.r = record.number(41, 46, "resistance")?;
+        branch.x = record.number(48, 53, "reactance")?;
+        branch.b = record.number(55, 62, "susceptance")?;
+        branch.current_limit = record.optional_number(71, 76, "current limit")?;
+        branch.element_name = record.text(78, 89);
+        Ok(Self {
+            rated_u1: record.number(23, 27, "rated voltage 1")?,
+            rated_u2: record.number(29, 33, "rated voltage 2")?,
+            nominal_power: record.number(35, 39, "nominal power")?,
+            g: record.number(64, 69, "conductance")?,
+            branch,
+        })
+    }
+}
+
+/// Voltage regulation by taps, each tap changes the voltage by `du` percents
+struct PhaseRegulation {
+    du: f64,
+    taps: i32,
+    tap: i32,
+    voltage: Option<f64>,
+}
+
+/// Phase shift by taps, each tap adds a voltage of `du` percents with an angle `theta`
+struct AngleRegulation {
+    du: f64,
+    theta: f64,
+    taps: i32,
+    tap: i32,
+    active_power: Option<f64>,
+    symmetrical: bool,
+}
+
+struct Regulation {
+    number: usize,
+    id: String,
+    phase: Option<PhaseRegulation>,
+    angle: Option<AngleRegulation>,
+}
+
+impl Regulation {
+    fn parse(record: &Record) -> Result<Self, UcteError> {
+        let id = format!(
+            "{} {} {}",
+            record.node_code(1, "node 1")?,
+            record.node_code(10, "node 2")?,
+            record.text(19, 19)
+        );
+        let phase = match record.optional_number(21, 25, "voltage step")? {
+            Some(du) => Some(PhaseRegulation {
+                du,
+                taps: record.integer(27, 28, "number of taps")?,
+                tap: record.integer(30, 32, "tap position")?,
+                voltage: record.optional_number(34, 38, "regulated voltage")?,
+            }),
+            None => None,
+        };
+        let angle = match record.optional_number(40, 44, "angle voltage step")? {
+            Some(du) => Some(AngleRegulation {
+                du,
+                theta: record.number(46, 48, "angle")?,
+                taps: record.integer(50, 51, "number of angle taps")?,
+                tap: record.integer(53, 55, "angle tap position")?,
+                active_power: record.optional_number(57, 61, "regulated active power")?,
+                symmetrical: match record.text(63, 66).as_str() {
+                    "SYMM" => true,
+                    "ASYM" | "" => false,
+                    other => {
+                        return Err(record.error(format!("invalid regulation type '{}'", other)))
+                    }
+                },
+            }),
+            None => None,
+        };
+        Ok(Self {
+            number: record.number,
+            id,
+            phase,
+            angle,
+        })
+    }
+}
+
+#[derive(Default)]
+struct UcteFile {
+    nodes: Vec<Node>,
+    // Position of the nodes by code
+    node_index: HashMap<String, usize>,
+    lines: Vec<Line>,
+    transformers: Vec<Transformer>,
+    regulations: Vec<Regulation>,
+}
+
+impl UcteFile {
+    fn parse(ucte: &str) -> Result<Self, UcteError> {
+        let mut file = Self::default();
+        let mut section = None;
+        let mut zone: Option<String> = None;
+
+        for (index, line) in ucte.lines().enumerate() {
+            let record = Record::new(index + 1, line);
+            if let Some(header) = line.strip_prefix("##") {
+                section = Some(match header.chars().next() {
+                    Some('C') => Section::Comments,
+                    Some('N') => Section::Nodes,
+                    Some('Z') if section == Some(Section::Nodes) => {
+                        zone = Some(header[1..].trim().to_string());
+                        Section::Nodes
+                    }
+                    Some('L') => Section::Lines,
+                    Some('T') if header.starts_with("TT") => Section::Ignored,
+                    Some('T') => Section::Transformers,
+                    Some('R') => Section::Regulations,
+                    Some('E') => Section::Ignored,
+                    _ => return Err(record.error(format!("unknown section '{}'", line.trim()))),
+                });
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match section {
+                None => return Err(record.error("record outside of a section".to_string())),
+                Some(Section::Comments) | Some(Section::Ignored) => {}
+                Some(Section::Nodes) => {
+                    let node = Node::parse(&record, zone.as_deref())?;
+                    if file.node_index.contains_key(&node.code) {
+                        return Err(record.error(format!("duplicate node {}", node.code)));
+                    }
+                    file.node_index.insert(node.code.clone(), file.nodes.len());
+                    file.nodes.push(node);
+                }
+                Some(Section::Lines) => file.lines.push(Line::parse(&record)?),
+                Some(Section::Transformers) => file.transformers.push(Transformer::parse(&record)?),
+                Some(Section::Regulations) => file.regulations.push(Regulation::parse(&record)?),
+            }
+        }
+        Ok(file)
+    }
+
+    fn node(&self, code: &str, number: usize) -> Result<&Node, UcteError> {
+        self.node_index
+            .get(code)
+            .map(|&index| &self.nodes[index])
+            .ok_or_else(|| UcteError::UnknownNode {
+                line: number,
+                node: code.to_string(),
+            })
+    }
+
+    fn to_json(&self, id: &str) -> Result<Value, UcteError> {
+        let mut substations = Vec::new();
+        for node in self.nodes.iter().filter(|node| !is_x_node(&node.code)) {
+            add_node(&mut substations, node);
+        }
+
+        let mut lines = Vec::new();
+        let mut x_node_lines: Vec<(&Node, Vec<Map<String, Value>>)> = Vec::new();
+        for line in &self.lines {
+            let branch = &line.branch;
+            let node1 = self.node(&branch.node1, branch.number)?;
+            let node2 = self.node(&branch.node2, branch.number)?;
+            match (is_x_node(&node1.code), is_x_node(&node2.code)) {
+                (true, true) => {
+                    return Err(UcteError::Syntax {
+                        line: branch.number,
+                        message: format!("line {} connects two X-nodes", branch.id()),
+                    })
+                }
+                (false, false) => {
+                    if branch.is_coupler() && node1.voltage_level_id() == node2.voltage_level_id() {
+                        add_coupler(&mut substations, branch, node1);
+                    } else {
+                        lines.push(line_json(branch, node1, node2));
+                    }
+                }
+                (x1, _) => {
+                    let (node, x_node) = if x1 { (node2, node1) } else { (node1, node2) };
+                    let dangling_line = dangling_line_json(branch, node, x_node);
+                    match x_node_lines
+                        .iter_mut()
+                        .find(|(node, _)| node.code == x_node.code)
+                    {
+                        Some((_, dangling_lines)) => dangling_lines.push(dangling_line),
+                        None => x_node_lines.push((x_node, vec![dangling_line])),
+                    }
+                }
+            }
+        }
+
+        // X-nodes connected twice pair their dangling lines, the injection of the X-node
+        // being held by the first one
+        let mut dangling_lines = Vec::new();
+        let mut tie_lines = Vec::new();
+        for (x_node, mut halves) in x_node_lines {
+            if let Some(first) = halves.first_mut() {
+                first.insert(
+                    "p0".to_string(),
+                    json!(x_node.active_load + x_node.active_generation),
+                );
+                first.insert(
+                    "q0".to_string(),
+                    json!(x_node.reactive_load + x_node.reactive_generation),
+                );
+            }
+            if halves.len() == 2 {
+                let half2 = halves.pop().unwrap_or_default();
+                let half1 = halves.pop().unwrap_or_default();
+                let id = format!(
+                    "{} + {}",
+                    half1["id"].as_str().unwrap_or_default(),
+                    half2["id"].as_str().unwrap_or_default()
+                );
+                tie_lines.push(json!({
+                    "id": id,
+                    "name": id,
+                    "danglingLine1": half1,
+                    "danglingLine2": half2,
+                    "ucteXnodeCode": x_node.code,
+                }));
+            } else {
+                dangling_lines.extend(halves.into_iter().map(Value::Object));
+            }
+        }
+
+        // Transformers are held by the substation of their second node, in which the
+        // substation of their first node is merged
+        let mut merged: HashMap<String, String> = HashMap::new();
+        let mut transformers: Vec<(String, Map<String, Value>)> = Vec::new();
+        for transformer in &self.transformers {
+            let branch = &transformer.branch;
+            let node1 = self.node(&branch.node1, branch.number)?;
+            let node2 = self.node(&branch.node2, branch.number)?;
+            if is_x_node(&node1.code) || is_x_node(&node2.code) {
+                return Err(UcteError::Syntax {
+                    line: branch.number,
+                    message: format!("transformer {} is connected to an X-node", branch.id()),
+                });
+            }
+            merge_substations(
+                &mut substations,
+                &mut merged,
+                &node1.substation_id(),
+                &node2.substation_id(),
+            );
+            transformers.push((
+                node2.substation_id(),
+                transformer_json(transformer, node1, node2),
+            ));
+        }
+
+        let transformer_index: HashMap<String, usize> = transformers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, transformer))| {
+                Some((transformer["id"].as_str()?.to_string(), index))
+            })
+            .collect();
+        for regulation in &self.regulations {
+            let index = transformer_index.get(&regulation.id).ok_or_else(|| {
+                UcteError::UnknownTransformer {
+                    line: regulation.number,
+                    id: regulation.id.clone(),
+                }
+            })?;
+            add_regulation(&mut transformers[*index].1, regulation);
+        }
+
+        for (substation_id, transformer) in transformers {
+            let substation_id = root(&merged, &substation_id);
+            if let Some(substation) = substations
+                .iter_mut()
+                .find(|substation| substation["id"] == substation_id)
+            {
+                push(
+                    substation,
+                    "twoWindingsTransformers",
+                    Value::Object(transformer),
+                );
+            }
+        }
+
+        Ok(json!({
+            "version": IidmVersion::LATEST.to_string(),
+            "id": id,
+            "caseDate": case_date(id),
+            "forecastDistance": 0,
+            "sourceFormat": "UCTE",
+            "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+            "substations": substations,
+            "lines": lines,
+            "danglingLines": dangling_lines,
+            "tieLines": tie_lines,
+        }))
+    }
+}
+
+fn case_date(id: &str) -> DateTime<chrono::FixedOffset> {
+    let prefix: String = id.chars().take(13).collect();
+    NaiveDateTime::parse_from_str(&prefix, "%Y%m%d_%H%M")
+        .map(|date| date.and_utc())
+        .unwrap_or(DateTime::UNIX_EPOCH)
+        .fixed_offset()
+}
+
+fn push(object: &mut Value, key: &str, item: Value) {
+    if let Some(items) = object.get_mut(key).and_then(Value::as_array_mut) {
+        items.push(item);
+    }
+}
+
+fn find_or_insert<'a>(
+    items: &'a mut Vec<Value>,
+    id: &str,
+    create: impl FnOnce() -> Value,
+) -> &'a mut Value {
+    match items.iter().position(|item| item["id"] == id) {
+        Some(position) => &mut items[position],
+        None => {
+            items.push(create());
+            items.last_mut().expect("item was just pushed")
+        }
+    }
+}
+
+// Substation in which a substation has been merged, or the substation itself
+fn root<'a>(merged: &'a HashMap<String, String>, id: &'a str) -> &'a str {
+    match merged.get(id) {
+        Some(parent) => root(merged, parent),
+        None => id,
+    }
+}
+
+// Moves the voltage levels of the substation of the first end of a transformer to the
+// substation of its second end
+fn merge_substations(
+    substations: &mut Vec<Value>,
+    merged: &mut HashMap<String, String>,
+    id1: &str,
+    id2: &str,
+) {
+    let id1 = root(merged, id1).to_string();
+    let id2 = root(merged, id2).to_string();
+    if id1 == id2 {
+        return;
+    }
+    let Some(position) = substations
+        .iter()
+        .position(|substation| substation["id"] == id1.as_str())
+    else {
+        return;
+    };
+    let mut substation1 = substations.remove(position);
+    if let Some(substation2) = substations
+        .iter_mut()
+        .find(|substation| substation["id"] == id2.as_str())
+    {
+        if let (Some(voltage_levels), Some(Value::Array(moved))) = (
+            substation2["voltageLevels"].as_array_mut(),
+            substation1.get_mut("voltageLevels").map(Value::take),
+        ) {
+            voltage_levels.extend(moved);
+        }
+    }
+    merged.insert(id1, id2);
+}
+
+fn find_voltage_level<'a>(substations: &'a mut [Value], node: &Node) -> Option<&'a mut Value> {
+    let voltage_level_id = node.voltage_level_id();
+    substations
+        .iter_mut()
+        .filter_map(|substation| substation["voltageLevels"].as_array_mut())
+        .flatten()
+        .find(|voltage_level| voltage_level["id"] == voltage_level_id.as_str())
+}
+
+fn add_node(substations: &mut Vec<Value>, node: &Node) {
+    let substation = find_or_insert(substations, &node.substation_id(), || {
+        json!({
+            "id": node.substation_id(),
+            "country": node.country,
+            "tso": "",
+            "geographicalTags": [],
+            "voltageLevels": [],
+            "twoWindingsTransformers": [],
+        })
+    });
+    let voltage_levels = substation["voltageLevels"]
+        .as_array_mut()
+        .expect("voltage levels are an array");
+    let voltage_level = find_or_insert(voltage_levels, &node.voltage_level_id(), || {
+        json!({
+            "id": node.voltage_level_id(),
+            "nominalV": node.nominal_v(),
+            "topologyKind": "BUS_BREAKER",
+            "generators": [],
+            "loads": [],
+            "busBreakerTopology": {"buses": [], "switches": []},
+        })
+    });
+
+    let mut bus = json!({"id": node.code});
+    if !node.geographical_name.is_empty() {
+        bus["geographicalName"] = json!(node.geographical_name);
+    }
+    push(&mut voltage_level["busBreakerTopology"], "buses", bus);
+
+    if node.active_load != 0.0 || node.reactive_load != 0.0 {
+        let load = json!({
+            "id": format!("{}_load", node.code),
+            "loadType": "UNDEFINED",
+            "p0": node.active_load,
+            "q0": node.reactive_load,
+            "bus": node.code,
+            "connectableBus": node.code,
+        });
+        push(voltage_level, "loads", load);
+    }
+
+    if node.has_generator() {
+        // Generations are negative in UCTE-DEF
+        let (min_p, max_p) = ordered(
+            -node.minimum_generation.unwrap_or(DEFAULT_POWER),
+            -node.maximum_generation.unwrap_or(-DEFAULT_POWER),
+        );
+        let (min_q, max_q) = ordered(
+            -node.minimum_reactive_generation.unwrap_or(DEFAULT_POWER),
+            -node.maximum_reactive_generation.unwrap_or(-DEFAULT_POWER),
+        );
+        let target_v = node
+            .voltage
+            .filter(|voltage| *voltage > 0.0)
+            .unwrap_or_else(|| node.nominal_v());
+        let generator = json!({
+            "id": format!("{}_generator", node.code),
+            "energySource": node.energy_source(),
+            "minP": min_p,
+            "maxP": max_p,
+            "voltageRegulatorOn": node.regulates_voltage(),
+            "targetP": -node.active_generation,
+            "targetV": target_v,
+            "targetQ": -node.reactive_generation,
+            "bus": node.code,
+            "connectableBus": node.code,
+            "minMaxReactiveLimits": {"minQ": min_q, "maxQ": max_q},
+        });
+        push(voltage_level, "generators", generator);
+    }
+}
+
+/// Power limits of generators without permissible generations
+const DEFAULT_POWER: f64 = 9999.0;
+
+fn ordered(a: f64, b: f64) -> (f64, f64) {
+    (a.min(b), a.max(b))
+}
+
+fn add_coupler(substations: &mut [Value], branch: &Branch, node: &Node) {
+    let Some(voltage_level) = find_voltage_level(substations, node) else {
+        return;
+    };
+    let switch = json!({
+        "id": branch.id(),
+        "kind": "BREAKER",
+        "open": !branch.in_operation(),
+        "retained": false,
+        "bus1": branch.node1,
+        "bus2": branch.node2,
+        "voltageLevelId": node.voltage_level_id(),
+    });
+    push(&mut voltage_level["busBreakerTopology"], "switches", switch);
+}
+
+// Connection of a side of a branch, disconnected when the branch is out of operation
+fn connect(object: &mut Map<String, Value>, suffix: &str, node: &Node, branch: &Branch) {
+    object.insert(
+        format!("voltageLevelId{}", suffix),
+        json!(node.voltage_level_id()),
+    );
+    if branch.in_operation() {
+        object.insert(format!("bus{}", suffix), json!(node.code));
+    }
+    object.insert(format!("connectableBus{}", suffix), json!(node.code));
+}
+
+fn add_current_limit(object: &mut Map<String, Value>, suffix: &str, current_limit: Option<f64>) {
+    let Some(permanent_limit) = current_limit else {
+        return;
+    };
+    let group = json!({
+        "id": DEFAULT_LIMITS_GROUP,
+        "currentLimits": {"permanentLimit": permanent_limit, "temporaryLimits": []},
+    });
+    object.insert(format!("operationalLimitsGroups{}", suffix), json!([group]));
+    object.insert(
+        format!("selectedOperationalLimitsGroupId{}", suffix),
+        json!(DEFAULT_LIMITS_GROUP),
+    );
+}
+
+fn add_element_name(object: &mut Map<String, Value>, branch: &Branch) {
+    if !branch.element_name.is_empty() {
+        object.insert("elementName".to_string(), json!(branch.element_name));
+    }
+}
+
+// Susceptances and conductances are in µS in UCTE-DEF
+const MICRO: f64 = 1e-6;
+
+fn line_json(branch: &Branch, node1: &Node, node2: &Node) -> Value {
+    let mut line = Map::new();
+    line.insert("id".to_string(), json!(branch.id()));
+    line.insert("r".to_string(), json!(branch.r));
+    line.insert("x".to_string(), json!(branch.x));
+    line.insert("g1".to_string(), json!(0.0));
+    line.insert("b1".to_string(), json!(branch.b * MICRO / 2.0));
+    line.insert("g2".to_string(), json!(0.0));
+    line.insert("b2".to_string(), json!(branch.b * MICRO / 2.0));
+    connect(&mut line, "1", node1, branch);
+    connect(&mut line, "2", node2, branch);
+    add_current_limit(&mut line, "1", branch.current_limit);
+    add_current_limit(&mut line, "2", branch.current_limit);
+    add_element_name(&mut line, branch);
+    Value::Object(line)
+}
+
+fn dangling_line_json(branch: &Branch, node: &Node, x_node: &Node) -> Map<String, Value> {
+    let mut dangling_line = Map::new();
+    dangling_line.insert("id".to_string(), json!(branch.id()));
+    dangling_line.insert("p0".to_string(), json!(0.0));
+    dangling_line.insert("q0".to_string(), json!(0.0));
+    dangling_line.insert("r".to_string(), json!(branch.r));
+    dangling_line.insert("x".to_string(), json!(branch.x));
+    dangling_line.insert("g".to_string(), json!(0.0));
+    dangling_line.insert("b".to_string(), json!(branch.b * MICRO));
+    connect(&mut dangling_line, "", node, branch);
+    dangling_line.insert("pairingKey".to_string(), json!(x_node.code));
+    add_current_limit(&mut dangling_line, "", branch.current_limit);
+    add_element_name(&mut dangling_line, branch);
+    dangling_line
+}
+
+// The regulated winding, node 2 of the UCTE transformer, is side 1 of the transformer and
+// the impedances are given on the side of node 1
+fn transformer_json(transformer: &Transformer, node1: &Node, node2: &Node) -> Map<String, Value> {
+    let branch = &transformer.branch;
+    let mut object = Map::new();
+    object.insert("id".to_string(), json!(branch.id()));
+    object.insert("r".to_string(), json!(branch.r));
+    object.insert("x".to_string(), json!(branch.x));
+    object.insert("g".to_string(), json!(transformer.g * MICRO));
+    object.insert("b".to_string(), json!(branch.b * MICRO));
+    object.insert("ratedU1".to_string(), json!(transformer.rated_u2));
+    object.insert("ratedU2".to_string(), json!(transformer.rated_u1));
+    object.insert("ratedS".to_string(), json!(transformer.nominal_power));
+    connect(&mut object, "1", node2, branch);
+    connect(&mut object, "2", node1, branch);
+    add_current_limit(&mut object, "2", branch.current_limit);
+    add_element_name(&mut object, branch);
+    object
+}
+
+fn add_regulation(transformer: &mut Map<String, Value>, regulation: &Regulation) {
+    let terminal_ref = json!({"id": regulation.id, "side": "ONE"});
+
+    if let Some(phase) = &regulation.phase {
+        let steps: Vec<Value> = (-phase.taps..=phase.taps)
+            .map(|tap| {
+                let rho = 1.0 / (1.0 + f64::from(tap) * phase.du / 100.0);
+                json!({"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": rho})
+            })
+            .collect();
+        let rated_u1 = transformer["ratedU1"].clone();
+        transformer.insert(
+            "ratioTapChanger".to_string(),
+            json!({
+                "regulating": phase.voltage.is_some(),
+                "lowTapPosition": -phase.taps,
+                "tapPosition": phase.tap,
+                "targetDeadband": 0.0,
+                "loadTapChangingCapabilities": true,
+                "regulationMode": "VOLTAGE",
+                "regulationValue": phase.voltage.map_or(rated_u1, Value::from),
+                "terminalRef": terminal_ref,
+                "steps": steps,
+            }),
+        );
+    }
+
+    if let Some(angle) = &regulation.angle {
+        let steps: Vec<Value> = (-angle.taps..=angle.taps)
+            .map(|tap| {
+                let du = f64::from(tap) * angle.du / 100.0;
+                let (rho, alpha) = if angle.symmetrical {
+                    (1.0, 2.0 * (du / 2.0).atan())
+                } else {
+                    let theta = angle.theta.to_radians();
+                    let (dx, dy) = (du * theta.cos(), du * theta.sin());
+                    (1.0 / (1.0 + dx).hypot(dy), dy.atan2(1.0 + dx))
+                };
+                json!({"r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": rho, "alpha": alpha.to_degrees()})
+            })
+            .collect();
+        transformer.insert(
+            "phaseTapChanger".to_string(),
+            json!({
+                "regulating": angle.active_power.is_some(),
+                "lowTapPosition": -angle.taps,
+                "tapPosition": angle.tap,
+                "regulationMode": if angle.active_power.is_some() { "ACTIVE_POWER_CONTROL" } else { "FIXED_TAP" },
+                "regulationValue": angle.active_power.unwrap_or(0.0),
+                "targetDeadband": 0.0,
+                "terminalRef": terminal_ref,
+                "steps": steps,
+            }),
+        );
+    }
+}
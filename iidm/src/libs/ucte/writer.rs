@@ -0,0 +1,779 @@
+//! Writer of UCTE-DEF files
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::Value;
+
+use super::{country_code, is_x_node, nominal_voltage, UcteError};
+use crate::libs::bus_branch::{reactive_bounds, unsupported_equipments};
+use crate::{
+    DanglingLine, EnergySource, Network, OperationalLimitsHolder, PhaseRegulationMode, Side,
+    TopologyKind, TwoWindingsTransformer, UnknownFields, VoltageLevel,
+};
+
+/// UCTE-DEF file written from a network, with the equipments that could not be represented
+pub struct UcteExport {
+    pub ucte: String,
+    pub warnings: Vec<String>,
+}
+
+/// Writes a bus-breaker network as a UCTE-DEF file.
+///
+/// Buses become nodes holding the loads and generators connected to them, lines and two
+/// windings transformers become branches and dangling lines are connected to X-nodes.
+/// Everything else is skipped and reported in the warnings. Values too large for their
+/// UCTE-DEF field are an error, rather than being written truncated.
+pub fn to_ucte(network: &Network) -> Result<UcteExport, UcteError> {
+    let mut writer = Writer::default();
+    writer.nodes(network);
+    writer.lines(network);
+    writer.x_nodes(network);
+    writer.transformers(network);
+    writer.skipped(network);
+    let ucte = writer.write(network);
+    if let Some(overflow) = writer.overflows.into_iter().next() {
+        return Err(overflow);
+    }
+    Ok(UcteExport {
+        ucte,
+        warnings: writer.warnings,
+    })
+}
+
+/// Line of a UCTE-DEF file being written, whose fields are at fixed columns
+struct Record {
+    chars: Vec<char>,
+    // Numbers too large for their field, with its first and last columns
+    overflows: Vec<(f64, usize, usize)>,
+}
+
+impl Record {
+    fn new() -> Self {
+        Self {
+            chars: Vec::new(),
+            overflows: Vec::new(),
+        }
+    }
+
+    // Columns are numbered from 1 and inclusive, as in the UCTE-DEF specification
+    fn text(mut self, start: usize, end: usize, text: &str) -> Self {
+        if self.chars.len() < end {
+            self.chars.resize(end, ' ');
+        }
+        for (column, c) in (start - 1..end).zip(text.chars()) {
+            self.chars[column] = c;
+        }
+        self
+    }
+
+    // Numbers are right aligned, with as many decimals as the field allows
+    fn number(mut self, start: usize, end: usize, value: f64) -> Self {
+        let width = end + 1 - start;
+        let text = format_number(value, width);
+        if text.len() > width {
+            self.overflows.push((value, start, end));
+        }
+        let padded = format!("{:>width$}", text, width = width);
+        self.text(start, end, &padded)
+    }
+
+    fn integer(mut self, start: usize, end: usize, value: i32) -> Self {
+        let width = end + 1 - start;
+        let padded = format!("{:>width$}", value, width = width);
+        if padded.len() > width {
+            self.overflows.push((value.into(), start, end));
+        }
+        self.text(start, end, &padded)
+    }
+
+    fn optional_number(self, start: usize, end: usize, value: Option<f64>) -> Self {
+        match value {
+            Some(value) => self.number(start, end, value),
+            None => self,
+        }
+    }
+
+    // Numbers too large for their field are reported as overflows of the element
+    fn build(self, overflows: &mut Vec<UcteError>, element: &str) -> String {
+        for (value, start, end) in self.overflows {
+            overflows.push(UcteError::Overflow {
+                element: element.to_string(),
+                value,
+                start,
+                end,
+            });
+        }
+        self.chars
+            .into_iter()
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+}
+
+// The shortest representation is returned when the value can't fit in the field
+fn format_number(value: f64, width: usize) -> String {
+    // Negative zeros would be written with their sign
+    let value = value + 0.0;
+    (0..width)
+        .rev()
+        .map(|decimals| format!("{:.*}", decimals, value))
+        .find(|text| text.len() <= width)
+        .unwrap_or_else(|| format!("{:.0}", value))
+}
+
+struct NodeRecord {
+    code: String,
+    name: String,
+    country: String,
+    node_type: u8,
+    voltage: Option<f64>,
+    active_load: f64,
+    reactive_load: f64,
+    active_generation: f64,
+    reactive_generation: f64,
+    minimum_generation: Option<f64>,
+    maximum_generation: Option<f64>,
+    minimum_reactive_generation: Option<f64>,
+    maximum_reactive_generation: Option<f64>,
+    plant_type: Option<char>,
+}
+
+impl NodeRecord {
+    fn new(code: String, name: &str, country: &str) -> Self {
+        Self {
+            code,
+            name: name.to_string(),
+            country: country.to_string(),
+            node_type: 0,
+            voltage: None,
+            active_load: 0.0,
+            reactive_load: 0.0,
+            active_generation: 0.0,
+            reactive_generation: 0.0,
+            minimum_generation: None,
+            maximum_generation: None,
+            minimum_reactive_generation: None,
+            maximum_reactive_generation: None,
+            plant_type: None,
+        }
+    }
+
+    fn build(&self, overflows: &mut Vec<UcteError>) -> String {
+        let mut record = Record::new()
+            .text(1, 8, &self.code)
+            .text(10, 21, &self.name)
+            .text(23, 23, "0")
+            .text(25, 25, &self.node_type.to_string())
+            .optional_number(27, 32, self.voltage)
+            .number(34, 40, self.active_load)
+            .number(42, 48, self.reactive_load)
+            .number(50, 56, self.active_generation)
+            .number(58, 64, self.reactive_generation)
+            .optional_number(66, 72, self.minimum_generation)
+            .optional_number(74, 80, self.maximum_generation)
+            .optional_number(82, 88, self.minimum_reactive_generation)
+            .optional_number(90, 96, self.maximum_reactive_generation);
+        if let Some(plant_type) = self.plant_type {
+            record = record.text(128, 128, &plant_type.to_string());
+        }
+        record.build(overflows, &format!("node {}", self.code))
+    }
+}
+
+/// Line, coupler or transformer, identified by its two nodes and its order code
+struct BranchRecord {
+    node1: String,
+    node2: String,
+    order_code: char,
+    status: u8,
+}
+
+impl BranchRecord {
+    fn start(&self) -> Record {
+        Record::new()
+            .text(1, 8, &self.node1)
+            .text(10, 17, &self.node2)
+            .text(19, 19, &self.order_code.to_string())
+            .text(21, 21, &self.status.to_string())
+    }
+
+    fn id(&self) -> String {
+        format!("{} {} {}", self.node1, self.node2, self.order_code)
+    }
+}
+
+const ORDER_CODES: &str = "123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Susceptances and conductances are in µS in UCTE-DEF
+const MICRO: f64 = 1e-6;
+
+#[derive(Default)]
+struct Writer {
+    warnings: Vec<String>,
+    overflows: Vec<UcteError>,
+    // UCTE node code of each bus
+    codes: BTreeMap<String, String>,
+    nodes: Vec<NodeRecord>,
+    branch_ids: HashSet<(String, String, char)>,
+    lines: Vec<String>,
+    transformers: Vec<String>,
+    regulations: Vec<String>,
+}
+
+impl Writer {
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    fn nodes(&mut self, network: &Network) {
+        for substation in &network.substations {
+            let Some(country_code) = country_code(&substation.country) else {
+                self.warn(format!(
+                    "Country '{}' of substation {} has no UCTE code, it is not exported",
+                    substation.country, substation.id
+                ));
+                continue;
+            };
+            for voltage_level in &substation.voltage_levels {
+                if voltage_level.topology_kind != TopologyKind::BusBreaker {
+                    self.warn(format!(
+                        "Voltage level {} is not bus-breaker, it is not exported",
+                        voltage_level.id
+                    ));
+                    continue;
+                }
+                let voltage_code = self.voltage_code(voltage_level);
+                for bus in voltage_level
+                    .bus_breaker_topology
+                    .iter()
+                    .flat_map(|topology| &topology.buses)
+                {
+                    let Some(code) =
+                        self.node_code(&bus.id, &substation.id, country_code, voltage_code)
+                    else {
+                        self.warn(format!("No UCTE node code is left for bus {}", bus.id));
+                        continue;
+                    };
+                    let name = bus
+                        .unknown_fields
+                        .get("geographicalName")
+                        .and_then(Value::as_str)
+                        .unwrap_or(&voltage_level.id);
+                    let mut node = NodeRecord::new(code.clone(), name, &substation.country);
+                    node.voltage = bus.v;
+                    self.injections(&mut node, &bus.id, voltage_level);
+                    self.codes.insert(bus.id.clone(), code);
+                    self.nodes.push(node);
+                }
+            }
+        }
+    }
+
+    // Voltage levels whose nominal voltage has no UCTE code use the closest one
+    fn voltage_code(&mut self, voltage_level: &VoltageLevel) -> char {
+        let (code, voltage) = ('0'..='9')
+            .filter_map(|code| nominal_voltage(code).map(|voltage| (code, voltage)))
+            .min_by(|(_, a), (_, b)| {
+                (a - voltage_level.nominal_v)
+                    .abs()
+                    .total_cmp(&(b - voltage_level.nominal_v).abs())
+            })
+            .expect("UCTE voltage codes are not empty");
+        if voltage != voltage_level.nominal_v {
+            self.warn(format!(
+                "Nominal voltage {} kV of voltage level {} is exported as {} kV",
+                voltage_level.nominal_v, voltage_level.id, voltage
+            ));
+        }
+        code
+    }
+
+    // Buses keep their id when it is a UCTE node code of their country and voltage level,
+    // the codes of the others are made of the country, the substation and a bus number
+    fn node_code(
+        &self,
+        bus: &str,
+        substation: &str,
+        country_code: char,
+        voltage_code: char,
+    ) -> Option<String> {
+        let is_used = |code: &str| self.codes.values().any(|used| used == code);
+        if bus.chars().count() == 8
+            && bus.starts_with(country_code)
+            && bus.chars().nth(6) == Some(voltage_code)
+            && !is_used(bus)
+        {
+            return Some(bus.to_string());
+        }
+
+        let spot: String = std::iter::once(country_code)
+            .chain(
+                substation
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .map(|c| c.to_ascii_uppercase())
+                    .chain(std::iter::repeat('_'))
+                    .take(5),
+            )
+            .collect();
+        ORDER_CODES
+            .chars()
+            .map(|bus_code| format!("{}{}{}", spot, voltage_code, bus_code))
+            .find(|code| !is_x_node(code) && !is_used(code))
+    }
+
+    fn injections(&mut self, node: &mut NodeRecord, bus: &str, voltage_level: &VoltageLevel) {
+        for load in voltage_level.loads.iter().flatten() {
+            if load.bus == bus {
+                node.active_load += load.p0;
+                node.reactive_load += load.q0;
+            }
+        }
+
+        let generators: Vec<_> = voltage_level
+            .generators
+            .iter()
+            .flatten()
+            .filter(|generator| generator.bus == bus)
+            .collect();
+        if generators.is_empty() {
+            return;
+        }
+        let (mut min_q, mut max_q) = (0.0, 0.0);
+        let (mut min_p, mut max_p) = (0.0, 0.0);
+        for generator in &generators {
+            // Generations are negative in UCTE-DEF
+            node.active_generation -= generator.target_p;
+            node.reactive_generation -= generator.target_q;
+            min_p -= generator.min_p;
+            max_p -= generator.max_p;
+            if generator.voltage_regulator_on {
+                node.node_type = 2;
+                node.voltage = Some(generator.target_v);
+            }
+            if let Some((min, max)) = reactive_bounds(generator, &mut self.warnings) {
+                min_q -= min;
+                max_q -= max;
+            }
+        }
+        node.minimum_generation = Some(min_p);
+        node.maximum_generation = Some(max_p);
+        node.minimum_reactive_generation = Some(min_q);
+        node.maximum_reactive_generation = Some(max_q);
+        node.plant_type = Some(match generators[0].energy_source {
+            EnergySource::Hydro => 'H',
+            EnergySource::Nuclear => 'N',
+            EnergySource::Wind => 'W',
+            EnergySource::Thermal => 'C',
+            EnergySource::Solar | EnergySource::Other => 'F',
+        });
+    }
+
+    // Node of a terminal and whether it is connected to it
+    fn terminal(&self, bus: &str, connectable_bus: &str) -> Option<(String, bool)> {
+        let connected = !bus.is_empty();
+        let bus = if connected { bus } else { connectable_bus };
+        self.codes.get(bus).map(|code| (code.clone(), connected))
+    }
+
+    // Branches keep the order code of their id when it follows the UCTE convention
+    fn branch(
+        &mut self,
+        id: &str,
+        node1: String,
+        node2: String,
+        connected: bool,
+    ) -> Option<BranchRecord> {
+        let preferred = id
+            .strip_prefix(&format!("{} {} ", node1, node2))
+            .and_then(|order| order.chars().next());
+        let order_code = preferred
+            .into_iter()
+            .chain(ORDER_CODES.chars())
+            .find(|order| {
+                !self
+                    .branch_ids
+                    .contains(&(node1.clone(), node2.clone(), *order))
+            })?;
+        self.branch_ids
+            .insert((node1.clone(), node2.clone(), order_code));
+        Some(BranchRecord {
+            node1,
+            node2,
+            order_code,
+            status: if connected { 0 } else { 8 },
+        })
+    }
+
+    fn lines(&mut self, network: &Network) {
+        for substation in &network.substations {
+            for voltage_level in &substation.voltage_levels {
+                let switches = voltage_level
+                    .bus_breaker_topology
+                    .iter()
+                    .flat_map(|topology| &topology.switches);
+                for switch in switches {
+                    let (Some((node1, _)), Some((node2, _))) = (
+                        self.terminal(&switch.bus1, ""),
+                        self.terminal(&switch.bus2, ""),
+                    ) else {
+                        continue;
+                    };
+                    let Some(mut branch) = self.branch(&switch.id, node1, node2, true) else {
+                        self.warn(format!(
+                            "No UCTE order code is left for switch {}",
+                            switch.id
+                        ));
+                        continue;
+                    };
+                    // Busbar couplers have the status 2, or 7 when open
+                    branch.status = if switch.open { 7 } else { 2 };
+                    let name = element_name(&switch.id, &switch.unknown_fields, &branch);
+                    let record = branch
+                        .start()
+                        .number(23, 28, 0.0)
+                        .number(30, 35, 0.0)
+                        .number(37, 44, 0.0)
+                        .text(53, 64, name)
+                        .build(&mut self.overflows, &format!("switch {}", switch.id));
+                    self.lines.push(record);
+                }
+            }
+        }
+
+        for line in &network.lines {
+            let terminals = (
+                self.terminal(&line.bus1, &line.connectable_bus1),
+                self.terminal(&line.bus2, &line.connectable_bus2),
+            );
+            let (Some((node1, connected1)), Some((node2, connected2))) = terminals else {
+                self.warn(format!(
+                    "Line {} is not connected to exported buses",
+                    line.id
+                ));
+                continue;
+            };
+            let Some(branch) = self.branch(&line.id, node1, node2, connected1 && connected2) else {
+                self.warn(format!("No UCTE order code is left for line {}", line.id));
+                continue;
+            };
+            if line.g1 != 0.0 || line.g2 != 0.0 {
+                self.warn(format!("Conductances of line {} are not exported", line.id));
+            }
+            let current_limit = [Side::One, Side::Two]
+                .into_iter()
+                .filter_map(|side| permanent_limit(line, side))
+                .chain(
+                    line.current_limits1
+                        .iter()
+                        .map(|limits| limits.permanent_limit),
+                )
+                .chain(
+                    line.current_limits2
+                        .iter()
+                        .map(|limits| limits.permanent_limit),
+                )
+                .reduce(f64::min);
+            let name = element_name(&line.id, &line.unknown_fields, &branch);
+            let record = branch
+                .start()
+                .number(23, 28, line.r)
+                .number(30, 35, line.x)
+                .number(37, 44, (line.b1 + line.b2) / MICRO)
+                .optional_number(46, 51, current_limit)
+                .text(53, 64, name)
+                .build(&mut self.overflows, &format!("line {}", line.id));
+            self.lines.push(record);
+        }
+    }
+
+    fn x_nodes(&mut self, network: &Network) {
+        for dangling_line in &network.dangling_lines {
+            let x_node = self.x_node(&dangling_line.id, pairing_key(dangling_line));
+            let mut node = NodeRecord::new(x_node.clone(), "", "XX");
+            node.active_load = dangling_line.p0;
+            node.reactive_load = dangling_line.q0;
+            self.nodes.push(node);
+            self.x_node_line(dangling_line, x_node);
+        }
+
+        for tie_line in &network.tie_lines {
+            let pairing_key = Some(tie_line.ucte_xnode_code.as_str()).filter(|key| !key.is_empty());
+            let x_node = self.x_node(&tie_line.id, pairing_key);
+            let mut node = NodeRecord::new(x_node.clone(), "", "XX");
+            for half in [&tie_line.dangling_line1, &tie_line.dangling_line2] {
+                node.active_load += half.p0;
+                node.reactive_load += half.q0;
+            }
+            self.nodes.push(node);
+            self.x_node_line(&tie_line.dangling_line1, x_node.clone());
+            self.x_node_line(&tie_line.dangling_line2, x_node);
+        }
+    }
+
+    // X-nodes keep their pairing key when it is a UCTE X-node code
+    fn x_node(&mut self, id: &str, pairing_key: Option<&str>) -> String {
+        let is_used = |code: &str| self.nodes.iter().any(|node| node.code == code);
+        if let Some(key) =
+            pairing_key.filter(|key| key.chars().count() == 8 && is_x_node(key) && !is_used(key))
+        {
+            return key.to_string();
+        }
+        let code = (1..)
+            .map(|number| format!("X{:07}", number))
+            .find(|code| !is_used(code))
+            .expect("X-node codes are not exhausted");
+        self.warn(format!("Boundary of {} is exported as X-node {}", id, code));
+        code
+    }
+
+    fn x_node_line(&mut self, dangling_line: &DanglingLine, x_node: String) {
+        let Some((node, connected)) =
+            self.terminal(&dangling_line.bus, &dangling_line.connectable_bus)
+        else {
+            self.warn(format!(
+                "Dangling line {} is not connected to an exported bus",
+                dangling_line.id
+            ));
+            return;
+        };
+        let Some(branch) = self.branch(&dangling_line.id, node, x_node, connected) else {
+            self.warn(format!(
+                "No UCTE order code is left for dangling line {}",
+                dangling_line.id
+            ));
+            return;
+        };
+        if dangling_line.g != 0.0 {
+            self.warn(format!(
+                "Conductance of dangling line {} is not exported",
+                dangling_line.id
+            ));
+        }
+        let name = element_name(&dangling_line.id, &dangling_line.unknown_fields, &branch);
+        let record = branch
+            .start()
+            .number(23, 28, dangling_line.r)
+            .number(30, 35, dangling_line.x)
+            .number(37, 44, dangling_line.b / MICRO)
+            .optional_number(46, 51, permanent_limit(dangling_line, Side::One))
+            .text(53, 64, name)
+            .build(
+                &mut self.overflows,
+                &format!("dangling line {}", dangling_line.id),
+            );
+        self.lines.push(record);
+    }
+
+    // The regulated winding, side 1 of the transformer, is node 2 of UCTE transformers
+    fn transformers(&mut self, network: &Network) {
+        for transformer in network
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.two_windings_transformers)
+        {
+            let terminals = (
+                self.terminal(&transformer.bus2, &transformer.connectable_bus2),
+                self.terminal(&transformer.bus1, &transformer.connectable_bus1),
+            );
+            let (Some((node1, connected1)), Some((node2, connected2))) = terminals else {
+                self.warn(format!(
+                    "Transformer {} is not connected to exported buses",
+                    transformer.id
+                ));
+                continue;
+            };
+            let Some(branch) = self.branch(&transformer.id, node1, node2, connected1 && connected2)
+            else {
+                self.warn(format!(
+                    "No UCTE order code is left for transformer {}",
+                    transformer.id
+                ));
+                continue;
+            };
+            let rated_s = transformer
+                .unknown_fields
+                .get("ratedS")
+                .and_then(Value::as_f64);
+            if rated_s.is_none() {
+                self.warn(format!(
+                    "Rated power of transformer {} is unknown",
+                    transformer.id
+                ));
+            }
+            let name = element_name(&transformer.id, &transformer.unknown_fields, &branch);
+            let record = branch
+                .start()
+                .number(23, 27, transformer.rated_u2)
+                .number(29, 33, transformer.rated_u1)
+                .number(35, 39, rated_s.unwrap_or_default())
+                .number(41, 46, transformer.r)
+                .number(48, 53, transformer.x)
+                .number(55, 62, transformer.b / MICRO)
+                .number(64, 69, transformer.g / MICRO)
+                .optional_number(71, 76, permanent_limit(transformer, Side::Two))
+                .text(78, 89, name)
+                .build(
+                    &mut self.overflows,
+                    &format!("transformer {}", transformer.id),
+                );
+            self.transformers.push(record);
+            self.regulation(transformer, &branch);
+        }
+    }
+
+    // Tap changers are written as the regulation laws of UCTE-DEF, from their last step
+    fn regulation(&mut self, transformer: &TwoWindingsTransformer, branch: &BranchRecord) {
+        let ratio = transformer.ratio_tap_changer.as_ref();
+        let phase = transformer.phase_tap_changer.as_ref();
+        if ratio.is_none() && phase.is_none() {
+            return;
+        }
+        let mut record = Record::new()
+            .text(1, 8, &branch.node1)
+            .text(10, 17, &branch.node2)
+            .text(19, 19, &branch.order_code.to_string());
+
+        if let Some(tap_changer) = ratio {
+            let rhos: Vec<f64> = tap_changer.steps.iter().map(|step| step.rho).collect();
+            let taps = self.taps(&transformer.id, tap_changer.low_tap_position, rhos.len());
+            let du = (1.0 / rhos.last().copied().unwrap_or(1.0) - 1.0) * 100.0 / taps.max(1) as f64;
+            record = record
+                .number(21, 25, du)
+                .integer(27, 28, taps)
+                .integer(30, 32, tap_changer.tap_position)
+                .optional_number(
+                    34,
+                    38,
+                    Some(tap_changer.regulation_value).filter(|_| tap_changer.regulating),
+                );
+        }
+
+        if let Some(tap_changer) = phase {
+            let taps = self.taps(
+                &transformer.id,
+                tap_changer.low_tap_position,
+                tap_changer.steps.len(),
+            );
+            let last = tap_changer.steps.last();
+            let rho = last.map_or(1.0, |step| step.rho);
+            let alpha = last.map_or(0.0, |step| step.alpha.to_radians());
+            let symmetrical = tap_changer
+                .steps
+                .iter()
+                .all(|step| (step.rho - 1.0).abs() < 1e-9);
+            let (du, theta, kind) = if symmetrical {
+                ((alpha / 2.0).tan() * 200.0, 90.0, "SYMM")
+            } else {
+                let (dx, dy) = (alpha.cos() / rho - 1.0, alpha.sin() / rho);
+                (dx.hypot(dy) * 100.0, dy.atan2(dx).to_degrees(), "ASYM")
+            };
+            let active_power = matches!(
+                tap_changer.regulation_mode,
+                PhaseRegulationMode::ActivePowerControl
+            )
+            .then_some(tap_changer.regulation_value);
+            record = record
+                .number(40, 44, du / taps.max(1) as f64)
+                .number(46, 48, theta)
+                .integer(50, 51, taps)
+                .integer(53, 55, tap_changer.tap_position)
+                .optional_number(57, 61, active_power)
+                .text(63, 66, kind);
+        }
+        let element = format!("regulation of transformer {}", transformer.id);
+        let record = record.build(&mut self.overflows, &element);
+        self.regulations.push(record);
+    }
+
+    // UCTE regulations have as many taps on both sides of the neutral tap
+    fn taps(&mut self, id: &str, low_tap_position: i32, steps: usize) -> i32 {
+        let taps = (steps as i32 - 1) / 2;
+        if low_tap_position != -taps || steps.is_multiple_of(2) {
+            self.warn(format!(
+                "Taps of transformer {} are not centered on the neutral tap",
+                id
+            ));
+        }
+        taps
+    }
+
+    fn skipped(&mut self, network: &Network) {
+        let shunts = network
+            .shunt_compensators
+            .iter()
+            .map(|shunt| format!("Shunt compensator {}", shunt.id));
+        let warnings: Vec<String> = unsupported_equipments(network)
+            .chain(shunts)
+            .map(|equipment| {
+                format!(
+                    "{} has no UCTE-DEF equivalent, it is not exported",
+                    equipment
+                )
+            })
+            .collect();
+        self.warnings.extend(warnings);
+    }
+
+    fn write(&mut self, network: &Network) -> String {
+        let mut ucte = vec![
+            format!("##C {}", network.case_date.format("%Y.%m.%d")),
+            network.id.clone(),
+            "##N".to_string(),
+        ];
+        // Zones are written in the order of the substations, X-nodes being the last ones
+        let mut countries: Vec<&str> = Vec::new();
+        for node in &self.nodes {
+            if !countries.contains(&node.country.as_str()) {
+                countries.push(&node.country);
+            }
+        }
+        for country in countries {
+            ucte.push(format!("##Z{}", country));
+            ucte.extend(
+                self.nodes
+                    .iter()
+                    .filter(|node| node.country == country)
+                    .map(|node| node.build(&mut self.overflows)),
+            );
+        }
+        ucte.push("##L".to_string());
+        ucte.append(&mut self.lines);
+        ucte.push("##T".to_string());
+        ucte.append(&mut self.transformers);
+        ucte.push("##R".to_string());
+        ucte.append(&mut self.regulations);
+
+        let mut ucte = ucte.join("\n");
+        ucte.push('\n');
+        ucte
+    }
+}
+
+fn permanent_limit(holder: &impl OperationalLimitsHolder, side: Side) -> Option<f64> {
+    holder
+        .selected_operational_limits_group(side)
+        .and_then(|group| group.current_limits.as_ref())
+        .map(|limits| limits.permanent_limit)
+}
+
+fn pairing_key(dangling_line: &DanglingLine) -> Option<&str> {
+    dangling_line
+        .unknown_fields
+        .get("pairingKey")
+        .and_then(Value::as_str)
+}
+
+// Equipments without element name are named after their id, unless it is their UCTE id
+fn element_name<'a>(
+    id: &'a str,
+    unknown_fields: &'a UnknownFields,
+    branch: &BranchRecord,
+) -> &'a str {
+    match unknown_fields.get("elementName").and_then(Value::as_str) {
+        Some(name) => name,
+        None if id == branch.id() => "",
+        None => id,
+    }
+}
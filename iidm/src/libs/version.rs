@@ -0,0 +1,498 @@
+//! IIDM versions and the migrations between them.
+//!
+//! The network model follows the latest IIDM version. Documents of older versions are
+//! upgraded to it when read, and networks are downgraded when written for an older
+//! version, converting or dropping what that version can't express.
+
+use std::{fmt, str::FromStr};
+
+use serde_json::{Map, Value};
+
+use super::error::IidmError;
+
+/// Version of the IIDM format, e.g. `1.12`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IidmVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl IidmVersion {
+    pub const OLDEST: Self = Self::new(1, 0);
+    pub const LATEST: Self = Self::new(1, 12);
+
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        (Self::OLDEST..=Self::LATEST).contains(self)
+    }
+}
+
+impl fmt::Display for IidmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for IidmVersion {
+    type Err = IidmError;
+
+    /// Parses a supported version written `major.minor`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_once('.')
+            .and_then(|(major, minor)| Some(Self::new(major.parse().ok()?, minor.parse().ok()?)))
+            .filter(IidmVersion::is_supported)
+            .ok_or_else(|| IidmError::UnsupportedVersion(s.to_string()))
+    }
+}
+
+/// Batteries exist since IIDM 1.1
+const BATTERIES: IidmVersion = IidmVersion::new(1, 1);
+/// Three windings transformers have a rated voltage at their star bus since IIDM 1.1
+const RATED_U0: IidmVersion = IidmVersion::new(1, 1);
+/// Transformers have a rated apparent power since IIDM 1.2
+const RATED_S: IidmVersion = IidmVersion::new(1, 2);
+/// Shunt compensators regulate voltage since IIDM 1.2
+const SHUNT_REGULATION: IidmVersion = IidmVersion::new(1, 2);
+/// Shunt compensators have a section model since IIDM 1.3
+const SHUNT_MODELS: IidmVersion = IidmVersion::new(1, 3);
+/// Networks have a minimum validation level since IIDM 1.7
+const VALIDATION_LEVEL: IidmVersion = IidmVersion::new(1, 7);
+/// Tie lines pair two dangling lines since IIDM 1.10
+const PAIRED_TIE_LINES: IidmVersion = IidmVersion::new(1, 10);
+/// Loads have an exponential or ZIP model since IIDM 1.10
+const LOAD_MODELS: IidmVersion = IidmVersion::new(1, 10);
+/// Networks have areas since IIDM 1.11
+const AREAS: IidmVersion = IidmVersion::new(1, 11);
+/// Limits are held by operational limits groups since IIDM 1.12
+const LIMITS_GROUPS: IidmVersion = IidmVersion::new(1, 12);
+
+/// Id of the group holding the limits of documents older than IIDM 1.12
+pub const DEFAULT_LIMITS_GROUP: &str = "DEFAULT";
+
+const LIMITS: [&str; 3] = ["currentLimits", "activePowerLimits", "apparentPowerLimits"];
+
+/// Checks the version of a network in the IIDM JSON layout and migrates it to the latest
+/// version, the version of the document is kept. Additions of later versions are optional
+/// in the model, only the attributes whose layout changed are migrated.
+///
+/// Returns the version of the document.
+pub fn upgrade(network: &mut Value) -> Result<IidmVersion, IidmError> {
+    let object = network
+        .as_object_mut()
+        .ok_or_else(|| IidmError::Malformed("IIDM network must be an object".to_string()))?;
+    let version: IidmVersion = object
+        .get("version")
+        .and_then(Value::as_str)
+        .ok_or(IidmError::MissingVersion)?
+        .parse()?;
+
+    if version < SHUNT_MODELS {
+        for shunt in items(object, "shuntCompensators") {
+            upgrade_shunt(shunt);
+        }
+    }
+    if version < VALIDATION_LEVEL {
+        // Older documents are always fully valid
+        object
+            .entry("minimumValidationLevel")
+            .or_insert_with(|| Value::String("STEADY_STATE_HYPOTHESIS".to_string()));
+    }
+    if version < PAIRED_TIE_LINES {
+        for tie_line in items(object, "tieLines") {
+            upgrade_tie_line(tie_line);
+        }
+    }
+    if version < LIMITS_GROUPS {
+        for (holder, suffix) in limits_holders(object) {
+            upgrade_limits(holder, suffix);
+        }
+    }
+
+    Ok(version)
+}
+
+/// Migrates a network in the IIDM JSON layout of the latest version to an older version,
+/// the equipments and attributes added by later versions being dropped
+pub fn downgrade(network: &mut Value, target: IidmVersion) {
+    let Some(object) = network.as_object_mut() else {
+        return;
+    };
+    object.insert("version".to_string(), Value::String(target.to_string()));
+
+    if target < LIMITS_GROUPS {
+        for (holder, suffix) in limits_holders(object) {
+            downgrade_limits(holder, suffix);
+        }
+    }
+    if target < AREAS {
+        object.remove("areas");
+    }
+    if target < PAIRED_TIE_LINES {
+        for tie_line in items(object, "tieLines") {
+            downgrade_tie_line(tie_line);
+        }
+    }
+    if target < LOAD_MODELS {
+        for load in voltage_levels(object).flat_map(|voltage_level| items(voltage_level, "loads")) {
+            load.remove("exponentialModel");
+            load.remove("zipModel");
+        }
+    }
+    if target < VALIDATION_LEVEL {
+        object.remove("minimumValidationLevel");
+    }
+    if target < SHUNT_MODELS {
+        for shunt in items(object, "shuntCompensators") {
+            downgrade_shunt(shunt);
+        }
+    }
+    if target < SHUNT_REGULATION {
+        for shunt in items(object, "shuntCompensators") {
+            for key in [
+                "regulatingTerminal",
+                "voltageRegulatorOn",
+                "targetV",
+                "targetDeadband",
+            ] {
+                shunt.remove(key);
+            }
+        }
+    }
+    if target < RATED_S {
+        for transformer in items(object, "substations")
+            .flat_map(|substation| items(substation, "twoWindingsTransformers"))
+        {
+            transformer.remove("ratedS");
+        }
+        for transformer in items(object, "threeWindingsTransformers") {
+            for key in ["ratedS1", "ratedS2", "ratedS3"] {
+                transformer.remove(key);
+            }
+        }
+    }
+    if target < RATED_U0 {
+        for transformer in items(object, "threeWindingsTransformers") {
+            transformer.remove("ratedU0");
+        }
+    }
+    if target < BATTERIES {
+        for voltage_level in voltage_levels(object) {
+            voltage_level.remove("batteries");
+        }
+    }
+}
+
+fn voltage_levels(
+    network: &mut Map<String, Value>,
+) -> impl Iterator<Item = &mut Map<String, Value>> {
+    items(network, "substations").flat_map(|substation| items(substation, "voltageLevels"))
+}
+
+fn items<'a>(
+    object: &'a mut Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = &'a mut Map<String, Value>> {
+    object
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+}
+
+// Attributes of XML documents are only typed when they are modelled, legacy attributes
+// may be strings
+fn number(value: Option<Value>) -> Option<f64> {
+    match value? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(raw) => raw.parse().ok(),
+        _ => None,
+    }
+}
+
+fn integer(value: Option<Value>) -> Option<i64> {
+    match value? {
+        Value::Number(number) => number.as_i64(),
+        Value::String(raw) => raw.parse().ok(),
+        _ => None,
+    }
+}
+
+fn insert_number(object: &mut Map<String, Value>, key: &str, value: Option<f64>) {
+    if let Some(value) = value.and_then(serde_json::Number::from_f64) {
+        object.insert(key.to_string(), Value::Number(value));
+    }
+}
+
+fn upgrade_shunt(shunt: &mut Map<String, Value>) {
+    if !shunt.contains_key("bPerSection") {
+        return;
+    }
+    let mut model = Map::new();
+    insert_number(
+        &mut model,
+        "bPerSection",
+        number(shunt.remove("bPerSection")),
+    );
+    let maximum = integer(shunt.remove("maximumSectionCount")).unwrap_or(0);
+    model.insert("maximumSectionCount".to_string(), Value::from(maximum));
+    shunt.insert("shuntLinearModel".to_string(), Value::Object(model));
+
+    let current = integer(shunt.remove("currentSectionCount")).unwrap_or(0);
+    shunt.insert("sectionCount".to_string(), Value::from(current));
+}
+
+fn downgrade_shunt(shunt: &mut Map<String, Value>) {
+    let (b_per_section, maximum) = if let Some(model) = shunt.remove("shuntLinearModel") {
+        let b_per_section = number(model.get("bPerSection").cloned());
+        (
+            b_per_section,
+            integer(model.get("maximumSectionCount").cloned()),
+        )
+    } else if let Some(model) = shunt.remove("shuntNonLinearModel") {
+        // Non-linear sections are approximated by equal sections reaching the same maximum
+        let sections = model
+            .get("sections")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let maximum = sections.len() as i64;
+        let b = number(
+            sections
+                .last()
+                .and_then(|section| section.get("b"))
+                .cloned(),
+        );
+        (b.map(|b| b / maximum as f64), Some(maximum))
+    } else {
+        return;
+    };
+
+    insert_number(shunt, "bPerSection", b_per_section);
+    shunt.insert(
+        "maximumSectionCount".to_string(),
+        Value::from(maximum.unwrap_or(0)),
+    );
+    if let Some(current) = shunt.remove("sectionCount") {
+        shunt.insert("currentSectionCount".to_string(), current);
+    }
+}
+
+// Before IIDM 1.10 a tie line carries the attributes of its two halves, suffixed by `_1`
+// and `_2`, and the connections of its two sides
+fn upgrade_tie_line(tie_line: &mut Map<String, Value>) {
+    if tie_line.contains_key("danglingLine1") || !tie_line.contains_key("id_1") {
+        return;
+    }
+    let pairing_key = tie_line
+        .get("ucteXnodeCode")
+        .filter(|code| code.as_str().is_some_and(|code| !code.is_empty()))
+        .cloned();
+
+    for side in ["1", "2"] {
+        let half = |key: &str| format!("{}_{}", key, side);
+        let mut dangling_line = Map::new();
+        if let Some(id) = tie_line.remove(&half("id")) {
+            dangling_line.insert("id".to_string(), id);
+        }
+        if let Some(name) = tie_line.remove(&half("name")) {
+            dangling_line.insert("name".to_string(), name);
+        }
+        dangling_line.insert("p0".to_string(), Value::from(0.0));
+        dangling_line.insert("q0".to_string(), Value::from(0.0));
+        insert_number(&mut dangling_line, "r", number(tie_line.remove(&half("r"))));
+        insert_number(&mut dangling_line, "x", number(tie_line.remove(&half("x"))));
+        for (key, first, second) in [("g", "g1", "g2"), ("b", "b1", "b2")] {
+            let first = number(tie_line.remove(&half(first))).unwrap_or(0.0);
+            let second = number(tie_line.remove(&half(second))).unwrap_or(0.0);
+            insert_number(&mut dangling_line, key, Some(first + second));
+        }
+        tie_line.remove(&half("xnodeP"));
+        tie_line.remove(&half("xnodeQ"));
+
+        // The connection of the side becomes the connection of the half
+        for key in ["voltageLevelId", "bus", "connectableBus", "node", "p", "q"]
+            .into_iter()
+            .chain(LIMITS)
+        {
+            if let Some(value) = tie_line.remove(&format!("{}{}", key, side)) {
+                let value = match key {
+                    "node" => integer(Some(value)).map(Value::from),
+                    "p" | "q" => number(Some(value)).map(Value::from),
+                    _ => Some(value),
+                };
+                if let Some(value) = value {
+                    dangling_line.insert(key.to_string(), value);
+                }
+            }
+        }
+        if let Some(pairing_key) = &pairing_key {
+            dangling_line.insert("pairingKey".to_string(), pairing_key.clone());
+        }
+
+        tie_line.insert(
+            format!("danglingLine{}", side),
+            Value::Object(dangling_line),
+        );
+    }
+}
+
+fn downgrade_tie_line(tie_line: &mut Map<String, Value>) {
+    for side in ["1", "2"] {
+        let Some(Value::Object(mut dangling_line)) =
+            tie_line.remove(&format!("danglingLine{}", side))
+        else {
+            continue;
+        };
+        let half = |key: &str| format!("{}_{}", key, side);
+
+        for key in ["id", "name", "r", "x"] {
+            if let Some(value) = dangling_line.remove(key) {
+                tie_line.insert(half(key), value);
+            }
+        }
+        // The shunt admittance of the half is split between its two ends
+        for (key, first, second) in [("g", "g1", "g2"), ("b", "b1", "b2")] {
+            let value = number(dangling_line.remove(key)).unwrap_or(0.0) / 2.0;
+            insert_number(tie_line, &half(first), Some(value));
+            insert_number(tie_line, &half(second), Some(value));
+        }
+        for key in ["voltageLevelId", "bus", "connectableBus", "node", "p", "q"]
+            .into_iter()
+            .chain(LIMITS)
+        {
+            if let Some(value) = dangling_line.remove(key) {
+                tie_line.insert(format!("{}{}", key, side), value);
+            }
+        }
+    }
+}
+
+// Equipments holding limits with the suffix of each of their sides
+fn limits_holders(
+    network: &mut Map<String, Value>,
+) -> Vec<(&mut Map<String, Value>, &'static str)> {
+    let mut holders = Vec::new();
+    for (key, value) in network.iter_mut() {
+        let Some(items) = value.as_array_mut() else {
+            continue;
+        };
+        let items = items.iter_mut().filter_map(Value::as_object_mut);
+        match key.as_str() {
+            "substations" => {
+                for substation in items {
+                    let transformers = substation
+                        .get_mut("twoWindingsTransformers")
+                        .and_then(Value::as_array_mut)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_object_mut);
+                    holders.extend(transformers.map(|t| (t, "1|2")));
+                }
+            }
+            "lines" => holders.extend(items.map(|line| (line, "1|2"))),
+            "threeWindingsTransformers" => holders.extend(items.map(|t| (t, "1|2|3"))),
+            "danglingLines" => holders.extend(items.map(|dangling_line| (dangling_line, ""))),
+            "tieLines" => {
+                for tie_line in items {
+                    for (side, dangling_line) in tie_line.iter_mut() {
+                        if side.starts_with("danglingLine") {
+                            if let Some(dangling_line) = dangling_line.as_object_mut() {
+                                holders.push((dangling_line, ""));
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    holders
+}
+
+fn upgrade_limits(holder: &mut Map<String, Value>, sides: &str) {
+    for suffix in sides.split('|') {
+        let groups = format!("operationalLimitsGroups{}", suffix);
+        if holder
+            .get(&groups)
+            .and_then(Value::as_array)
+            .is_some_and(|groups| !groups.is_empty())
+        {
+            continue;
+        }
+
+        let mut group = Map::new();
+        for kind in LIMITS {
+            match holder.remove(&format!("{}{}", kind, suffix)) {
+                Some(Value::Null) | None => {}
+                Some(limits) => {
+                    group.insert(kind.to_string(), limits);
+                }
+            }
+        }
+        if group.is_empty() {
+            continue;
+        }
+        group.insert(
+            "id".to_string(),
+            Value::String(DEFAULT_LIMITS_GROUP.to_string()),
+        );
+        holder.insert(groups, Value::Array(vec![Value::Object(group)]));
+        holder.insert(
+            format!("selectedOperationalLimitsGroupId{}", suffix),
+            Value::String(DEFAULT_LIMITS_GROUP.to_string()),
+        );
+    }
+}
+
+// Only the selected group can be expressed, the other groups are dropped
+fn downgrade_limits(holder: &mut Map<String, Value>, sides: &str) {
+    for suffix in sides.split('|') {
+        let groups = holder.remove(&format!("operationalLimitsGroups{}", suffix));
+        let selected = holder.remove(&format!("selectedOperationalLimitsGroupId{}", suffix));
+        let (Some(Value::Array(groups)), Some(selected)) = (groups, selected) else {
+            continue;
+        };
+        let Some(Value::Object(mut group)) = groups
+            .into_iter()
+            .find(|group| group.get("id") == Some(&selected))
+        else {
+            continue;
+        };
+        for kind in LIMITS {
+            if let Some(limits) = group.remove(kind) {
+                holder.insert(format!("{}{}", kind, suffix), limits);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!("1.0".parse::<IidmVersion>().ok(), Some(IidmVersion::OLDEST));
+        assert_eq!(
+            "1.12".parse::<IidmVersion>().ok(),
+            Some(IidmVersion::LATEST)
+        );
+        assert!(IidmVersion::new(1, 9) < IidmVersion::new(1, 10));
+        assert!(matches!(
+            "1.13".parse::<IidmVersion>(),
+            Err(IidmError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            "2.0".parse::<IidmVersion>(),
+            Err(IidmError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            "latest".parse::<IidmVersion>(),
+            Err(IidmError::UnsupportedVersion(_))
+        ));
+    }
+}
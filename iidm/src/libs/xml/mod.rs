@@ -0,0 +1,309 @@
+//! IIDM XML format (`.xiidm`).
+//!
+//! XML documents are converted from and to the IIDM JSON layout of [`Network`], XML
+//! attributes being typed from the JSON schema of the entities, and unmodelled ones from
+//! their value.
+//!
+//! [`Network`]: crate::Network
+
+use schemars::{schema_for, JsonSchema};
+use serde_json::{Number, Value};
+
+use super::error::IidmError;
+use super::version::IidmVersion;
+use crate::{
+    ActivePowerControl, BusbarSectionPosition, EntsoeArea, Extension, GeneratorShortCircuit,
+    HvdcAngleDroopActivePowerControl, SubstationPosition,
+};
+
+mod reader;
+mod writer;
+
+pub use reader::{from_xml, xml_to_json};
+pub use writer::to_xml;
+
+const ITESLA_NAMESPACE_PREFIX: &str = "http://www.itesla_project.eu/schema/iidm/";
+const POWSYBL_NAMESPACE_PREFIX: &str = "http://www.powsybl.org/schema/iidm/";
+
+/// Returns the IIDM version of an IIDM XML namespace, e.g. `1.12` for `.../iidm/1_12`
+pub fn namespace_version(namespace: &str) -> Result<IidmVersion, IidmError> {
+    namespace
+        .strip_prefix(POWSYBL_NAMESPACE_PREFIX)
+        .or_else(|| namespace.strip_prefix(ITESLA_NAMESPACE_PREFIX))
+        .filter(|version| !version.contains('/'))
+        .ok_or_else(|| IidmError::UnsupportedVersion(format!("namespace {}", namespace)))?
+        .replace('_', ".")
+        .parse()
+}
+
+/// Returns the IIDM XML namespace of an IIDM version, e.g. `.../iidm/1_12` for `1.12`
+pub fn version_namespace(version: IidmVersion) -> String {
+    // Namespaces moved to the powsybl domain with IIDM 1.6
+    let prefix = if version < IidmVersion::new(1, 6) {
+        ITESLA_NAMESPACE_PREFIX
+    } else {
+        POWSYBL_NAMESPACE_PREFIX
+    };
+    format!("{}{}_{}", prefix, version.major, version.minor)
+}
+
+/// JSON schema of an entity with its definitions, used to type the XML attributes
+#[derive(Default)]
+struct SchemaTree {
+    root: Value,
+}
+
+impl SchemaTree {
+    fn of<T: JsonSchema>() -> Self {
+        let root = serde_json::to_value(schema_for!(T)).unwrap_or_default();
+        Self { root }
+    }
+
+    fn root(&self) -> Option<&Value> {
+        Some(&self.root)
+    }
+
+    fn definition(&self, name: &str) -> Option<&Value> {
+        self.root.get("definitions")?.get(name)
+    }
+
+    // Follows references and skips the null alternative of optional values
+    fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        {
+            return self.definition(name).map_or(schema, |s| self.resolve(s));
+        }
+        if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+            if let Some(alternative) = alternatives
+                .iter()
+                .find(|alternative| alternative.get("type") != Some(&Value::from("null")))
+            {
+                return self.resolve(alternative);
+            }
+        }
+        schema
+    }
+
+    // Looks for a property in the object and in its flattened alternatives
+    fn property<'a>(&'a self, object: &'a Value, key: &str) -> Option<&'a Value> {
+        let object = self.resolve(object);
+        if let Some(property) = object.get("properties").and_then(|p| p.get(key)) {
+            return Some(property);
+        }
+        ["oneOf", "anyOf", "allOf"]
+            .iter()
+            .filter_map(|keyword| object.get(keyword).and_then(Value::as_array))
+            .flatten()
+            .find_map(|alternative| self.property(alternative, key))
+    }
+
+    fn types<'a>(&'a self, schema: &'a Value) -> Vec<&'a str> {
+        match self.resolve(schema).get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn is_array(&self, schema: &Value) -> bool {
+        self.types(schema).contains(&"array")
+    }
+
+    fn items<'a>(&'a self, schema: &'a Value) -> Option<&'a Value> {
+        self.resolve(schema).get("items")
+    }
+
+    fn required<'a>(&'a self, object: &'a Value) -> impl Iterator<Item = &'a str> {
+        self.resolve(object)
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+    }
+
+    fn attribute(&self, schema: Option<&Value>, raw: &str) -> Value {
+        let Some(schema) = schema else {
+            return unmodelled_attribute(raw);
+        };
+        let types = self.types(schema);
+        let typed = if types.contains(&"boolean") {
+            raw.parse().ok().map(Value::Bool)
+        } else if types.contains(&"integer") {
+            raw.parse::<i64>().ok().map(Value::from)
+        } else if types.contains(&"number") {
+            raw.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+        } else if types.contains(&"array") {
+            let items = self.items(schema);
+            Some(Value::Array(
+                raw.split(',')
+                    .filter(|item| !item.is_empty())
+                    .map(|item| self.attribute(items, item.trim()))
+                    .collect(),
+            ))
+        } else {
+            None
+        };
+        typed.unwrap_or_else(|| Value::String(raw.to_string()))
+    }
+}
+
+// Attributes missing from the schema are typed from their value, numbers and booleans being
+// kept as such only when written back the same, e.g. `405.0` but not `007` or `1E3`
+fn unmodelled_attribute(raw: &str) -> Value {
+    let typed = match raw {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        _ => raw.parse::<i64>().ok().map(Value::from).or_else(|| {
+            raw.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+        }),
+    };
+    typed
+        .filter(|value| serde_json::to_string(value).is_ok_and(|written| written == raw))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+// `temporaryLimit` -> `temporaryLimits`, `operationalLimitsGroup1` -> `operationalLimitsGroups1`
+fn plural(name: &str) -> String {
+    let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let suffix = &name[stem.len()..];
+    let plural = if let Some(stem) = stem.strip_suffix('y') {
+        format!("{}ies", stem)
+    } else if stem.ends_with('s') || stem.ends_with("ch") {
+        format!("{}es", stem)
+    } else {
+        format!("{}s", stem)
+    };
+    plural + suffix
+}
+
+// Inverse of `plural`: `temporaryLimits` -> `temporaryLimit`, `buses` -> `bus`
+fn singular(key: &str) -> String {
+    let stem = key.trim_end_matches(|c: char| c.is_ascii_digit());
+    let suffix = &key[stem.len()..];
+    let singular = if let Some(stem) = stem.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if stem.ends_with("ses") || stem.ends_with("ches") {
+        stem[..stem.len() - 2].to_string()
+    } else {
+        stem.strip_suffix('s').unwrap_or(stem).to_string()
+    };
+    singular + suffix
+}
+
+/// Namespace of an extension written in IIDM XML, extensions are read whatever their
+/// namespace
+fn extension_namespace(name: &str) -> String {
+    let mut snake_case = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake_case.push('_');
+        }
+        snake_case.push(c.to_ascii_lowercase());
+    }
+    format!("{}ext/{}/1_0", POWSYBL_NAMESPACE_PREFIX, snake_case)
+}
+
+fn extension_schema(name: &str) -> Option<SchemaTree> {
+    let tree = match name {
+        n if n == SubstationPosition::NAME => SchemaTree::of::<SubstationPosition>(),
+        n if n == BusbarSectionPosition::NAME => SchemaTree::of::<BusbarSectionPosition>(),
+        n if n == ActivePowerControl::NAME => SchemaTree::of::<ActivePowerControl>(),
+        n if n == GeneratorShortCircuit::NAME => SchemaTree::of::<GeneratorShortCircuit>(),
+        n if n == HvdcAngleDroopActivePowerControl::NAME => {
+            SchemaTree::of::<HvdcAngleDroopActivePowerControl>()
+        }
+        n if n == EntsoeArea::NAME => SchemaTree::of::<EntsoeArea>(),
+        _ => return None,
+    };
+    Some(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_version() {
+        assert_eq!(
+            namespace_version("http://www.itesla_project.eu/schema/iidm/1_0").ok(),
+            Some(IidmVersion::new(1, 0))
+        );
+        assert_eq!(
+            namespace_version("http://www.powsybl.org/schema/iidm/1_12").ok(),
+            Some(IidmVersion::new(1, 12))
+        );
+        assert!(matches!(
+            namespace_version("http://www.powsybl.org/schema/iidm/1_13"),
+            Err(IidmError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            namespace_version("http://www.powsybl.org/schema/iidm/ext/entsoe_area/1_0"),
+            Err(IidmError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_unmodelled_attribute() {
+        assert_eq!(unmodelled_attribute("405.0"), Value::from(405.0));
+        assert_eq!(unmodelled_attribute("2"), Value::from(2));
+        assert_eq!(unmodelled_attribute("-1.5"), Value::from(-1.5));
+        assert_eq!(unmodelled_attribute("true"), Value::Bool(true));
+        // Values which would not be written back the same stay strings
+        for raw in ["007", "1.0E-5", "NaN", "True", "FR", ""] {
+            assert_eq!(unmodelled_attribute(raw), Value::String(raw.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(plural("temporaryLimit"), "temporaryLimits");
+        assert_eq!(plural("bus"), "buses");
+        assert_eq!(plural("switch"), "switches");
+        assert_eq!(plural("battery"), "batteries");
+        assert_eq!(
+            plural("operationalLimitsGroup1"),
+            "operationalLimitsGroups1"
+        );
+    }
+
+    #[test]
+    fn test_singular_inverts_plural() {
+        for name in [
+            "temporaryLimit",
+            "bus",
+            "switch",
+            "battery",
+            "line",
+            "alias",
+            "operationalLimitsGroup1",
+        ] {
+            assert_eq!(singular(&plural(name)), name);
+        }
+    }
+
+    #[test]
+    fn test_version_namespace() {
+        assert_eq!(
+            version_namespace(IidmVersion::new(1, 5)),
+            "http://www.itesla_project.eu/schema/iidm/1_5"
+        );
+        assert_eq!(
+            version_namespace(IidmVersion::LATEST),
+            "http://www.powsybl.org/schema/iidm/1_12"
+        );
+        assert_eq!(
+            extension_namespace("entsoeArea"),
+            "http://www.powsybl.org/schema/iidm/ext/entsoe_area/1_0"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+//! Reader of IIDM XML documents
+
+use std::collections::HashMap;
+
+use roxmltree::{Document, Node};
+use serde_json::{Map, Value};
+
+use super::{extension_schema, namespace_version, plural, SchemaTree};
+use crate::libs::error::IidmError;
+use crate::libs::version::upgrade;
+use crate::Network;
+
+/// Reads a network from an IIDM XML document
+pub fn from_xml(xml: &str) -> Result<Network, IidmError> {
+    let value = xml_to_json(xml)?;
+    let mut network: Network = serde_json::from_value(value)?;
+    // Nodes of node-breaker topologies only exist through their connections in XML
+    network.declare_referenced_nodes();
+    Ok(network)
+}
+
+/// Converts an IIDM XML document to the IIDM JSON layout of [`Network`], upgraded to the
+/// latest IIDM version
+pub fn xml_to_json(xml: &str) -> Result<Value, IidmError> {
+    let document = Document::parse(xml)
+        .map_err(|e| IidmError::Malformed(format!("Invalid IIDM XML: {}", e)))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "network" {
+        return Err(IidmError::Malformed(format!(
+            "IIDM XML root element must be a network, found {}",
+            root.tag_name().name()
+        )));
+    }
+
+    let namespace = root.tag_name().namespace().unwrap_or_default();
+    let version = namespace_version(namespace)?;
+
+    let schema = SchemaTree::of::<Network>();
+    let mut reader = Reader::new(namespace);
+    let mut network = reader.element(root, schema.root(), &schema);
+
+    let object = network.as_object_mut().expect("network is an object");
+    object.insert("version".to_string(), Value::String(version.to_string()));
+    reader.relocate(object);
+
+    upgrade(&mut network)?;
+    Ok(network)
+}
+
+/// Where an IIDM XML element is stored in the JSON layout of [`Network`]
+enum Target {
+    /// Array of the network, IIDM XML nests these equipments in substations or voltage levels
+    Network(&'static str, &'static str),
+    /// Busbar sections are written in the node-breaker topology
+    VoltageLevel(&'static str, &'static str),
+    /// Converter stations are embedded in their HVDC line
+    ConverterStation,
+    /// References to other entities become a list of ids
+    Ids(&'static str),
+}
+
+fn target(element: &str) -> Option<Target> {
+    match element {
+        "shunt" => Some(Target::Network("shuntCompensators", "ShuntCompensator")),
+        "staticVarCompensator" => Some(Target::Network(
+            "staticVarCompensators",
+            "StaticVarCompensator",
+        )),
+        "danglingLine" => Some(Target::Network("danglingLines", "DanglingLine")),
+        "threeWindingsTransformer" => Some(Target::Network(
+            "threeWindingsTransformers",
+            "ThreeWindingsTransformer",
+        )),
+        "busbarSection" => Some(Target::VoltageLevel("busbar_sections", "BusbarSection")),
+        "vscConverterStation" | "lccConverterStation" => Some(Target::ConverterStation),
+        "voltageLevelRef" => Some(Target::Ids("voltageLevelIds")),
+        _ => None,
+    }
+}
+
+struct Reader<'a> {
+    namespace: &'a str,
+    voltage_level: Option<String>,
+    network_items: HashMap<&'static str, Vec<Value>>,
+    voltage_level_items: Vec<(&'static str, Value)>,
+    converter_stations: HashMap<String, Value>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(namespace: &'a str) -> Self {
+        Self {
+            namespace,
+            voltage_level: None,
+            network_items: HashMap::new(),
+            voltage_level_items: Vec::new(),
+            converter_stations: HashMap::new(),
+        }
+    }
+
+    fn element(&mut self, node: Node, schema: Option<&Value>, tree: &SchemaTree) -> Value {
+        let name = node.tag_name().name();
+        let mut object = Map::new();
+
+        for attribute in node.attributes().filter(|a| a.namespace().is_none()) {
+            let property = schema.and_then(|s| tree.property(s, attribute.name()));
+            object.insert(
+                attribute.name().to_string(),
+                tree.attribute(property, attribute.value()),
+            );
+        }
+
+        // Equipments of a voltage level may need its id, and their busbar sections are
+        // collected while reading its topology
+        let is_voltage_level = name == "voltageLevel";
+        let parent_voltage_level = self.voltage_level.clone();
+        let mut parent_items = Vec::new();
+        if is_voltage_level {
+            self.voltage_level = object.get("id").and_then(Value::as_str).map(str::to_string);
+            parent_items = std::mem::take(&mut self.voltage_level_items);
+        }
+
+        for child in node.children().filter(Node::is_element) {
+            self.child(&mut object, child, schema, tree);
+        }
+
+        if is_voltage_level {
+            let items = std::mem::replace(&mut self.voltage_level_items, parent_items);
+            for (key, item) in items {
+                push(&mut object, key, item);
+            }
+        }
+
+        if let Some(schema) = schema {
+            for required in tree.required(schema) {
+                if object.contains_key(required) {
+                    continue;
+                }
+                let property = tree.property(schema, required);
+                let default = match required {
+                    "name" => object.get("id").cloned(),
+                    "voltageLevelId" => self.voltage_level.clone().map(Value::String),
+                    _ if property.is_some_and(|p| tree.is_array(p)) => Some(Value::Array(vec![])),
+                    _ => None,
+                };
+                if let Some(default) = default {
+                    object.insert(required.to_string(), default);
+                }
+            }
+        }
+
+        self.voltage_level = parent_voltage_level;
+        Value::Object(object)
+    }
+
+    fn child(
+        &mut self,
+        object: &mut Map<String, Value>,
+        child: Node,
+        schema: Option<&Value>,
+        tree: &SchemaTree,
+    ) {
+        let name = child.tag_name().name();
+        let in_iidm = child.tag_name().namespace() == Some(self.namespace);
+
+        if in_iidm && name == "extension" {
+            let holder = self.extension(child);
+            push(object, "extensions", holder);
+            return;
+        }
+
+        if in_iidm {
+            match target(name) {
+                Some(Target::Network(key, definition)) => {
+                    let mut item = self.element(child, tree.definition(definition), tree);
+                    // The voltage level of the equipment is implicit in XML, it is kept to
+                    // write the equipment back in it
+                    if let (Some(item), Some(voltage_level)) =
+                        (item.as_object_mut(), &self.voltage_level)
+                    {
+                        item.entry("voltageLevelId")
+                            .or_insert_with(|| Value::String(voltage_level.clone()));
+                    }
+                    self.network_items.entry(key).or_default().push(item);
+                    return;
+                }
+                Some(Target::VoltageLevel(key, definition)) => {
+                    let item = self.element(child, tree.definition(definition), tree);
+                    self.voltage_level_items.push((key, item));
+                    return;
+                }
+                Some(Target::ConverterStation) => {
+                    let item = self.element(child, tree.definition("HvdcConverterStation"), tree);
+                    if let Some(id) = item.get("id").and_then(Value::as_str) {
+                        self.converter_stations.insert(id.to_string(), item);
+                    }
+                    return;
+                }
+                Some(Target::Ids(key)) => {
+                    if let Some(id) = child.attribute("id") {
+                        push(object, key, Value::String(id.to_string()));
+                    }
+                    return;
+                }
+                None => {}
+            }
+        }
+
+        // Single elements keep their name, repeated ones are stored in a plural array
+        let plural = plural(name);
+        let property = schema.and_then(|schema| {
+            [name, plural.as_str()].into_iter().find_map(|key| {
+                tree.property(schema, key)
+                    .map(|property| (key.to_string(), property))
+            })
+        });
+
+        match property {
+            Some((key, property)) if tree.is_array(property) => {
+                let item = self.element(child, tree.items(property), tree);
+                push(object, &key, item);
+            }
+            Some((key, property)) => {
+                let item = self.element(child, Some(property), tree);
+                object.insert(key, item);
+            }
+            // Unmodelled elements are kept as unknown fields, limits of equipments older
+            // than IIDM 1.12 being typed for their migration to limits groups
+            None => {
+                let definition = legacy_limits(name).and_then(|name| tree.definition(name));
+                let item = self.element(child, definition, tree);
+                match object.get_mut(name) {
+                    Some(Value::Array(items)) => items.push(item),
+                    Some(existing) => {
+                        let first = existing.take();
+                        *existing = Value::Array(vec![first, item]);
+                    }
+                    None => {
+                        object.insert(name.to_string(), item);
+                    }
+                }
+            }
+        }
+    }
+
+    fn extension(&mut self, node: Node) -> Value {
+        let mut extensions = Map::new();
+        for child in node.children().filter(Node::is_element) {
+            let name = child.tag_name().name();
+            // Attributes of extensions without typed model are typed from their value
+            let value = match extension_schema(name) {
+                Some(tree) => self.element(child, tree.root(), &tree),
+                None => self.element(child, None, &SchemaTree::default()),
+            };
+            extensions.insert(name.to_string(), value);
+        }
+
+        let mut holder = Map::new();
+        holder.insert(
+            "id".to_string(),
+            Value::String(node.attribute("id").unwrap_or_default().to_string()),
+        );
+        holder.insert("extensions".to_string(), Value::Object(extensions));
+        Value::Object(holder)
+    }
+
+    // Moves the equipments read inside substations and voltage levels to the network
+    fn relocate(&mut self, network: &mut Map<String, Value>) {
+        for (key, items) in self.network_items.drain() {
+            for item in items {
+                push(network, key, item);
+            }
+        }
+
+        if let Some(Value::Array(hvdc_lines)) = network.get_mut("hvdcLines") {
+            for hvdc_line in hvdc_lines.iter_mut().filter_map(Value::as_object_mut) {
+                for key in ["converterStation1", "converterStation2"] {
+                    let station = hvdc_line
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .and_then(|id| self.converter_stations.remove(id));
+                    if let Some(station) = station {
+                        hvdc_line.insert(key.to_string(), station);
+                    }
+                }
+            }
+        }
+
+        // Since IIDM 1.10 tie lines reference two dangling lines of the network
+        let tie_lines = network
+            .get_mut("tieLines")
+            .and_then(Value::as_array_mut)
+            .map(std::mem::take)
+            .unwrap_or_default();
+        let mut resolved = Vec::with_capacity(tie_lines.len());
+        for mut tie_line in tie_lines {
+            if let Some(object) = tie_line.as_object_mut() {
+                for (reference, key) in [
+                    ("danglingLineId1", "danglingLine1"),
+                    ("danglingLineId2", "danglingLine2"),
+                ] {
+                    let Some(id) = object.get(reference).and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if let Some(dangling_line) = take_by_id(network, "danglingLines", id) {
+                        object.remove(reference);
+                        object.insert(key.to_string(), dangling_line);
+                    }
+                }
+                if !object.contains_key("ucteXnodeCode") {
+                    let pairing_key = object
+                        .get("danglingLine1")
+                        .and_then(|line| line.get("pairingKey"))
+                        .cloned()
+                        .unwrap_or_else(|| Value::String(String::new()));
+                    object.insert("ucteXnodeCode".to_string(), pairing_key);
+                }
+            }
+            resolved.push(tie_line);
+        }
+        if !resolved.is_empty() {
+            network.insert("tieLines".to_string(), Value::Array(resolved));
+        }
+    }
+}
+
+fn legacy_limits(element: &str) -> Option<&'static str> {
+    match element.trim_end_matches(|c: char| c.is_ascii_digit()) {
+        "currentLimits" => Some("CurrentLimits"),
+        "activePowerLimits" => Some("ActivePowerLimits"),
+        "apparentPowerLimits" => Some("ApparentPowerLimits"),
+        _ => None,
+    }
+}
+
+fn push(object: &mut Map<String, Value>, key: &str, item: Value) {
+    match object
+        .entry(key)
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => items.push(item),
+        // A single value read before is kept as the first item
+        other => *other = Value::Array(vec![other.take(), item]),
+    }
+}
+
+fn take_by_id(network: &mut Map<String, Value>, key: &str, id: &str) -> Option<Value> {
+    let items = network.get_mut(key)?.as_array_mut()?;
+    let position = items
+        .iter()
+        .position(|item| item.get("id").and_then(Value::as_str) == Some(id))?;
+    Some(items.remove(position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keeps_single_value() {
+        let mut object = Map::new();
+        push(&mut object, "items", Value::from(1));
+        push(&mut object, "items", Value::from(2));
+        assert_eq!(object["items"], serde_json::json!([1, 2]));
+
+        object.insert("item".to_string(), Value::from("first"));
+        push(&mut object, "item", Value::from("second"));
+        assert_eq!(object["item"], serde_json::json!(["first", "second"]));
+    }
+}
@@ -0,0 +1,383 @@
+//! Writer of IIDM XML documents
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{Map, Value};
+
+use super::{extension_namespace, extension_schema, singular, version_namespace, SchemaTree};
+use crate::libs::{error::IidmError, json::to_json, version::IidmVersion};
+use crate::Network;
+
+const IIDM_PREFIX: &str = "iidm";
+
+/// Writes a network as an IIDM XML document of the given IIDM version
+pub fn to_xml(network: &Network, version: IidmVersion) -> Result<String, IidmError> {
+    let mut value = to_json(network, version)?;
+    let object = value.as_object_mut().expect("network is an object");
+    // The version is given by the namespace
+    object.remove("version");
+
+    let schema = SchemaTree::of::<Network>();
+    let mut writer = Writer::default();
+    let mut root = writer.element(IIDM_PREFIX, "network", object, schema.root(), &schema);
+    relocate(&mut root, &network.referenced_nodes());
+
+    let namespace = version_namespace(version);
+    let namespaces = std::iter::once((IIDM_PREFIX.to_string(), namespace))
+        .chain(writer.extension_namespaces)
+        .map(|(prefix, namespace)| (format!("xmlns:{}", prefix), namespace));
+    root.attributes.splice(0..0, namespaces);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    root.write(&mut xml, 0);
+    Ok(xml)
+}
+
+/// XML element built from the JSON layout of [`Network`]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(prefix: &str, name: &str) -> Self {
+        Self {
+            name: format!("{}:{}", prefix, name),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn local_name(&self) -> &str {
+        self.name
+            .split_once(':')
+            .map_or(self.name.as_str(), |(_, name)| name)
+    }
+
+    fn rename(&mut self, name: &str) {
+        self.name = format!("{}:{}", IIDM_PREFIX, name);
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn remove_attribute(&mut self, name: &str) -> Option<String> {
+        let position = self.attributes.iter().position(|(key, _)| key == name)?;
+        Some(self.attributes.remove(position).1)
+    }
+
+    fn take_children(&mut self, name: &str) -> Vec<Element> {
+        let (taken, kept) = std::mem::take(&mut self.children)
+            .into_iter()
+            .partition(|child| child.local_name() == name);
+        self.children = kept;
+        taken
+    }
+
+    fn children_mut<'a>(&'a mut self, name: &'a str) -> impl Iterator<Item = &'a mut Element> {
+        self.children
+            .iter_mut()
+            .filter(move |child| child.local_name() == name)
+    }
+
+    // Orders the children as the sequences of the IIDM XML schema
+    fn sort_children(&mut self, order: &[&str]) {
+        self.children.sort_by_key(|child| match child.local_name() {
+            "extension" => usize::MAX,
+            name => order
+                .iter()
+                .position(|ordered| *ordered == name)
+                .unwrap_or(order.len()),
+        });
+    }
+
+    fn write(&self, xml: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        xml.push_str(&indent);
+        xml.push('<');
+        xml.push_str(&self.name);
+        for (name, value) in &self.attributes {
+            xml.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+        }
+        if self.children.is_empty() {
+            xml.push_str("/>\n");
+            return;
+        }
+        xml.push_str(">\n");
+        for child in &self.children {
+            child.write(xml, depth + 1);
+        }
+        xml.push_str(&format!("{}</{}>\n", indent, self.name));
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    extension_namespaces: BTreeMap<String, String>,
+}
+
+impl Writer {
+    fn element(
+        &mut self,
+        prefix: &str,
+        name: &str,
+        object: &Map<String, Value>,
+        schema: Option<&Value>,
+        tree: &SchemaTree,
+    ) -> Element {
+        let mut element = Element::new(prefix, name);
+
+        for (key, value) in object {
+            match value {
+                Value::Null => {}
+                Value::Object(child) => {
+                    let property = schema.and_then(|s| tree.property(s, key));
+                    let child = self.element(prefix, key, child, property, tree);
+                    element.children.push(child);
+                }
+                Value::Array(holders) if key == "extensions" && prefix == IIDM_PREFIX => {
+                    for holder in holders.iter().filter_map(Value::as_object) {
+                        let extension = self.extension(holder);
+                        element.children.push(extension);
+                    }
+                }
+                Value::Array(ids) if key == "voltageLevelIds" => {
+                    for id in ids.iter().filter_map(Value::as_str) {
+                        let mut reference = Element::new(prefix, "voltageLevelRef");
+                        reference
+                            .attributes
+                            .push(("id".to_string(), id.to_string()));
+                        element.children.push(reference);
+                    }
+                }
+                Value::Array(items) if items.iter().all(|item| !item.is_object()) => {
+                    if !items.is_empty() {
+                        let joined: Vec<String> = items.iter().map(scalar).collect();
+                        element.attributes.push((key.clone(), joined.join(",")));
+                    }
+                }
+                Value::Array(items) => {
+                    // Repeated elements are stored in a plural array, unmodelled ones under
+                    // their own name
+                    let property = schema.and_then(|s| tree.property(s, key));
+                    let (child_name, item_schema) = match property {
+                        Some(property) if tree.is_array(property) => {
+                            (element_name(key), tree.items(property))
+                        }
+                        _ => (key.clone(), None),
+                    };
+                    for item in items.iter().filter_map(Value::as_object) {
+                        let child = self.element(prefix, &child_name, item, item_schema, tree);
+                        element.children.push(child);
+                    }
+                }
+                value => element.attributes.push((key.clone(), scalar(value))),
+            }
+        }
+
+        // Terminal references come first in the IIDM XML schema
+        element.sort_children(&["terminalRef", "regulatingTerminal"]);
+        element
+    }
+
+    fn extension(&mut self, holder: &Map<String, Value>) -> Element {
+        let mut element = Element::new(IIDM_PREFIX, "extension");
+        if let Some(id) = holder.get("id").and_then(Value::as_str) {
+            element.attributes.push(("id".to_string(), id.to_string()));
+        }
+
+        let extensions = holder.get("extensions").and_then(Value::as_object);
+        for (name, extension) in extensions.into_iter().flatten() {
+            let Some(extension) = extension.as_object() else {
+                continue;
+            };
+            // Each extension is written in its own namespace, prefixed by its name
+            self.extension_namespaces
+                .entry(name.clone())
+                .or_insert_with(|| extension_namespace(name));
+            let tree = extension_schema(name).unwrap_or_default();
+            let child = self.element(name, name, extension, tree.root(), &tree);
+            element.children.push(child);
+        }
+        element
+    }
+}
+
+// Name of the elements stored in an array of the JSON layout
+fn element_name(key: &str) -> String {
+    match key {
+        "shuntCompensators" => "shunt".to_string(),
+        "busbar_sections" => "busbarSection".to_string(),
+        key => singular(key),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+// Line breaks and tabs are written as character references, attribute values being
+// normalized to spaces on read otherwise
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+        .replace('\t', "&#9;")
+}
+
+// Moves the equipments stored at the network level of the JSON layout to the substations
+// and voltage levels holding them in IIDM XML
+fn relocate(network: &mut Element, referenced_nodes: &HashMap<String, HashSet<i32>>) {
+    let mut injections = network.take_children("shunt");
+    injections.extend(network.take_children("staticVarCompensator"));
+    injections.extend(network.take_children("danglingLine"));
+
+    for hvdc_line in network.children_mut("hvdcLine") {
+        for key in ["converterStation1", "converterStation2"] {
+            for mut station in hvdc_line.take_children(key) {
+                let kind = if station.attribute("powerFactor").is_some() {
+                    "lccConverterStation"
+                } else {
+                    "vscConverterStation"
+                };
+                station.rename(kind);
+                let id = station.attribute("id").unwrap_or_default().to_string();
+                hvdc_line.attributes.push((key.to_string(), id));
+                injections.push(station);
+            }
+        }
+    }
+
+    // Since IIDM 1.10 tie lines reference two dangling lines of the network
+    for tie_line in network.children_mut("tieLine") {
+        for (key, reference) in [
+            ("danglingLine1", "danglingLineId1"),
+            ("danglingLine2", "danglingLineId2"),
+        ] {
+            for mut dangling_line in tie_line.take_children(key) {
+                dangling_line.rename("danglingLine");
+                let id = dangling_line
+                    .attribute("id")
+                    .unwrap_or_default()
+                    .to_string();
+                tie_line.attributes.push((reference.to_string(), id));
+                injections.push(dangling_line);
+            }
+        }
+    }
+
+    for mut injection in injections {
+        let voltage_level = injection.attribute("voltageLevelId").map(str::to_string);
+        match voltage_level.and_then(|id| find_voltage_level(network, &id)) {
+            Some(voltage_level) => {
+                injection.remove_attribute("voltageLevelId");
+                voltage_level.children.push(injection);
+            }
+            None => network.children.push(injection),
+        }
+    }
+
+    for transformer in network.take_children("threeWindingsTransformer") {
+        let voltage_level = transformer.attribute("voltageLevelId1").unwrap_or_default();
+        let substation = network.children_mut("substation").find(|substation| {
+            substation
+                .children
+                .iter()
+                .any(|child| is_voltage_level(child, voltage_level))
+        });
+        match substation {
+            Some(substation) => substation.children.push(transformer),
+            None => network.children.push(transformer),
+        }
+    }
+
+    for substation in network.children_mut("substation") {
+        for voltage_level in substation.children_mut("voltageLevel") {
+            write_topology(voltage_level, referenced_nodes);
+            voltage_level.sort_children(&[
+                "nodeBreakerTopology",
+                "busBreakerTopology",
+                "generator",
+                "battery",
+                "load",
+                "shunt",
+                "staticVarCompensator",
+                "vscConverterStation",
+                "lccConverterStation",
+                "danglingLine",
+            ]);
+        }
+        substation.sort_children(&[
+            "voltageLevel",
+            "twoWindingsTransformer",
+            "threeWindingsTransformer",
+        ]);
+    }
+    network.sort_children(&["substation", "line", "tieLine", "hvdcLine"]);
+}
+
+fn is_voltage_level(element: &Element, id: &str) -> bool {
+    element.local_name() == "voltageLevel" && element.attribute("id") == Some(id)
+}
+
+fn find_voltage_level<'a>(network: &'a mut Element, id: &str) -> Option<&'a mut Element> {
+    network
+        .children_mut("substation")
+        .flat_map(|substation| substation.children.iter_mut())
+        .find(|child| is_voltage_level(child, id))
+}
+
+// Busbar sections are written in the node-breaker topology, whose nodes only exist
+// through their connections: nodes are only written when unreferenced or carrying
+// attributes of their own
+fn write_topology(voltage_level: &mut Element, referenced_nodes: &HashMap<String, HashSet<i32>>) {
+    let id = voltage_level
+        .attribute("id")
+        .unwrap_or_default()
+        .to_string();
+    let referenced = referenced_nodes.get(&id);
+    let mut busbar_sections = voltage_level.take_children("busbarSection");
+    if !busbar_sections.is_empty() && voltage_level.children_mut("nodeBreakerTopology").count() == 0
+    {
+        let topology = Element::new(IIDM_PREFIX, "nodeBreakerTopology");
+        voltage_level.children.push(topology);
+    }
+
+    for child in &mut voltage_level.children {
+        match child.local_name() {
+            "nodeBreakerTopology" => {
+                child.children.retain(|node| {
+                    node.local_name() != "node"
+                        || node.attributes.len() > 1
+                        || !node
+                            .attribute("id")
+                            .and_then(|id| id.parse().ok())
+                            .is_some_and(|id| referenced.is_some_and(|nodes| nodes.contains(&id)))
+                });
+                child
+                    .children
+                    .splice(0..0, std::mem::take(&mut busbar_sections));
+            }
+            "busBreakerTopology" => {}
+            _ => continue,
+        }
+        // The voltage level of the topology elements is implicit
+        for element in &mut child.children {
+            if element.attribute("voltageLevelId") == Some(id.as_str()) {
+                element.remove_attribute("voltageLevelId");
+            }
+        }
+    }
+}
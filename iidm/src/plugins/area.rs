@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy_ecs::{prelude::*, world::EntityRef};
+
+use crate::{
+    Area, AreaBoundaryType, AssetRegistry, DanglingLine, HvdcConverterStation, HvdcLine, Line,
+    Side, ThreeWindingsTransformer, TieLine, TwoWindingsTransformer,
+};
+
+/// Computes the net interchange of every registered area, positive when the area exports.
+///
+/// The interchange is the active power flowing out of the area through its boundaries.
+/// Areas without declared boundaries fall back on the lines, transformers, tie lines and HVDC
+/// lines connecting one of their voltage levels to a voltage level outside the area, and on
+/// the dangling lines of their voltage levels, which lead outside the network. Terminals
+/// without flow are ignored.
+pub fn compute_area_interchanges(world: &mut World) -> HashMap<String, f64> {
+    let areas: Vec<Area> = world.query::<&Area>().iter(world).cloned().collect();
+
+    areas
+        .into_iter()
+        .map(|area| {
+            let interchange = if area.area_boundaries.is_empty() {
+                branches_interchange(world, &area)
+            } else {
+                boundaries_interchange(world, &area)
+            };
+            (area.id, interchange)
+        })
+        .collect()
+}
+
+fn boundaries_interchange(world: &World, area: &Area) -> f64 {
+    let Some(registry) = world.get_resource::<AssetRegistry>() else {
+        return 0.0;
+    };
+
+    area.area_boundaries
+        .iter()
+        .filter_map(|boundary| {
+            let entity = world.get_entity(registry.find(&boundary.id)?).ok()?;
+            match boundary.boundary_type {
+                // The boundary side flow is approximated by the network side flow
+                AreaBoundaryType::Boundary => entity.get::<DanglingLine>()?.p,
+                AreaBoundaryType::TerminalRef => {
+                    terminal_p(entity, boundary.side.unwrap_or(Side::One))
+                }
+            }
+        })
+        .sum()
+}
+
+fn terminal_p(entity: EntityRef, side: Side) -> Option<f64> {
+    if let Some(line) = entity.get::<Line>() {
+        return match side {
+            Side::One => line.p1,
+            Side::Two => line.p2,
+            Side::Three => None,
+        };
+    }
+    if let Some(transformer) = entity.get::<TwoWindingsTransformer>() {
+        return match side {
+            Side::One => transformer.p1,
+            Side::Two => transformer.p2,
+            Side::Three => None,
+        };
+    }
+    if let Some(transformer) = entity.get::<ThreeWindingsTransformer>() {
+        return transformer.leg(side).p;
+    }
+    if let Some(station) = entity.get::<HvdcConverterStation>() {
+        return station.p;
+    }
+    entity
+        .get::<DanglingLine>()
+        .and_then(|dangling_line| dangling_line.p)
+}
+
+fn branches_interchange(world: &mut World, area: &Area) -> f64 {
+    let voltage_levels: HashSet<&str> = area.voltage_level_ids.iter().map(String::as_str).collect();
+    let inside = |voltage_level_id: &str| voltage_levels.contains(voltage_level_id);
+
+    let mut interchange = 0.0;
+
+    let mut two_sides =
+        |vl1: &str, p1: Option<f64>, vl2: &str, p2: Option<f64>| match (inside(vl1), inside(vl2)) {
+            (true, false) => interchange += p1.unwrap_or(0.0),
+            (false, true) => interchange += p2.unwrap_or(0.0),
+            _ => {}
+        };
+
+    for line in world.query::<&Line>().iter(world) {
+        two_sides(
+            &line.voltage_level_id1,
+            line.p1,
+            &line.voltage_level_id2,
+            line.p2,
+        );
+    }
+    for transformer in world.query::<&TwoWindingsTransformer>().iter(world) {
+        two_sides(
+            &transformer.voltage_level_id1,
+            transformer.p1,
+            &transformer.voltage_level_id2,
+            transformer.p2,
+        );
+    }
+
+    // The halves of tie lines are registered as dangling lines too, they are accounted for
+    // by their tie line
+    let mut halves = HashSet::new();
+    for tie_line in world.query::<&TieLine>().iter(world) {
+        let (half1, half2) = (&tie_line.dangling_line1, &tie_line.dangling_line2);
+        two_sides(
+            &half1.voltage_level_id,
+            half1.p,
+            &half2.voltage_level_id,
+            half2.p,
+        );
+        halves.extend([half1.id.clone(), half2.id.clone()]);
+    }
+    for hvdc_line in world.query::<&HvdcLine>().iter(world) {
+        let (station1, station2) = (&hvdc_line.converter_station1, &hvdc_line.converter_station2);
+        two_sides(
+            &station1.voltage_level_id,
+            station1.p,
+            &station2.voltage_level_id,
+            station2.p,
+        );
+    }
+
+    for dangling_line in world.query::<&DanglingLine>().iter(world) {
+        if !halves.contains(&dangling_line.id) && inside(&dangling_line.voltage_level_id) {
+            interchange += dangling_line.p.unwrap_or(0.0);
+        }
+    }
+
+    for transformer in world.query::<&ThreeWindingsTransformer>().iter(world) {
+        let legs = transformer.legs();
+        let crosses_border = legs.iter().any(|(_, leg)| inside(&leg.voltage_level_id))
+            && legs.iter().any(|(_, leg)| !inside(&leg.voltage_level_id));
+        if crosses_border {
+            interchange += legs
+                .iter()
+                .filter(|(_, leg)| inside(&leg.voltage_level_id))
+                .filter_map(|(_, leg)| leg.p)
+                .sum::<f64>();
+        }
+    }
+
+    interchange
+}
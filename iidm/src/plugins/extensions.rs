@@ -0,0 +1,95 @@
+use bevy_ecs::prelude::*;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::{AssetRegistry, Extension, ExtensionHolder, Identifiable, UnknownExtensions};
+
+#[derive(Event)]
+pub struct RegisterExtensionEvent<T: Component> {
+    pub id: String,
+    pub extension: T,
+}
+
+pub fn handle_register_extension_events<T: Component + Clone>(
+    mut register_events: EventReader<RegisterExtensionEvent<T>>,
+    mut commands: Commands,
+    mut registery: ResMut<AssetRegistry>,
+) {
+    for RegisterExtensionEvent { id, extension } in register_events.read() {
+        registery.add_component(&mut commands, id, extension.clone());
+    }
+}
+
+type RegisterExtensionFn = fn(&mut World, &str, &Value) -> Result<(), serde_json::Error>;
+
+/// Registry of the typed extensions, by IIDM extension name
+#[derive(Resource, Default)]
+pub struct ExtensionRegistry {
+    extensions: HashMap<&'static str, RegisterExtensionFn>,
+}
+
+impl ExtensionRegistry {
+    /// Registers a typed extension, its `Events<RegisterExtensionEvent<T>>` must be initialized
+    pub fn register<T: Extension>(&mut self) {
+        self.extensions
+            .insert(T::NAME, send_register_extension_event::<T>);
+    }
+
+    /// Checks if an extension name has a typed model
+    pub fn contains(&self, name: &str) -> bool {
+        self.extensions.contains_key(name)
+    }
+
+    fn get(&self, name: &str) -> Option<RegisterExtensionFn> {
+        self.extensions.get(name).copied()
+    }
+}
+
+fn send_register_extension_event<T: Extension>(
+    world: &mut World,
+    id: &str,
+    value: &Value,
+) -> Result<(), serde_json::Error> {
+    let extension: T = serde_json::from_value(value.clone())?;
+    let mut event_writer = world.resource_mut::<Events<RegisterExtensionEvent<T>>>();
+    event_writer.send(RegisterExtensionEvent {
+        id: id.to_string(),
+        extension,
+    });
+    Ok(())
+}
+
+impl Identifiable for ExtensionHolder {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn register(&self, world: &mut World, schedule: &mut Schedule) {
+        let mut unknown = Map::new();
+
+        for (name, value) in &self.extensions {
+            let handler = world
+                .get_resource::<ExtensionRegistry>()
+                .and_then(|registry| registry.get(name));
+
+            // Extensions without typed model, or not matching it, are kept verbatim
+            let registered = handler.is_some_and(|handler| handler(world, &self.id, value).is_ok());
+            if !registered {
+                unknown.insert(name.clone(), value.clone());
+            }
+        }
+
+        if !unknown.is_empty() {
+            if let Some(mut event_writer) =
+                world.get_resource_mut::<Events<RegisterExtensionEvent<UnknownExtensions>>>()
+            {
+                event_writer.send(RegisterExtensionEvent {
+                    id: self.id(),
+                    extension: UnknownExtensions(unknown),
+                });
+            }
+        }
+
+        schedule.run(world);
+    }
+}
@@ -1,3 +1,11 @@
+mod area;
+mod extensions;
+mod terminal;
+
+pub use area::*;
+pub use extensions::*;
+pub use terminal::*;
+
 use bevy_ecs::prelude::*;
 
 use crate::{AssetRegistry, Identifiable, Updatable};
@@ -12,8 +20,10 @@ where
     pub update: T::Updater,
 }
 
+/// Update event that could not be applied, its entity or component missing or the update
+/// being invalid
 #[derive(Event, Debug, Clone)]
-pub struct EntityNotFoundEvent {
+pub struct UpdateErrorEvent {
     pub id: String,
     pub error_type: ErrorType,
     pub component_type: String,
@@ -23,27 +33,36 @@ pub struct EntityNotFoundEvent {
 pub enum ErrorType {
     EntityNotFound,
     ComponentNotFound,
+    /// The update is inconsistent with the component, which is left unchanged
+    InvalidUpdate(String),
 }
 
 pub fn handle_update_events<T: Component + Updatable>(
     mut update_events: EventReader<UpdateEvent<T>>,
-    mut error_events: EventWriter<EntityNotFoundEvent>,
+    mut error_events: EventWriter<UpdateErrorEvent>,
     registery: Res<AssetRegistry>,
     mut query: Query<&mut T>,
 ) where
-    T: 'static,
     T::Updater: Send + Sync + Clone,
+    T::Err: std::fmt::Display,
 {
     for UpdateEvent { id, update } in update_events.read() {
         match registery.find(id) {
             Some(entity) => {
                 match query.get_mut(entity) {
-                    Ok(mut component) => {
-                        component.update(update.clone());
-                    }
+                    Ok(mut component) => match component.check_update(update) {
+                        Ok(()) => component.update(update.clone()),
+                        Err(error) => {
+                            error_events.send(UpdateErrorEvent {
+                                id: id.clone(),
+                                error_type: ErrorType::InvalidUpdate(error.to_string()),
+                                component_type: std::any::type_name::<T>().to_string(),
+                            });
+                        }
+                    },
                     Err(_) => {
                         // Component exists but has wrong type
-                        error_events.send(EntityNotFoundEvent {
+                        error_events.send(UpdateErrorEvent {
                             id: id.clone(),
                             error_type: ErrorType::ComponentNotFound,
                             component_type: std::any::type_name::<T>().to_string(),
@@ -53,7 +72,7 @@ pub fn handle_update_events<T: Component + Updatable>(
             }
             None => {
                 // Entity with this ID doesn't exist
-                error_events.send(EntityNotFoundEvent {
+                error_events.send(UpdateErrorEvent {
                     id: id.clone(),
                     error_type: ErrorType::EntityNotFound,
                     component_type: std::any::type_name::<T>().to_string(),
@@ -76,9 +95,7 @@ pub fn handle_register_events<T: Component + Identifiable + Clone>(
     mut register_events: EventReader<RegisterEvent<T>>,
     mut commands: Commands,
     mut registery: ResMut<AssetRegistry>,
-) where
-    T: 'static,
-{
+) {
     for RegisterEvent { id, component } in register_events.read() {
         registery.add_component(&mut commands, id, component.clone());
     }
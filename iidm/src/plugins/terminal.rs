@@ -0,0 +1,127 @@
+use bevy_ecs::{prelude::*, world::EntityRef};
+
+use crate::{
+    AssetRegistry, Battery, BusbarSection, DanglingLine, Generator, HvdcConverterStation, Line,
+    Load, ShuntCompensator, Side, StaticVarCompensator, TerminalRef, ThreeWindingsTransformer,
+    TieLine, TwoWindingsTransformer,
+};
+
+/// Terminal reference that does not match a terminal of the registered network
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TerminalResolutionError {
+    #[error("Equipment {id} referenced by a terminal was not found")]
+    EquipmentNotFound { id: String },
+    #[error("Equipment {id} has no terminal on side {side:?}")]
+    SideNotFound { id: String, side: Option<Side> },
+}
+
+impl TerminalRef {
+    /// Resolves the referenced equipment through the registry.
+    ///
+    /// Injections are referenced without side, branches and three windings transformers
+    /// must give the side of the referenced terminal.
+    pub fn resolve(&self, world: &World) -> Result<Entity, TerminalResolutionError> {
+        let not_found = || TerminalResolutionError::EquipmentNotFound {
+            id: self.id.clone(),
+        };
+
+        let entity = world
+            .get_resource::<AssetRegistry>()
+            .and_then(|registry| registry.find(&self.id))
+            .ok_or_else(not_found)?;
+        let entity_ref = world.get_entity(entity).map_err(|_| not_found())?;
+        let terminal_count = terminal_count(entity_ref).ok_or_else(not_found)?;
+
+        let side_exists = match (self.side, terminal_count) {
+            (None, count) => count == 1,
+            (Some(Side::One), _) => true,
+            (Some(Side::Two), count) => count >= 2,
+            (Some(Side::Three), count) => count == 3,
+        };
+        if !side_exists {
+            return Err(TerminalResolutionError::SideNotFound {
+                id: self.id.clone(),
+                side: self.side,
+            });
+        }
+
+        Ok(entity)
+    }
+}
+
+fn terminal_count(entity: EntityRef) -> Option<usize> {
+    if entity.contains::<ThreeWindingsTransformer>() {
+        return Some(3);
+    }
+    if entity.contains::<Line>()
+        || entity.contains::<TwoWindingsTransformer>()
+        || entity.contains::<TieLine>()
+    {
+        return Some(2);
+    }
+    let injection = entity.contains::<Generator>()
+        || entity.contains::<Load>()
+        || entity.contains::<Battery>()
+        || entity.contains::<BusbarSection>()
+        || entity.contains::<ShuntCompensator>()
+        || entity.contains::<StaticVarCompensator>()
+        || entity.contains::<DanglingLine>()
+        || entity.contains::<HvdcConverterStation>();
+    injection.then_some(1)
+}
+
+/// Checks the regulating terminals of the registered equipments and the terminals
+/// regulated by tap changers.
+///
+/// Returns the id of each regulating equipment with the error of its terminal reference.
+pub fn check_regulating_terminals(world: &mut World) -> Vec<(String, TerminalResolutionError)> {
+    let mut terminals: Vec<(String, TerminalRef)> = Vec::new();
+
+    for generator in world.query::<&Generator>().iter(world) {
+        terminals.extend(regulating_terminal(
+            &generator.id,
+            &generator.regulating_terminal,
+        ));
+    }
+    for shunt in world.query::<&ShuntCompensator>().iter(world) {
+        terminals.extend(regulating_terminal(&shunt.id, &shunt.regulating_terminal));
+    }
+    for svc in world.query::<&StaticVarCompensator>().iter(world) {
+        terminals.extend(regulating_terminal(&svc.id, &svc.regulating_terminal));
+    }
+    for station in world.query::<&HvdcConverterStation>().iter(world) {
+        terminals.extend(regulating_terminal(
+            &station.id,
+            &station.regulating_terminal,
+        ));
+    }
+    for transformer in world.query::<&TwoWindingsTransformer>().iter(world) {
+        if let Some(tap_changer) = &transformer.ratio_tap_changer {
+            terminals.push((transformer.id.clone(), tap_changer.terminal_ref.clone()));
+        }
+        if let Some(tap_changer) = &transformer.phase_tap_changer {
+            terminals.push((transformer.id.clone(), tap_changer.terminal_ref.clone()));
+        }
+    }
+    for transformer in world.query::<&ThreeWindingsTransformer>().iter(world) {
+        for (_, leg) in transformer.legs() {
+            if let Some(tap_changer) = &leg.ratio_tap_changer {
+                terminals.push((transformer.id.clone(), tap_changer.terminal_ref.clone()));
+            }
+            if let Some(tap_changer) = &leg.phase_tap_changer {
+                terminals.push((transformer.id.clone(), tap_changer.terminal_ref.clone()));
+            }
+        }
+    }
+
+    terminals
+        .into_iter()
+        .filter_map(|(id, terminal)| terminal.resolve(world).err().map(|error| (id, error)))
+        .collect()
+}
+
+fn regulating_terminal(id: &str, terminal: &Option<TerminalRef>) -> Option<(String, TerminalRef)> {
+    terminal
+        .as_ref()
+        .map(|terminal| (id.to_string(), terminal.clone()))
+}
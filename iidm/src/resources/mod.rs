@@ -70,7 +70,7 @@ mod tests {
 
         {
             let mut queue = CommandQueue::default();
-            let mut commands = Commands::new(&mut queue, &mut world);
+            let mut commands = Commands::new(&mut queue, &world);
             registry.register(&mut commands, "test_entity");
             queue.apply(&mut world);
         }
@@ -87,7 +87,7 @@ mod tests {
 
         {
             let mut queue = CommandQueue::default();
-            let mut commands = Commands::new(&mut queue, &mut world);
+            let mut commands = Commands::new(&mut queue, &world);
             registry.register(&mut commands, "test_entity");
             queue.apply(&mut world);
         }
@@ -117,7 +117,7 @@ mod tests {
 
         {
             let mut queue = CommandQueue::default();
-            let mut commands = Commands::new(&mut queue, &mut world);
+            let mut commands = Commands::new(&mut queue, &world);
             registry.add_component(&mut commands, "test_entity", TestComponent);
             queue.apply(&mut world);
         }
@@ -135,7 +135,7 @@ mod tests {
 
         let entity = {
             let mut queue = CommandQueue::default();
-            let mut commands = Commands::new(&mut queue, &mut world);
+            let mut commands = Commands::new(&mut queue, &world);
             let entity = registry.register(&mut commands, "test_entity");
             queue.apply(&mut world);
             entity
@@ -146,7 +146,7 @@ mod tests {
 
         {
             let mut queue = CommandQueue::default();
-            let mut commands = Commands::new(&mut queue, &mut world);
+            let mut commands = Commands::new(&mut queue, &world);
             registry.add_component(&mut commands, "test_entity", TestComponent);
             queue.apply(&mut world);
         }
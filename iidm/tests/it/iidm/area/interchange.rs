@@ -0,0 +1,139 @@
+use super::*;
+use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+use iidm::*;
+use serde_json::json;
+
+const NETWORK_FILE: &str = "tests/data/network.json";
+
+fn init_world() -> (World, Schedule) {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<AssetRegistry>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    world.init_resource::<Events<RegisterEvent<Area>>>();
+    world.init_resource::<Events<RegisterEvent<DanglingLine>>>();
+    world.init_resource::<Events<RegisterEvent<TieLine>>>();
+    world.init_resource::<Events<RegisterEvent<HvdcLine>>>();
+    world.init_resource::<Events<RegisterEvent<HvdcConverterStation>>>();
+    schedule.add_systems(handle_register_events::<Line>);
+    schedule.add_systems(handle_register_events::<Area>);
+    schedule.add_systems(handle_register_events::<DanglingLine>);
+    schedule.add_systems(handle_register_events::<TieLine>);
+    schedule.add_systems(handle_register_events::<HvdcLine>);
+    schedule.add_systems(handle_register_events::<HvdcConverterStation>);
+
+    (world, schedule)
+}
+
+fn create_solved_lines() -> Vec<Line> {
+    let test_network = std::fs::read_to_string(NETWORK_FILE).unwrap();
+    let mut network: Network = serde_json::from_str(&test_network).unwrap();
+    for line in network.lines.iter_mut() {
+        line.p1 = Some(302.4);
+        line.p2 = Some(-300.4);
+    }
+    network.lines
+}
+
+#[test]
+fn test_interchange_from_boundaries() {
+    let (mut world, mut schedule) = init_world();
+    for line in create_solved_lines() {
+        line.register(&mut world, &mut schedule);
+    }
+
+    let mut area = create_default_area();
+    // The dangling line isn't part of this network
+    area.area_boundaries
+        .retain(|boundary| boundary.boundary_type == AreaBoundaryType::TerminalRef);
+    area.register(&mut world, &mut schedule);
+
+    let interchanges = compute_area_interchanges(&mut world);
+    assert_eq!(interchanges.len(), 1);
+    assert_eq!(interchanges["ControlArea_A"], 302.4);
+}
+
+#[test]
+fn test_interchange_from_branches() {
+    let (mut world, mut schedule) = init_world();
+    for line in create_solved_lines() {
+        line.register(&mut world, &mut schedule);
+    }
+
+    let mut area_a = create_default_area();
+    area_a.area_boundaries.clear();
+    area_a.register(&mut world, &mut schedule);
+
+    let mut area_b = create_default_area();
+    area_b.id = "ControlArea_B".to_string();
+    area_b.voltage_level_ids = vec!["VLHV2".to_string(), "VLLOAD".to_string()];
+    area_b.area_boundaries.clear();
+    area_b.register(&mut world, &mut schedule);
+
+    let interchanges = compute_area_interchanges(&mut world);
+    assert!((interchanges["ControlArea_A"] - 604.8).abs() < 1e-9);
+    assert!((interchanges["ControlArea_B"] + 600.8).abs() < 1e-9);
+}
+
+#[test]
+fn test_interchange_with_unknown_boundary() {
+    let (mut world, mut schedule) = init_world();
+
+    let area = create_default_area();
+    area.register(&mut world, &mut schedule);
+
+    let interchanges = compute_area_interchanges(&mut world);
+    assert_eq!(interchanges["ControlArea_A"], 0.0);
+}
+
+fn dangling_line(id: &str, voltage_level_id: &str, p: f64) -> serde_json::Value {
+    json!({
+        "id": id, "p0": 0.0, "q0": 0.0, "r": 1.0, "x": 10.0, "g": 0.0, "b": 0.0,
+        "voltageLevelId": voltage_level_id, "p": p
+    })
+}
+
+fn converter_station(id: &str, voltage_level_id: &str, p: f64) -> serde_json::Value {
+    json!({
+        "id": id, "name": id, "voltageLevelId": voltage_level_id, "p": p,
+        "lossFactor": 1.1, "reactivePowerSetpoint": 0.0
+    })
+}
+
+#[test]
+fn test_interchange_from_tie_hvdc_and_dangling_lines() {
+    let (mut world, mut schedule) = init_world();
+
+    let tie_line: TieLine = serde_json::from_value(json!({
+        "id": "TIE", "name": "TIE", "ucteXnodeCode": "XNODE",
+        "danglingLine1": dangling_line("TIE_1", "VLHV1", 150.0),
+        "danglingLine2": dangling_line("TIE_2", "VLHV2", -149.0)
+    }))
+    .unwrap();
+    tie_line.register(&mut world, &mut schedule);
+
+    let hvdc_line: HvdcLine = serde_json::from_value(json!({
+        "id": "HVDC", "name": "HVDC", "r": 1.0, "nominalV": 400.0,
+        "convertersMode": "SIDE1_INVERTER_SIDE2_RECTIFIER",
+        "activePowerSetpoint": 50.0, "maxP": 300.0,
+        "converterStation1": converter_station("CS1", "VLGEN", -50.0),
+        "converterStation2": converter_station("CS2", "VLLOAD", 51.0)
+    }))
+    .unwrap();
+    hvdc_line.register(&mut world, &mut schedule);
+
+    // Dangling lines of the area lead outside the network, those of other areas are ignored
+    for (id, voltage_level_id, p) in [("DL_A", "VLHV1", 20.0), ("DL_B", "VLHV2", 30.0)] {
+        let dangling_line: DanglingLine =
+            serde_json::from_value(dangling_line(id, voltage_level_id, p)).unwrap();
+        dangling_line.register(&mut world, &mut schedule);
+    }
+
+    let mut area = create_default_area();
+    area.area_boundaries.clear();
+    area.register(&mut world, &mut schedule);
+
+    let interchanges = compute_area_interchanges(&mut world);
+    assert!((interchanges["ControlArea_A"] - (150.0 - 50.0 + 20.0)).abs() < 1e-9);
+}
@@ -0,0 +1,20 @@
+use iidm::Area;
+
+mod interchange;
+mod serialization;
+
+const VALID_AREA_JSON: &str = r#"{
+            "id": "ControlArea_A",
+            "name": "Control Area A",
+            "areaType": "ControlArea",
+            "interchangeTarget": -602.6,
+            "voltageLevelIds": ["VLGEN", "VLHV1"],
+            "areaBoundaries": [
+                { "type": "terminalRef", "id": "NHV1_NHV2_1", "side": "ONE", "ac": true },
+                { "type": "boundary", "id": "DL", "ac": true }
+            ]
+        }"#;
+
+fn create_default_area() -> Area {
+    serde_json::from_str(VALID_AREA_JSON).unwrap()
+}
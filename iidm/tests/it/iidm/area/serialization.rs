@@ -0,0 +1,49 @@
+use iidm::{Area, AreaBoundaryType, Network, Side, Updatable};
+
+use super::{create_default_area, VALID_AREA_JSON};
+
+#[test]
+fn test_deserialize_from_json() {
+    let area: Area = serde_json::from_str(VALID_AREA_JSON).unwrap();
+    assert_eq!(area.id, "ControlArea_A");
+    assert_eq!(area.name.as_deref(), Some("Control Area A"));
+    assert_eq!(area.area_type, "ControlArea");
+    assert_eq!(area.interchange_target, Some(-602.6));
+    assert_eq!(area.voltage_level_ids, vec!["VLGEN", "VLHV1"]);
+
+    assert_eq!(area.area_boundaries.len(), 2);
+    let terminal = &area.area_boundaries[0];
+    assert_eq!(terminal.boundary_type, AreaBoundaryType::TerminalRef);
+    assert_eq!(terminal.side, Some(Side::One));
+    let boundary = &area.area_boundaries[1];
+    assert_eq!(boundary.boundary_type, AreaBoundaryType::Boundary);
+    assert!(boundary.side.is_none());
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let area = create_default_area();
+    let json = serde_json::to_string(&area).unwrap();
+    let deserialized: Area = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&deserialized).unwrap(),
+        serde_json::to_value(&area).unwrap()
+    );
+}
+
+#[test]
+fn test_network_without_areas() -> Result<(), Box<dyn std::error::Error>> {
+    let test_network = std::fs::read_to_string("tests/data/network.json")?;
+    let network: Network = serde_json::from_str(&test_network)?;
+    assert!(network.areas.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_json_update_interchange_target() {
+    let mut area = create_default_area();
+    area.update_from_json(r#"{"interchangeTarget": 100.0}"#)
+        .unwrap();
+    assert_eq!(area.interchange_target, Some(100.0));
+    assert_eq!(area.area_boundaries.len(), 2);
+}
@@ -0,0 +1,221 @@
+use std::io::{Cursor, Write};
+
+use iidm::libs::cgmes::{from_cgmes, from_cgmes_zip, CgmesError};
+use iidm::libs::compression::{compress, Compression};
+use iidm::*;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::*;
+
+#[test]
+fn test_cgmes_import() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = read_profiles()?;
+    let profiles: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let import = from_cgmes("cgmes", &profiles)?;
+    let network = &import.network;
+
+    assert_eq!(network.source_format, "CGMES");
+    assert_eq!(network.case_date.to_rfc3339(), "2026-10-19T10:30:00+00:00");
+    assert_eq!(network.substations.len(), 2);
+    let substation = &network.substations[0];
+    assert_eq!(substation.id, "_S1");
+    assert_eq!(substation.country, "BE");
+    assert_eq!(substation.voltage_levels.len(), 2);
+    assert_eq!(network.substations[1].voltage_levels.len(), 1);
+    assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+
+    // Connectivity nodes and terminals have their own nodes, linked by switches and internal
+    // connections
+    assert!(network.validate_node_connections().is_empty());
+    let voltage_level = &substation.voltage_levels[0];
+    assert_eq!(voltage_level.id, "_VL1_380");
+    assert_eq!(voltage_level.nominal_v, 380.0);
+    assert_eq!(voltage_level.topology_kind, TopologyKind::NodeBreaker);
+    let topology = voltage_level.node_breaker_topology.as_ref().unwrap();
+    assert_eq!(topology.nodes.len(), 5);
+    assert_eq!(topology.internal_connections.len(), 3);
+    let breaker = &topology.switches[0];
+    assert_eq!(breaker.id, "_BR1");
+    assert!(matches!(breaker.kind, SwitchKind::Breaker));
+    assert!(!breaker.open);
+    assert!(breaker.retained);
+    let disconnector = &network.substations[1].voltage_levels[0]
+        .node_breaker_topology
+        .as_ref()
+        .unwrap()
+        .switches[0];
+    assert!(matches!(disconnector.kind, SwitchKind::Disconnector));
+
+    // Busbar voltages come from the state variables of their topological node
+    let busbar_section = &voltage_level.busbar_sections.as_ref().unwrap()[0];
+    assert_eq!(busbar_section.name, "Busbar 1");
+    assert_eq!(busbar_section.v, Some(402.5));
+    assert_eq!(busbar_section.angle, Some(-1.5));
+
+    // Machine powers are given in load sign convention
+    let generator = &substation.voltage_levels[1].generators.as_ref().unwrap()[0];
+    assert_eq!(generator.id, "_G1");
+    assert_eq!(generator.energy_source, EnergySource::Thermal);
+    assert_eq!(generator.target_p, 300.0);
+    assert_eq!(generator.target_q, 50.0);
+    assert_eq!(generator.target_v, 21.0);
+    assert!(generator.voltage_regulator_on);
+    assert_eq!((generator.min_p, generator.max_p), (50.0, 500.0));
+    assert_eq!(generator.p, Some(-300.0));
+
+    let load = &network.substations[1].voltage_levels[0]
+        .loads
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!((load.p0, load.q0), (250.0, 80.0));
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.section_count, 2);
+    let (b, _) = shunt.admittance().unwrap();
+    assert!((b - 2e-4).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_cgmes_branches_import() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = read_profiles()?;
+    let profiles: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let network = from_cgmes("cgmes", &profiles)?.network;
+
+    let line = &network.lines[0];
+    assert_eq!(line.id, "_L1");
+    assert_eq!(
+        (
+            line.voltage_level_id1.as_str(),
+            line.voltage_level_id2.as_str()
+        ),
+        ("_VL1_380", "_VL2_380")
+    );
+    assert_eq!((line.r, line.x), (2.0, 20.0));
+    assert_eq!(line.b1, 5e-5);
+    assert_eq!((line.p1, line.q2), (Some(210.0), Some(-25.0)));
+    let limits = line.selected_operational_limits_group(Side::One).unwrap();
+    assert_eq!(limits.id, "_OLS_L1_1");
+    let current_limits = limits.current_limits.as_ref().unwrap();
+    assert_eq!(current_limits.permanent_limit, 1000.0);
+    let durations: Vec<i32> = current_limits
+        .temporary_limits
+        .iter()
+        .map(|limit| limit.acceptable_duration)
+        .collect();
+    assert_eq!(durations, [600, 60]);
+    assert_eq!(current_limits.temporary_limits[1].value, 1500.0);
+    assert!(line.selected_operational_limits_group(Side::Two).is_none());
+
+    // The impedance of the first end is brought to the rated voltage of the second one
+    let transformer = &network.substations[0].two_windings_transformers[0];
+    assert_eq!(transformer.id, "_TR1");
+    assert_eq!((transformer.rated_u1, transformer.rated_u2), (400.0, 21.0));
+    assert!((transformer.x - 40.0 * (21.0f64 / 400.0).powi(2)).abs() < 1e-12);
+    let tap_changer = transformer.ratio_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.low_tap_position, 1);
+    assert_eq!(tap_changer.steps.len(), 21);
+    assert_eq!(tap_changer.tap_position, 13);
+    assert!((tap_changer.steps[12].rho - 1.0 / 1.02).abs() < 1e-12);
+    assert!(tap_changer.regulating);
+    assert_eq!(tap_changer.regulation_value, 21.5);
+    assert_eq!(tap_changer.terminal_ref.id, "_TR1");
+    assert_eq!(tap_changer.terminal_ref.side, Some(Side::Two));
+
+    // Lines ending at a boundary node of another model are dangling lines
+    let dangling_line = &network.dangling_lines[0];
+    assert_eq!(dangling_line.id, "_BL1");
+    assert_eq!(dangling_line.b, 2e-5);
+    assert_eq!((dangling_line.p0, dangling_line.q0), (40.0, 5.0));
+    assert_eq!(dangling_line.unknown_fields["pairingKey"], "_CN_BOUNDARY");
+    Ok(())
+}
+
+#[test]
+fn test_cgmes_unmapped_classes() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = read_profiles()?;
+    let profiles: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let import = from_cgmes("cgmes", &profiles)?;
+
+    let unmapped: Vec<(&str, usize)> = import
+        .unmapped_classes
+        .iter()
+        .map(|(class, count)| (class.as_str(), *count))
+        .collect();
+    assert_eq!(
+        unmapped,
+        [
+            ("ControlArea", 1),
+            ("EquivalentInjection", 1),
+            ("LoadResponseCharacteristic", 1),
+            ("TopologicalIsland", 1),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cgmes_equipment_profile_only() -> Result<(), Box<dyn std::error::Error>> {
+    let equipment = std::fs::read_to_string(EQ_FILE)?;
+    let network = from_cgmes("eq", &[&equipment])?.network;
+
+    // Without state variables, switches are in their normal state and no flow is known
+    assert!(network.validate_node_connections().is_empty());
+    let line = &network.lines[0];
+    assert_eq!(line.p1, None);
+    let transformer = &network.substations[0].two_windings_transformers[0];
+    let tap_changer = transformer.ratio_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.tap_position, 11);
+    assert!(!tap_changer.regulating);
+    Ok(())
+}
+
+#[test]
+fn test_cgmes_invalid_xml() {
+    let result = from_cgmes("invalid", &["<rdf:RDF>"]);
+    assert!(matches!(result, Err(CgmesError::Xml { document: 0, .. })));
+
+    let result = from_cgmes("invalid", &["<RDF/>"]);
+    assert!(matches!(
+        result,
+        Err(CgmesError::Syntax { document: 0, .. })
+    ));
+}
+
+#[test]
+fn test_cgmes_zip_import() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = read_profiles()?;
+    let refs: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let expected = serde_json::to_value(from_cgmes("cgmes", &refs)?.network)?;
+
+    // Profiles are archived as they are, in archives of their own or compressed
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    writer.start_file("EQ.xml", options)?;
+    writer.write_all(profiles[0].as_bytes())?;
+    writer.start_file("SSH.xml", options)?;
+    writer.write_all(profiles[1].as_bytes())?;
+    writer.start_file("TP.zip", options)?;
+    writer.write_all(&compress(
+        profiles[2].as_bytes(),
+        Compression::Zip,
+        "TP.xml",
+    )?)?;
+    writer.start_file("SV.xml.gz", options)?;
+    writer.write_all(&compress(profiles[3].as_bytes(), Compression::Gzip, "")?)?;
+    writer.start_file("README.txt", options)?;
+    writer.write_all(b"CGMES model")?;
+    let archive = writer.finish()?.into_inner();
+
+    let import = from_cgmes_zip("cgmes", &archive)?;
+    assert_eq!(serde_json::to_value(import.network)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_cgmes_zip_without_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let archive = compress(b"CGMES model", Compression::Zip, "README.txt")?;
+    let result = from_cgmes_zip("empty", &archive);
+    assert!(matches!(result, Err(CgmesError::Archive(_))));
+    Ok(())
+}
@@ -0,0 +1,13 @@
+mod import;
+
+const EQ_FILE: &str = "tests/data/cgmes/EQ.xml";
+const SSH_FILE: &str = "tests/data/cgmes/SSH.xml";
+const TP_FILE: &str = "tests/data/cgmes/TP.xml";
+const SV_FILE: &str = "tests/data/cgmes/SV.xml";
+
+fn read_profiles() -> std::io::Result<Vec<String>> {
+    [EQ_FILE, SSH_FILE, TP_FILE, SV_FILE]
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect()
+}
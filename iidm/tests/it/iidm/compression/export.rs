@@ -0,0 +1,39 @@
+use iidm::libs::compression::{
+    compress, decompress, read_iidm, to_compressed_json, to_compressed_xml, Compression,
+};
+use iidm::libs::json::from_json;
+use iidm::libs::version::IidmVersion;
+
+use super::*;
+
+#[test]
+fn test_compressed_export_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let expected = serde_json::to_value(&network)?;
+
+    for compression in [Compression::Gzip, Compression::Zstd, Compression::Zip] {
+        let json = to_compressed_json(&network, IidmVersion::LATEST, compression)?;
+        assert_eq!(Compression::detect(&json), Some(compression));
+        assert_eq!(serde_json::to_value(read_iidm(&json)?)?, expected);
+
+        let xml = to_compressed_xml(&network, IidmVersion::LATEST, compression)?;
+        assert_eq!(Compression::detect(&xml), Some(compression));
+        assert_eq!(serde_json::to_value(read_iidm(&xml)?)?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_zip_entry_without_network_extension() -> Result<(), Box<dyn std::error::Error>> {
+    let archive = compress(b"{}", Compression::Zip, "network.txt")?;
+    let error = decompress(&archive).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn test_compression_extensions() {
+    assert_eq!(Compression::Gzip.extension(), "gz");
+    assert_eq!(Compression::Zstd.extension(), "zst");
+    assert_eq!(Compression::Zip.extension(), "zip");
+}
@@ -0,0 +1,62 @@
+use iidm::libs::compression::{decompress, read_iidm, Compression};
+use iidm::libs::error::IidmError;
+use iidm::libs::json::from_json;
+
+use super::*;
+
+#[test]
+fn test_compression_detection() -> Result<(), Box<dyn std::error::Error>> {
+    let detect = |path| -> std::io::Result<Option<Compression>> {
+        Ok(Compression::detect(&std::fs::read(path)?))
+    };
+    assert_eq!(detect(NETWORK_JSON_FILE)?, None);
+    assert_eq!(detect(NETWORK_GZIP_FILE)?, Some(Compression::Gzip));
+    assert_eq!(detect(NETWORK_ZSTD_FILE)?, Some(Compression::Zstd));
+    assert_eq!(detect(NETWORK_ZIP_FILE)?, Some(Compression::Zip));
+    Ok(())
+}
+
+#[test]
+fn test_compressed_json_import() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read(NETWORK_JSON_FILE)?;
+    let expected = serde_json::to_value(from_json(&json)?)?;
+
+    for path in [NETWORK_GZIP_FILE, NETWORK_ZSTD_FILE] {
+        let bytes = std::fs::read(path)?;
+        assert_eq!(decompress(&bytes)?.as_ref(), json.as_slice());
+        assert_eq!(serde_json::to_value(from_json(&bytes)?)?, expected);
+        assert_eq!(serde_json::to_value(read_iidm(&bytes)?)?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_zip_import_reads_first_network_entry() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read(NETWORK_JSON_FILE)?;
+    let network = read_iidm(&std::fs::read(NETWORK_ZIP_FILE)?)?;
+
+    // The archive holds the XML export of the JSON network
+    assert_eq!(
+        serde_json::to_value(&network)?,
+        serde_json::to_value(from_json(&json)?)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_plain_documents_are_not_copied() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read(NETWORK_JSON_FILE)?;
+    assert!(matches!(decompress(&json)?, std::borrow::Cow::Borrowed(_)));
+    Ok(())
+}
+
+#[test]
+fn test_truncated_gzip_import() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(NETWORK_GZIP_FILE)?;
+    let result = read_iidm(&bytes[..bytes.len() / 2]);
+    match result {
+        Err(error @ IidmError::Io(_)) => assert!(error.to_string().starts_with("I/O error")),
+        other => panic!("Expected an I/O error, got {:?}", other.map(|_| ())),
+    }
+    Ok(())
+}
@@ -0,0 +1,8 @@
+mod export;
+mod import;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_GZIP_FILE: &str = "tests/data/network.json.gz";
+const NETWORK_ZSTD_FILE: &str = "tests/data/network.json.zst";
+/// Archive of a readme followed by `network.xiidm`
+const NETWORK_ZIP_FILE: &str = "tests/data/network.zip";
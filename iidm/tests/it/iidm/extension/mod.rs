@@ -0,0 +1,33 @@
+use iidm::Network;
+
+mod register;
+mod serialization;
+
+const NETWORK_FILE: &str = "tests/data/network.json";
+
+const EXTENSIONS_JSON: &str = r#"[
+    {
+        "id": "P1",
+        "extensions": {
+            "substationPosition": {
+                "coordinate": { "latitude": 51.0, "longitude": 2.0 }
+            },
+            "entsoeArea": { "code": "FR" }
+        }
+    },
+    {
+        "id": "GEN",
+        "extensions": {
+            "activePowerControl": { "participate": true, "droop": 4.0 },
+            "generatorShortCircuit": { "directTransX": 20.0, "stepUpTransformerX": 10.0 },
+            "vendorSpecific": { "foo": [1, 2, 3], "bar": "baz" }
+        }
+    }
+]"#;
+
+fn create_network_with_extensions() -> Network {
+    let test_network = std::fs::read_to_string(NETWORK_FILE).unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&test_network).unwrap();
+    value["extensions"] = serde_json::from_str(EXTENSIONS_JSON).unwrap();
+    serde_json::from_value(value).unwrap()
+}
@@ -0,0 +1,102 @@
+use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+use iidm::*;
+
+use super::*;
+
+fn init_world() -> (World, Schedule) {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<AssetRegistry>();
+    world.init_resource::<Events<UpdateErrorEvent>>();
+    world.init_resource::<Events<RegisterEvent<Network>>>();
+    world.init_resource::<Events<RegisterEvent<Substation>>>();
+    world.init_resource::<Events<RegisterEvent<VoltageLevel>>>();
+    world.init_resource::<Events<RegisterEvent<Generator>>>();
+    world.init_resource::<Events<RegisterEvent<Load>>>();
+    world.init_resource::<Events<RegisterEvent<TwoWindingsTransformer>>>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    schedule.add_systems(handle_register_events::<Network>);
+    schedule.add_systems(handle_register_events::<Substation>);
+    schedule.add_systems(handle_register_events::<VoltageLevel>);
+    schedule.add_systems(handle_register_events::<Generator>);
+    schedule.add_systems(handle_register_events::<Load>);
+    schedule.add_systems(handle_register_events::<TwoWindingsTransformer>);
+    schedule.add_systems(handle_register_events::<Line>);
+
+    // Typed extensions
+    let mut registry = ExtensionRegistry::default();
+    world.init_resource::<Events<RegisterExtensionEvent<ActivePowerControl>>>();
+    world.init_resource::<Events<RegisterExtensionEvent<SubstationPosition>>>();
+    world.init_resource::<Events<RegisterExtensionEvent<UnknownExtensions>>>();
+    world.init_resource::<Events<UpdateEvent<ActivePowerControl>>>();
+    schedule.add_systems(handle_register_extension_events::<ActivePowerControl>);
+    schedule.add_systems(handle_register_extension_events::<SubstationPosition>);
+    schedule.add_systems(handle_register_extension_events::<UnknownExtensions>);
+    schedule.add_systems(handle_update_events::<ActivePowerControl>);
+    registry.register::<ActivePowerControl>();
+    registry.register::<SubstationPosition>();
+    world.insert_resource(registry);
+
+    (world, schedule)
+}
+
+#[test]
+fn test_register_extensions_on_extendable() {
+    let (mut world, mut schedule) = init_world();
+    create_network_with_extensions().register(&mut world, &mut schedule);
+
+    let registry = world.resource::<AssetRegistry>();
+    let gen = world.entity(registry.find("GEN").unwrap());
+    assert!(gen.contains::<Generator>());
+    assert_eq!(gen.get::<ActivePowerControl>().unwrap().droop, 4.0);
+
+    let p1 = world.entity(registry.find("P1").unwrap());
+    assert!(p1.contains::<Substation>());
+    assert_eq!(
+        p1.get::<SubstationPosition>().unwrap().coordinate.latitude,
+        51.0
+    );
+}
+
+#[test]
+fn test_register_unknown_extensions() {
+    let (mut world, mut schedule) = init_world();
+    create_network_with_extensions().register(&mut world, &mut schedule);
+
+    let registry = world.resource::<AssetRegistry>();
+
+    // Not registered in this world: kept verbatim with the vendor extension
+    let gen = world.entity(registry.find("GEN").unwrap());
+    let unknown = gen.get::<UnknownExtensions>().unwrap();
+    assert_eq!(unknown.0.len(), 2);
+    assert_eq!(unknown.0["vendorSpecific"]["bar"], "baz");
+    assert!(unknown.0.contains_key("generatorShortCircuit"));
+
+    let p1 = world.entity(registry.find("P1").unwrap());
+    assert!(p1
+        .get::<UnknownExtensions>()
+        .unwrap()
+        .0
+        .contains_key("entsoeArea"));
+}
+
+#[test]
+fn test_update_extension() {
+    let (mut world, mut schedule) = init_world();
+    create_network_with_extensions().register(&mut world, &mut schedule);
+
+    let update = ActivePowerControlUpdater::validate_json(r#"{"droop": 6.0}"#).unwrap();
+    let mut event_writer = world.resource_mut::<Events<UpdateEvent<ActivePowerControl>>>();
+    event_writer.send(UpdateEvent {
+        id: "GEN".to_string(),
+        update,
+    });
+    schedule.run(&mut world);
+
+    let registry = world.resource::<AssetRegistry>();
+    let gen = world.entity(registry.find("GEN").unwrap());
+    let control = gen.get::<ActivePowerControl>().unwrap();
+    assert_eq!(control.droop, 6.0);
+    assert!(control.participate);
+}
@@ -0,0 +1,69 @@
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_typed_extensions() {
+    let network = create_network_with_extensions();
+    assert_eq!(network.extensions.len(), 2);
+
+    let p1 = &network.extensions[0];
+    assert_eq!(p1.id, "P1");
+    let position = p1.get::<SubstationPosition>().unwrap().unwrap();
+    assert_eq!(position.coordinate.latitude, 51.0);
+    assert_eq!(position.coordinate.longitude, 2.0);
+    assert_eq!(p1.get::<EntsoeArea>().unwrap().unwrap().code, "FR");
+    assert!(p1.get::<ActivePowerControl>().is_none());
+
+    let gen = &network.extensions[1];
+    let control = gen.get::<ActivePowerControl>().unwrap().unwrap();
+    assert!(control.participate);
+    assert_eq!(control.droop, 4.0);
+    assert!(control.participation_factor.is_none());
+    let short_circuit = gen.get::<GeneratorShortCircuit>().unwrap().unwrap();
+    assert_eq!(short_circuit.direct_trans_x, 20.0);
+    assert!(short_circuit.direct_subtrans_x.is_none());
+}
+
+#[test]
+fn test_unknown_extensions_preserved() {
+    let network = create_network_with_extensions();
+    let json = serde_json::to_value(&network).unwrap();
+
+    let expected: serde_json::Value = serde_json::from_str(EXTENSIONS_JSON).unwrap();
+    assert_eq!(json["extensions"], expected);
+}
+
+#[test]
+fn test_insert_extension() {
+    let mut holder = ExtensionHolder {
+        id: "BBS".to_string(),
+        ..Default::default()
+    };
+    holder
+        .insert(&BusbarSectionPosition {
+            busbar_index: 1,
+            section_index: 2,
+        })
+        .unwrap();
+
+    let value = serde_json::to_value(&holder).unwrap();
+    assert_eq!(
+        value["extensions"]["busbarSectionPosition"]["busbarIndex"],
+        1
+    );
+    assert_eq!(
+        value["extensions"]["busbarSectionPosition"]["sectionIndex"],
+        2
+    );
+}
+
+#[test]
+fn test_network_without_extensions() {
+    let test_network = std::fs::read_to_string(NETWORK_FILE).unwrap();
+    let network: Network = serde_json::from_str(&test_network).unwrap();
+    assert!(network.extensions.is_empty());
+
+    let json = serde_json::to_value(&network).unwrap();
+    assert!(json.get("extensions").is_none());
+}
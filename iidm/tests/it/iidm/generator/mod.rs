@@ -0,0 +1,45 @@
+use iidm::Generator;
+
+mod reactive_limits;
+mod regulating_terminal;
+mod serialization;
+
+const VALID_GENERATOR_JSON: &str = r#"{
+            "id": "GEN",
+            "energySource": "OTHER",
+            "minP": -9999.99,
+            "maxP": 9999.99,
+            "voltageRegulatorOn": true,
+            "targetP": 607.0,
+            "targetV": 24.5,
+            "targetQ": 301.0,
+            "bus": "NGEN",
+            "connectableBus": "NGEN",
+            "minMaxReactiveLimits": {
+                "minQ": -9999.99,
+                "maxQ": 9999.99
+            }
+        }"#;
+
+const CURVE_JSON: &str = r#"{
+            "points": [
+                { "p": 0.0, "minQ": -100.0, "maxQ": 100.0 },
+                { "p": 200.0, "minQ": -50.0, "maxQ": 60.0 },
+                { "p": 100.0, "minQ": -80.0, "maxQ": 90.0 }
+            ]
+        }"#;
+
+fn create_default_generator() -> Generator {
+    serde_json::from_str(VALID_GENERATOR_JSON).unwrap()
+}
+
+fn create_curve_generator() -> Generator {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_GENERATOR_JSON).unwrap();
+    let object = value.as_object_mut().unwrap();
+    object.remove("minMaxReactiveLimits");
+    object.insert(
+        "reactiveCapabilityCurve".to_string(),
+        serde_json::from_str(CURVE_JSON).unwrap(),
+    );
+    serde_json::from_value(value).unwrap()
+}
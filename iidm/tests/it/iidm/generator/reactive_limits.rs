@@ -0,0 +1,122 @@
+use iidm::*;
+
+use super::*;
+
+fn curve_limits() -> ReactiveLimits {
+    create_curve_generator().reactive_limits.unwrap()
+}
+
+#[test]
+fn test_min_max_range() {
+    let limits = ReactiveLimits::MinMax(MinMaxReactiveLimits {
+        min_q: -10.0,
+        max_q: 20.0,
+        unknown_fields: UnknownFields::new(),
+    });
+    assert_eq!(limits.reactive_range(0.0), Some((-10.0, 20.0)));
+    assert_eq!(limits.reactive_range(1000.0), Some((-10.0, 20.0)));
+}
+
+#[test]
+fn test_curve_points() {
+    let limits = curve_limits();
+    assert_eq!(limits.reactive_range(0.0), Some((-100.0, 100.0)));
+    assert_eq!(limits.reactive_range(100.0), Some((-80.0, 90.0)));
+    assert_eq!(limits.reactive_range(200.0), Some((-50.0, 60.0)));
+}
+
+#[test]
+fn test_curve_interpolation() {
+    let limits = curve_limits();
+    assert_eq!(limits.reactive_range(50.0), Some((-90.0, 95.0)));
+    assert_eq!(limits.reactive_range(150.0), Some((-65.0, 75.0)));
+}
+
+#[test]
+fn test_curve_outside_points() {
+    let limits = curve_limits();
+    assert_eq!(limits.reactive_range(-50.0), Some((-100.0, 100.0)));
+    assert_eq!(limits.reactive_range(500.0), Some((-50.0, 60.0)));
+}
+
+#[test]
+fn test_empty_curve() {
+    let limits = ReactiveLimits::Curve(ReactiveCapabilityCurve {
+        points: vec![],
+        unknown_fields: UnknownFields::new(),
+    });
+    assert!(limits.reactive_range(0.0).is_none());
+    assert!(limits.check(0.0, 1e9).is_ok());
+}
+
+#[test]
+fn test_setpoint_inside_limits() {
+    let mut generator = create_curve_generator();
+    generator.target_p = 150.0;
+    generator.target_q = 75.0;
+    assert!(generator.check_reactive_limits().is_ok());
+}
+
+#[test]
+fn test_setpoint_outside_limits() {
+    let mut generator = create_curve_generator();
+    generator.target_p = 150.0;
+    generator.target_q = 80.0;
+
+    let violation = generator.check_reactive_limits().unwrap_err();
+    assert_eq!(
+        violation,
+        ReactiveLimitsViolation {
+            p: 150.0,
+            q: 80.0,
+            min_q: -65.0,
+            max_q: 75.0,
+        }
+    );
+    assert!(violation.to_string().contains("outside"));
+}
+
+#[test]
+fn test_setpoint_without_limits() {
+    let mut generator = create_default_generator();
+    generator.reactive_limits = None;
+    generator.target_q = 1e9;
+    assert!(generator.check_reactive_limits().is_ok());
+}
+
+#[test]
+fn test_battery_setpoint() {
+    let battery: Battery = serde_json::from_str(
+        r#"{
+            "id": "BAT",
+            "targetP": 10.0,
+            "targetQ": -30.0,
+            "minP": -50.0,
+            "maxP": 50.0,
+            "bus": "NGEN",
+            "connectableBus": "NGEN",
+            "minMaxReactiveLimits": { "minQ": -20.0, "maxQ": 20.0 }
+        }"#,
+    )
+    .unwrap();
+
+    let violation = battery.check_reactive_limits().unwrap_err();
+    assert_eq!(violation.min_q, -20.0);
+    assert_eq!(violation.q, -30.0);
+}
+
+#[test]
+fn test_bounds() {
+    assert_eq!(curve_limits().bounds(), Some((-100.0, 100.0)));
+    let limits = ReactiveLimits::MinMax(MinMaxReactiveLimits {
+        min_q: -10.0,
+        max_q: 20.0,
+        unknown_fields: UnknownFields::new(),
+    });
+    assert_eq!(limits.bounds(), Some((-10.0, 20.0)));
+    let empty = ReactiveLimits::Curve(ReactiveCapabilityCurve {
+        points: vec![],
+        unknown_fields: UnknownFields::new(),
+    });
+    assert!(empty.bounds().is_none());
+}
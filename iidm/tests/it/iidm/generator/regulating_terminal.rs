@@ -0,0 +1,133 @@
+use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+use iidm::*;
+
+use super::create_default_generator;
+
+const NETWORK_FILE: &str = "tests/data/network.json";
+
+fn init_world() -> (World, Schedule) {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<AssetRegistry>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    world.init_resource::<Events<RegisterEvent<Generator>>>();
+    schedule.add_systems(handle_register_events::<Line>);
+    schedule.add_systems(handle_register_events::<Generator>);
+
+    let test_network = std::fs::read_to_string(NETWORK_FILE).unwrap();
+    let network: Network = serde_json::from_str(&test_network).unwrap();
+    for line in network.lines {
+        line.register(&mut world, &mut schedule);
+    }
+
+    (world, schedule)
+}
+
+fn terminal(id: &str, side: Option<Side>) -> TerminalRef {
+    TerminalRef {
+        id: id.to_string(),
+        side,
+        unknown_fields: UnknownFields::new(),
+    }
+}
+
+#[test]
+fn test_regulating_terminal_serialization() {
+    let mut generator = create_default_generator();
+    assert!(generator.regulating_terminal.is_none());
+    assert!(serde_json::to_value(&generator)
+        .unwrap()
+        .get("regulatingTerminal")
+        .is_none());
+
+    generator.regulating_terminal = Some(terminal("NHV1_NHV2_1", Some(Side::Two)));
+    let json = serde_json::to_value(&generator).unwrap();
+    assert_eq!(
+        json["regulatingTerminal"],
+        serde_json::json!({ "id": "NHV1_NHV2_1", "side": "TWO" })
+    );
+
+    let deserialized: Generator = serde_json::from_value(json).unwrap();
+    let regulating_terminal = deserialized.regulating_terminal.unwrap();
+    assert_eq!(regulating_terminal.side, Some(Side::Two));
+}
+
+#[test]
+fn test_resolve_terminals() {
+    let (mut world, mut schedule) = init_world();
+    create_default_generator().register(&mut world, &mut schedule);
+
+    let registry = world.resource::<AssetRegistry>();
+    let line = registry.find("NHV1_NHV2_1").unwrap();
+    let generator = registry.find("GEN").unwrap();
+
+    assert_eq!(
+        terminal("NHV1_NHV2_1", Some(Side::Two)).resolve(&world),
+        Ok(line)
+    );
+    assert_eq!(terminal("GEN", None).resolve(&world), Ok(generator));
+}
+
+#[test]
+fn test_resolve_unknown_equipment() {
+    let (world, _) = init_world();
+    assert_eq!(
+        terminal("UNKNOWN", None).resolve(&world),
+        Err(TerminalResolutionError::EquipmentNotFound {
+            id: "UNKNOWN".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_resolve_unknown_side() {
+    let (mut world, mut schedule) = init_world();
+    create_default_generator().register(&mut world, &mut schedule);
+
+    assert_eq!(
+        terminal("NHV1_NHV2_1", Some(Side::Three)).resolve(&world),
+        Err(TerminalResolutionError::SideNotFound {
+            id: "NHV1_NHV2_1".to_string(),
+            side: Some(Side::Three),
+        })
+    );
+    assert_eq!(
+        terminal("NHV1_NHV2_1", None).resolve(&world),
+        Err(TerminalResolutionError::SideNotFound {
+            id: "NHV1_NHV2_1".to_string(),
+            side: None,
+        })
+    );
+    assert_eq!(
+        terminal("GEN", Some(Side::Two)).resolve(&world),
+        Err(TerminalResolutionError::SideNotFound {
+            id: "GEN".to_string(),
+            side: Some(Side::Two),
+        })
+    );
+}
+
+#[test]
+fn test_check_regulating_terminals() {
+    let (mut world, mut schedule) = init_world();
+
+    let mut remote = create_default_generator();
+    remote.regulating_terminal = Some(terminal("NHV1_NHV2_1", Some(Side::One)));
+    remote.register(&mut world, &mut schedule);
+
+    let mut invalid = create_default_generator();
+    invalid.id = "GEN2".to_string();
+    invalid.regulating_terminal = Some(terminal("MISSING", None));
+    invalid.register(&mut world, &mut schedule);
+
+    assert_eq!(
+        check_regulating_terminals(&mut world),
+        vec![(
+            "GEN2".to_string(),
+            TerminalResolutionError::EquipmentNotFound {
+                id: "MISSING".to_string()
+            }
+        )]
+    );
+}
@@ -0,0 +1,120 @@
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_deserialize_min_max_limits() {
+    let generator = create_default_generator();
+    match generator.reactive_limits {
+        Some(ReactiveLimits::MinMax(limits)) => {
+            assert_eq!(limits.min_q, -9999.99);
+            assert_eq!(limits.max_q, 9999.99);
+        }
+        other => panic!("Expected min/max limits, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_deserialize_curve() {
+    let generator = create_curve_generator();
+    match generator.reactive_limits {
+        Some(ReactiveLimits::Curve(curve)) => assert_eq!(curve.points.len(), 3),
+        other => panic!("Expected a capability curve, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_deserialize_without_limits() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_GENERATOR_JSON).unwrap();
+    value
+        .as_object_mut()
+        .unwrap()
+        .remove("minMaxReactiveLimits");
+    let generator: Generator = serde_json::from_value(value).unwrap();
+    assert!(generator.reactive_limits.is_none());
+}
+
+#[test]
+fn test_serialize_iidm_attribute() {
+    let value = serde_json::to_value(create_default_generator()).unwrap();
+    assert_eq!(value["minMaxReactiveLimits"]["minQ"], -9999.99);
+    assert!(value.get("reactiveCapabilityCurve").is_none());
+    assert!(value.get("reactiveLimits").is_none());
+
+    let value = serde_json::to_value(create_curve_generator()).unwrap();
+    assert!(value["reactiveCapabilityCurve"]["points"].is_array());
+    assert!(value.get("minMaxReactiveLimits").is_none());
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let generator = create_curve_generator();
+    let json = serde_json::to_string(&generator).unwrap();
+    let deserialized: Generator = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&deserialized).unwrap(),
+        serde_json::to_value(&generator).unwrap()
+    );
+}
+
+#[test]
+fn test_json_update_reactive_limits() {
+    let json = r#"{"minMaxReactiveLimits": {"minQ": -10.0, "maxQ": 10.0}}"#;
+    let validation = GeneratorUpdater::validate_json(json);
+    assert!(validation.is_ok(), "{:?}", validation.err());
+
+    let mut generator = create_curve_generator();
+    generator.update(validation.unwrap());
+    assert!(matches!(
+        generator.reactive_limits,
+        Some(ReactiveLimits::MinMax(MinMaxReactiveLimits {
+            min_q: -10.0,
+            max_q: 10.0,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn test_malformed_reactive_limits() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_GENERATOR_JSON).unwrap();
+    value["minMaxReactiveLimits"] = serde_json::json!({"minQ": "low", "maxQ": 10.0});
+    let result = serde_json::from_value::<Generator>(value);
+    assert!(result.is_err(), "Malformed limits must not be dropped");
+}
+
+#[test]
+fn test_exclusive_reactive_limits() {
+    let mut value = serde_json::to_value(create_curve_generator()).unwrap();
+    value["minMaxReactiveLimits"] = serde_json::json!({"minQ": -10.0, "maxQ": 10.0});
+    let error = serde_json::from_value::<Generator>(value).unwrap_err();
+    assert!(error.to_string().contains("exclusive"));
+}
+
+#[test]
+fn test_reactive_limits_not_in_unknown_fields() {
+    let generator = create_curve_generator();
+    assert!(generator.unknown_fields.is_empty());
+
+    let json = serde_json::to_string(&generator).unwrap();
+    assert_eq!(json.matches("reactiveCapabilityCurve").count(), 1);
+}
+
+#[test]
+fn test_json_update_exclusive_reactive_limits() {
+    let json = r#"{
+        "minMaxReactiveLimits": {"minQ": -10.0, "maxQ": 10.0},
+        "reactiveCapabilityCurve": {"points": []}
+    }"#;
+    assert!(GeneratorUpdater::validate_json(json).is_err());
+
+    let json = r#"{"reactiveLimits": {"minMaxReactiveLimits": {"minQ": -10.0, "maxQ": 10.0}}}"#;
+    assert!(GeneratorUpdater::validate_json(json).is_err());
+}
+
+#[test]
+fn test_json_update_nested_typo_in_reactive_limits() {
+    let json = r#"{"minMaxReactiveLimits": {"minQ": -10.0, "maxQ": 10.0, "maxq": 10.0}}"#;
+    let error = GeneratorUpdater::validate_json(json).unwrap_err();
+    assert!(error.to_string().contains("minMaxReactiveLimits.maxq"));
+}
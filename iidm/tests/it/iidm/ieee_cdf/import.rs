@@ -0,0 +1,175 @@
+use iidm::libs::ieee_cdf::{from_ieee_cdf, IeeeCdfError};
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_ieee14_import() -> Result<(), Box<dyn std::error::Error>> {
+    let cdf = std::fs::read_to_string(IEEE14_FILE)?;
+    let network = from_ieee_cdf("ieee14", &cdf)?;
+
+    assert_eq!(network.source_format, "IEEE-CDF");
+    assert_eq!(network.case_date.to_rfc3339(), "1993-08-19T00:00:00+00:00");
+    // Counts of the published case: 14 buses, 5 generators including the synchronous
+    // condensers, 11 loads, 17 lines, 3 transformers and a shunt. Buses 4, 7 and 9, and
+    // buses 5 and 6, are linked by transformers
+    assert_eq!(network.substations.len(), 11);
+    let voltage_levels: Vec<&VoltageLevel> = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .collect();
+    assert_eq!(voltage_levels.len(), 14);
+    let generators: Vec<&Generator> = voltage_levels
+        .iter()
+        .flat_map(|voltage_level| voltage_level.generators.iter().flatten())
+        .collect();
+    assert_eq!(generators.len(), 5);
+    let loads: Vec<&Load> = voltage_levels
+        .iter()
+        .flat_map(|voltage_level| voltage_level.loads.iter().flatten())
+        .collect();
+    assert_eq!(loads.len(), 11);
+    assert_eq!(network.lines.len(), 17);
+    let transformers: Vec<&TwoWindingsTransformer> = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.two_windings_transformers)
+        .collect();
+    assert_eq!(transformers.len(), 3);
+    assert_eq!(network.shunt_compensators.len(), 1);
+
+    // The total load of the published case is 259 MW and 73.5 MVAr
+    let total_p: f64 = loads.iter().map(|load| load.p0).sum();
+    let total_q: f64 = loads.iter().map(|load| load.q0).sum();
+    assert!((total_p - 259.0).abs() < 1e-9);
+    assert!((total_q - 73.5).abs() < 1e-9);
+
+    let generator = generators[0];
+    assert_eq!(generator.id, "GEN-1");
+    assert_eq!(generator.target_p, 232.4);
+    assert!(generator.voltage_regulator_on);
+    let condenser = generators[2];
+    assert_eq!(condenser.id, "GEN-3");
+    assert_eq!(condenser.target_p, 0.0);
+    assert_eq!(condenser.target_v, 1.01);
+
+    let transformer = transformers[0];
+    assert_eq!(transformer.id, "TWT-4-7-1");
+    assert_eq!(transformer.rated_u1, 0.978);
+    assert_eq!(network.lines[0].id, "LINE-1-2-1");
+    assert_counts(IEEE14_FILE, 14, 5, 17, 3)
+}
+
+/// Checks the counts of buses, generators, lines and transformers of a published case
+fn assert_counts(
+    file: &str,
+    buses: usize,
+    generators: usize,
+    lines: usize,
+    transformers: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cdf = std::fs::read_to_string(file)?;
+    let network = from_ieee_cdf(file, &cdf)?;
+    let voltage_levels: Vec<&VoltageLevel> = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .collect();
+    assert_eq!(voltage_levels.len(), buses);
+    let generator_count = voltage_levels
+        .iter()
+        .flat_map(|voltage_level| voltage_level.generators.iter().flatten())
+        .count();
+    assert_eq!(generator_count, generators);
+    assert_eq!(network.lines.len(), lines);
+    let transformer_count = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.two_windings_transformers)
+        .count();
+    assert_eq!(transformer_count, transformers);
+    Ok(())
+}
+
+#[test]
+#[ignore = "the IEEE 30-bus case of the UW archive is not vendored in tests/data yet"]
+fn test_ieee30_import() -> Result<(), Box<dyn std::error::Error>> {
+    assert_counts(IEEE30_FILE, 30, 6, 37, 4)
+}
+
+#[test]
+#[ignore = "the IEEE 57-bus case of the UW archive is not vendored in tests/data yet"]
+fn test_ieee57_import() -> Result<(), Box<dyn std::error::Error>> {
+    assert_counts(IEEE57_FILE, 57, 7, 63, 17)
+}
+
+#[test]
+#[ignore = "the IEEE 118-bus case of the UW archive is not vendored in tests/data yet"]
+fn test_ieee118_import() -> Result<(), Box<dyn std::error::Error>> {
+    assert_counts(IEEE118_FILE, 118, 54, 177, 9)
+}
+
+#[test]
+fn test_transformers_import() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_ieee_cdf("transformers", TRANSFORMER_CASE)?;
+
+    assert_eq!(network.case_date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+    // Both buses of a transformer are in the same substation
+    assert_eq!(network.substations.len(), 2);
+    let substation = &network.substations[0];
+    assert_eq!(substation.id, "SUB-1");
+    assert_eq!(substation.voltage_levels.len(), 2);
+    assert_eq!(substation.voltage_levels[1].id, "VL-2");
+    let transformer = &substation.two_windings_transformers[0];
+    assert_eq!(transformer.id, "TWT-1-2-1");
+    assert_eq!(transformer.voltage_level_id2, "VL-2");
+    assert!((transformer.rated_u1 - 0.95 * 220.0).abs() < 1e-9);
+    assert_eq!(transformer.rated_u2, 110.0);
+    assert!((transformer.x - 0.05 * 110.0 * 110.0 / 100.0).abs() < 1e-9);
+    let tap_changer = transformer.phase_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.steps[0].alpha, -5.0);
+    let limits = transformer
+        .selected_operational_limits_group(Side::One)
+        .unwrap();
+    let permanent_limit = limits.current_limits.as_ref().unwrap().permanent_limit;
+    assert!((permanent_limit - 100.0e3 / (3f64.sqrt() * 220.0)).abs() < 1e-9);
+
+    let generator = &substation.voltage_levels[0].generators.as_ref().unwrap()[0];
+    assert_eq!(generator.target_v, 1.02 * 220.0);
+
+    let line = &network.lines[0];
+    assert_eq!(line.id, "LINE-2-3-1");
+    assert!((line.b1 - 0.02 / (110.0 * 110.0 / 100.0) / 2.0).abs() < 1e-12);
+
+    // Shunt admittances are in per-unit of the base power
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.id, "SHUNT-2");
+    let (b, _) = shunt.admittance().unwrap();
+    assert!((b - 0.2 * 100.0 / 110f64.powi(2)).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_ieee_cdf_invalid_number() {
+    let cdf = TRANSFORMER_CASE.replace("  0.01000    0.10000", "  0.01000    0.1x000");
+    let result = from_ieee_cdf("invalid", &cdf);
+    assert!(matches!(result, Err(IeeeCdfError::Syntax { line: 9, .. })));
+}
+
+#[test]
+fn test_ieee_cdf_unknown_bus() {
+    let cdf = TRANSFORMER_CASE.replace("   2    3  1 1", "   2    5  1 1");
+    let result = from_ieee_cdf("unknown", &cdf);
+    assert!(matches!(
+        result,
+        Err(IeeeCdfError::UnknownBus { line: 9, bus: 5 })
+    ));
+}
+
+#[test]
+fn test_ieee_cdf_missing_bus_data() {
+    let cdf = TRANSFORMER_CASE.replace("BUS DATA FOLLOWS", "LOSS ZONES FOLLOWS");
+    let result = from_ieee_cdf("missing", &cdf);
+    assert!(matches!(result, Err(IeeeCdfError::Missing(data)) if data == "bus data"));
+}
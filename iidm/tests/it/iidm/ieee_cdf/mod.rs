@@ -0,0 +1,20 @@
+mod import;
+
+const IEEE14_FILE: &str = "tests/data/ieee14.cdf";
+const IEEE30_FILE: &str = "tests/data/ieee30.cdf";
+const IEEE57_FILE: &str = "tests/data/ieee57.cdf";
+const IEEE118_FILE: &str = "tests/data/ieee118.cdf";
+
+/// Case with base voltages, an off-nominal phase shifting transformer and a shunt
+const TRANSFORMER_CASE: &str = " 10/19/26 TEST                 100.0 2026 W Phase shifter
+BUS DATA FOLLOWS                             3 ITEMS
+   1 HV            1  1  3  1.020   0.00      0.0       0.0    80.0    10.0   220.0  1.020    50.0   -50.0     0.0    0.00    0
+   2 MV            1  1  0  1.000  -2.00     50.0      10.0     0.0     0.0   110.0  0.000     0.0     0.0     0.0    0.20    0
+   3 MV            1  1  0  1.000  -3.00     20.0       5.0     0.0     0.0   110.0  0.000     0.0     0.0     0.0    0.00    0
+-999
+BRANCH DATA FOLLOWS                          2 ITEMS
+   1    2  1 1  1 1   0.00100    0.05000    0.0000  100     0     0    0 0   0.950     5.0    0.0    0.0    0.0     0.0    0.0
+   2    3  1 1  1 0   0.01000    0.10000    0.0200   50     0     0    0 0   0.000     0.0    0.0    0.0    0.0     0.0    0.0
+-999
+END OF DATA
+";
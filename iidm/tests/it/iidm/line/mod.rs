@@ -43,6 +43,12 @@ fn assert_default_values(line: &Line) {
     assert_eq!(line.bus2, "NHV2");
     assert_eq!(line.connectable_bus2, "NHV2");
 
+    // Optional state variables
+    assert!(line.p1.is_none());
+    assert!(line.q1.is_none());
+    assert!(line.p2.is_none());
+    assert!(line.q2.is_none());
+
     // Optional current limits
     assert!(line.current_limits1.is_none());
     assert!(line.current_limits2.is_none());
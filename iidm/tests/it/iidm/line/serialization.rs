@@ -1,4 +1,4 @@
-use iidm::{Line, Updatable};
+use iidm::{JsonSchema, Line, LineUpdater, Updatable};
 
 use super::{assert_default_values, create_default_line, VALID_LINE_JSON};
 
@@ -40,3 +40,83 @@ fn test_json_update_renamed_fields() {
         .unwrap();
     assert_eq!(line.voltage_level_id1, "newvl1");
 }
+
+#[test]
+fn test_deserialize_state_variables() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_LINE_JSON).unwrap();
+    value["p1"] = serde_json::json!(302.4);
+    value["q1"] = serde_json::json!(98.7);
+    value["p2"] = serde_json::json!(-300.4);
+    value["q2"] = serde_json::json!(-137.2);
+
+    let line: Line = serde_json::from_value(value).unwrap();
+    assert_eq!(line.p1, Some(302.4));
+    assert_eq!(line.q1, Some(98.7));
+    assert_eq!(line.p2, Some(-300.4));
+    assert_eq!(line.q2, Some(-137.2));
+}
+
+#[test]
+fn test_serialize_without_state_variables() {
+    let line = create_default_line();
+    let value = serde_json::to_value(&line).unwrap();
+    assert!(value.get("p1").is_none());
+    assert!(value.get("q2").is_none());
+}
+
+#[test]
+fn test_json_update_state_variables() {
+    let mut line = create_default_line();
+    line.update_from_json(r#"{"p1": 302.4, "q1": 98.7}"#)
+        .unwrap();
+    assert_eq!(line.p1, Some(302.4));
+    assert_eq!(line.q1, Some(98.7));
+    assert!(line.p2.is_none());
+}
+
+#[test]
+fn test_json_update_keeps_unknown_fields() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_LINE_JSON).unwrap();
+    value["fictitious"] = true.into();
+    let mut line: Line = serde_json::from_value(value).unwrap();
+
+    // Unmodelled attributes are kept on export but can't be updated
+    let validation = LineUpdater::validate_json(r#"{"fictitious": false}"#);
+    assert!(validation.is_err());
+
+    line.update(LineUpdater::validate_json(r#"{"r": 5.0}"#).unwrap());
+    assert_eq!(line.r, 5.0);
+    assert_eq!(line.unknown_fields["fictitious"], true);
+}
+
+#[test]
+fn test_json_update_nested_typo() {
+    let mut line = create_default_line();
+
+    let json = r#"{"currentLimits1": {"permanentLimit": 900.0, "temporaryLimit": []}}"#;
+    let error = LineUpdater::validate_json(json).unwrap_err();
+    assert!(error.to_string().contains("currentLimits1.temporaryLimit"));
+    assert!(line.update_from_json(json).is_err());
+    assert!(line.current_limits1.is_none());
+
+    let json = r#"{"operationalLimitsGroups1": [{
+        "id": "DEFAULT",
+        "currentLimits": {
+            "permanentLimit": 900.0,
+            "temporaryLimits": [{"name": "20'", "acceptableDuration": 1200, "valu": 1000.0}]
+        }
+    }]}"#;
+    let error = LineUpdater::validate_json(json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("operationalLimitsGroups1[0].currentLimits.temporaryLimits[0].valu"));
+
+    let json = r#"{"currentLimits1": {"permanentLimit": 900.0, "temporaryLimits": []}}"#;
+    line.update_from_json(json).unwrap();
+    assert_eq!(line.current_limits1.unwrap().permanent_limit, 900.0);
+}
+
+#[test]
+fn test_deserialize_updater_unknown_field() {
+    assert!(serde_json::from_str::<LineUpdater>(r#"{"rr": 5.0}"#).is_err());
+}
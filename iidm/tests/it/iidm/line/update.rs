@@ -58,7 +58,9 @@ fn test_update_current_limits() {
             name: "limit1".to_string(),
             acceptable_duration: 20,
             value: 1200.0,
+            unknown_fields: UnknownFields::new(),
         }],
+        unknown_fields: UnknownFields::new(),
     };
 
     line.update(LineUpdater {
@@ -83,6 +85,7 @@ fn test_update_remove_current_limits() {
         current_limits1: Some(Some(CurrentLimits {
             permanent_limit: 1000.0,
             temporary_limits: vec![],
+            unknown_fields: UnknownFields::new(),
         })),
         ..Default::default()
     });
@@ -116,7 +119,7 @@ fn test_handle_line_update() {
     let mut schedule = Schedule::default();
 
     // Init Resources and Systems
-    world.init_resource::<Events<EntityNotFoundEvent>>();
+    world.init_resource::<Events<UpdateErrorEvent>>();
     world.init_resource::<Events<RegisterEvent<Line>>>();
     world.init_resource::<Events<UpdateEvent<Line>>>();
     world.init_resource::<AssetRegistry>();
@@ -135,11 +138,22 @@ fn test_handle_line_update() {
         voltage_level_id1: "vl1".to_string(),
         bus1: "bus1".to_string(),
         connectable_bus1: "bus1".to_string(),
+        node1: None,
         voltage_level_id2: "vl2".to_string(),
         bus2: "bus2".to_string(),
         connectable_bus2: "bus2".to_string(),
+        node2: None,
+        p1: None,
+        q1: None,
+        p2: None,
+        q2: None,
         current_limits1: None,
         current_limits2: None,
+        operational_limits_groups1: vec![],
+        selected_operational_limits_group_id1: None,
+        operational_limits_groups2: vec![],
+        selected_operational_limits_group_id2: None,
+        unknown_fields: UnknownFields::new(),
     };
 
     // Register line with event
@@ -197,7 +211,7 @@ fn test_multiple_line_updates() {
     let mut schedule = Schedule::default();
 
     // Init Resources and Systems
-    world.init_resource::<Events<EntityNotFoundEvent>>();
+    world.init_resource::<Events<UpdateErrorEvent>>();
     world.init_resource::<Events<RegisterEvent<Line>>>();
     world.init_resource::<Events<UpdateEvent<Line>>>();
     world.init_resource::<AssetRegistry>();
@@ -216,11 +230,22 @@ fn test_multiple_line_updates() {
         voltage_level_id1: "vl1".to_string(),
         bus1: "bus1".to_string(),
         connectable_bus1: "bus1".to_string(),
+        node1: None,
         voltage_level_id2: "vl2".to_string(),
         bus2: "bus2".to_string(),
         connectable_bus2: "bus2".to_string(),
+        node2: None,
+        p1: None,
+        q1: None,
+        p2: None,
+        q2: None,
         current_limits1: None,
         current_limits2: None,
+        operational_limits_groups1: vec![],
+        selected_operational_limits_group_id1: None,
+        operational_limits_groups2: vec![],
+        selected_operational_limits_group_id2: None,
+        unknown_fields: UnknownFields::new(),
     };
 
     // Register line with event
@@ -0,0 +1,156 @@
+use iidm::libs::matpower::{from_matpower, to_matpower};
+use iidm::libs::xml::from_xml;
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_case9_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let matpower = std::fs::read_to_string(CASE9_FILE)?;
+    let network = from_matpower("case9", &matpower)?;
+
+    let export = to_matpower(&network);
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+    assert!(export.case.starts_with("function mpc = case9\n"));
+    assert_eq!(export.mapping.buses[&5], vec!["BUS-5"]);
+    assert_eq!(
+        export.mapping.generators,
+        vec!["GEN-1-1", "GEN-2-1", "GEN-3-1"]
+    );
+    assert_eq!(export.mapping.branches[1], "LINE-4-5-1");
+
+    let exported = from_matpower("case9", &export.case)?;
+    assert_eq!(exported.lines.len(), network.lines.len());
+    for (exported, line) in exported.lines.iter().zip(&network.lines) {
+        assert_eq!(exported.id, line.id);
+        assert!((exported.r - line.r).abs() < 1e-9);
+        assert!((exported.x - line.x).abs() < 1e-9);
+        assert!((exported.b1 - line.b1).abs() < 1e-12);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_transformers_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_matpower("transformers", TRANSFORMER_CASE)?;
+
+    let export = to_matpower(&network);
+    let exported = from_matpower("transformers", &export.case)?;
+
+    let transformers = &exported.substations[0].two_windings_transformers;
+    assert!((transformers[0].rated_u1 - 0.95 * 220.0).abs() < 1e-9);
+    let tap_changer = transformers[1].phase_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.steps[0].alpha, -5.0);
+    assert!(exported.lines[0].bus1.is_empty());
+
+    let shunt = &exported.shunt_compensators[0];
+    let (b, _) = shunt.admittance().unwrap();
+    assert!((b - 19.36 / 110.0 / 110.0).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_matpower_export_mapping() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let network: Network = serde_json::from_str(&json)?;
+
+    let export = to_matpower(&network);
+    assert_eq!(export.mapping.buses[&1], vec!["NGEN"]);
+    assert_eq!(
+        export.mapping.branches,
+        vec!["NHV1_NHV2_1", "NHV1_NHV2_2", "NGEN_NHV1", "NHV2_NLOAD"]
+    );
+
+    // The ratio of the transformer is held by its first rated voltage once read back
+    let exported = from_matpower(&network.id, &export.case)?;
+    let transformer = &exported.substations[0].two_windings_transformers[0];
+    let original = &network.substations[0].two_windings_transformers[0];
+    let rho = original.rated_u2 / original.rated_u1;
+    assert!((transformer.rated_u2 / transformer.rated_u1 - rho).abs() < 1e-9);
+
+    let mapping = serde_json::to_value(&export.mapping)?;
+    assert_eq!(mapping["buses"]["2"], serde_json::json!(["NHV1"]));
+    Ok(())
+}
+
+#[test]
+fn test_matpower_export_empty_curve() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut network: Network = serde_json::from_str(&json)?;
+    let generator = &mut network.substations[0].voltage_levels[0]
+        .generators
+        .as_mut()
+        .unwrap()[0];
+    generator.reactive_limits = Some(ReactiveLimits::Curve(ReactiveCapabilityCurve {
+        points: vec![],
+        unknown_fields: UnknownFields::new(),
+    }));
+
+    // Generators without points are exported with the default limits
+    let export = to_matpower(&network);
+    assert!(export.warnings.contains(
+        &"Reactive capability curve of generator GEN has no points, it is not exported".to_string()
+    ));
+    let exported = from_matpower(&network.id, &export.case)?;
+    let generator = &exported.substations[0].voltage_levels[0]
+        .generators
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!(
+        generator
+            .reactive_limits
+            .as_ref()
+            .and_then(ReactiveLimits::bounds),
+        Some((-9999.0, 9999.0))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_matpower_export_node_breaker() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(NODE_BREAKER_XML)?;
+
+    let export = to_matpower(&network);
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+    // The load behind the open breaker is on an isolated bus of its own
+    assert_eq!(export.mapping.buses[&1], vec!["BBS1"]);
+    assert_eq!(export.mapping.buses[&2], vec!["VL1_2"]);
+    assert_eq!(export.mapping.buses[&3], vec!["B2"]);
+    assert_eq!(export.mapping.generators, vec!["GEN"]);
+    assert_eq!(export.mapping.branches, vec!["L"]);
+
+    let exported = from_matpower(&network.id, &export.case)?;
+    assert_eq!(exported.lines.len(), 1);
+    assert!(!exported.lines[0].bus1.is_empty());
+    assert!(!exported.lines[0].bus2.is_empty());
+    let loads: Vec<_> = exported
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .flat_map(|voltage_level| voltage_level.loads.iter().flatten())
+        .map(|load| load.p0)
+        .collect();
+    assert_eq!(loads, vec![20.0]);
+    Ok(())
+}
+
+#[test]
+fn test_matpower_export_tap_position_out_of_steps() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut network: Network = serde_json::from_str(&json)?;
+    let transformer = network
+        .substations
+        .iter_mut()
+        .flat_map(|substation| substation.two_windings_transformers.iter_mut())
+        .find(|transformer| transformer.id == "NHV2_NLOAD")
+        .unwrap();
+    transformer.ratio_tap_changer.as_mut().unwrap().tap_position = 99;
+
+    let export = to_matpower(&network);
+    assert!(export.warnings.contains(
+        &"Tap position 99 of the ratio tap changer of transformer NHV2_NLOAD has no step, \
+          the nominal ratio is exported"
+            .to_string()
+    ));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use iidm::libs::matpower::{from_matpower, MatpowerError};
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_case9_import() -> Result<(), Box<dyn std::error::Error>> {
+    let matpower = std::fs::read_to_string(CASE9_FILE)?;
+    let network = from_matpower("case9", &matpower)?;
+
+    assert_eq!(network.source_format, "MATPOWER");
+    // Branches of case9 have no ratio, they are all lines
+    assert_eq!(network.substations.len(), 9);
+    assert_eq!(network.lines.len(), 9);
+
+    let generators: Vec<&Generator> = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .flat_map(|voltage_level| voltage_level.generators.iter().flatten())
+        .collect();
+    assert_eq!(generators.len(), 3);
+    assert_eq!(generators[0].id, "GEN-1-1");
+    assert_eq!(generators[0].target_p, 72.3);
+    assert_eq!(generators[0].target_v, 1.04 * 345.0);
+    assert!(generators[0].voltage_regulator_on);
+
+    let load = &network.substations[4].voltage_levels[0]
+        .loads
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!(load.id, "LOAD-5");
+    assert_eq!((load.p0, load.q0), (90.0, 30.0));
+
+    // Per-unit impedances are converted with the base voltage and the base power
+    let line = &network.lines[1];
+    assert_eq!(line.id, "LINE-4-5-1");
+    let base_impedance = 345.0 * 345.0 / 100.0;
+    assert!((line.r - 0.017 * base_impedance).abs() < 1e-9);
+    assert!((line.b1 - 0.158 / base_impedance / 2.0).abs() < 1e-12);
+    let limits = line.selected_operational_limits_group(Side::One).unwrap();
+    let permanent_limit = limits.current_limits.as_ref().unwrap().permanent_limit;
+    assert!((permanent_limit - 250.0e3 / (3f64.sqrt() * 345.0)).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_transformers_import() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_matpower("transformers", TRANSFORMER_CASE)?;
+
+    // Buses linked by transformers are in the same substation
+    assert_eq!(network.substations.len(), 2);
+    let substation = &network.substations[0];
+    assert_eq!(substation.id, "SUB-1");
+    assert_eq!(substation.voltage_levels.len(), 3);
+    assert_eq!(substation.two_windings_transformers.len(), 2);
+
+    let transformer = &substation.two_windings_transformers[0];
+    assert_eq!(transformer.id, "TWT-1-2-1");
+    assert!((transformer.rated_u1 - 0.95 * 220.0).abs() < 1e-9);
+    assert_eq!(transformer.rated_u2, 110.0);
+    assert!((transformer.x - 0.05 * 110.0 * 110.0 / 100.0).abs() < 1e-9);
+
+    let shifter = &substation.two_windings_transformers[1];
+    let tap_changer = shifter.phase_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.steps[0].alpha, -5.0);
+
+    // Out of service branches are disconnected
+    assert_eq!(network.lines.len(), 1);
+    assert!(network.lines[0].bus1.is_empty());
+
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.id, "SHUNT-2");
+    assert_eq!(shunt.admittance(), Some((19.36 / 110.0 / 110.0, 0.0)));
+
+    // Equipments of isolated buses are disconnected
+    let isolated = &network.substations[1].voltage_levels[0];
+    let load = &isolated.loads.as_ref().unwrap()[0];
+    assert!(load.bus.is_empty());
+    assert_eq!(load.connectable_bus, "BUS-4");
+    Ok(())
+}
+
+#[test]
+fn test_matpower_invalid_number() {
+    let matpower = TRANSFORMER_CASE.replace("0.001 0.05 0 100", "0.001 x 0 100");
+    let result = from_matpower("invalid", &matpower);
+    assert!(matches!(result, Err(MatpowerError::Syntax { line: 9, .. })));
+}
+
+#[test]
+fn test_matpower_unknown_bus() {
+    let matpower = TRANSFORMER_CASE.replace("2 3 0.01", "2 5 0.01");
+    let result = from_matpower("unknown", &matpower);
+    assert!(matches!(
+        result,
+        Err(MatpowerError::UnknownBus { line: 11, bus: 5 })
+    ));
+}
+
+#[test]
+fn test_matpower_missing_base_power() {
+    let matpower = TRANSFORMER_CASE.replace("mpc.baseMVA = 100;", "");
+    let result = from_matpower("missing", &matpower);
+    assert!(matches!(result, Err(MatpowerError::Missing(field)) if field == "mpc.baseMVA"));
+}
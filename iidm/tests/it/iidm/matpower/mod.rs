@@ -0,0 +1,55 @@
+mod export;
+mod import;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const CASE9_FILE: &str = "tests/data/case9.m";
+
+/// Node-breaker voltage level with a generator behind a closed breaker, a load behind an
+/// open one and a second load on the busbar, linked by a line to a bus-breaker voltage level
+const NODE_BREAKER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<iidm:network xmlns:iidm="http://www.powsybl.org/schema/iidm/1_10" id="nb" caseDate="2016-06-27T12:27:58.535+02:00" forecastDistance="0" sourceFormat="test" minimumValidationLevel="STEADY_STATE_HYPOTHESIS">
+    <iidm:substation id="S1" country="FR" tso="RTE">
+        <iidm:voltageLevel id="VL1" nominalV="400.0" topologyKind="NODE_BREAKER">
+            <iidm:nodeBreakerTopology>
+                <iidm:busbarSection id="BBS1" name="BBS1" node="0"/>
+                <iidm:switch id="BRK1" kind="BREAKER" retained="true" open="false" node1="0" node2="1"/>
+                <iidm:switch id="BRK2" kind="BREAKER" retained="true" open="true" node1="0" node2="2"/>
+                <iidm:switch id="DIS" kind="DISCONNECTOR" retained="false" open="false" node1="0" node2="3"/>
+                <iidm:internalConnection node1="0" node2="4"/>
+            </iidm:nodeBreakerTopology>
+            <iidm:generator id="GEN" energySource="HYDRO" minP="0.0" maxP="100.0" voltageRegulatorOn="true" targetP="50.0" targetV="400.0" targetQ="0.0" node="1">
+                <iidm:minMaxReactiveLimits minQ="-50.0" maxQ="50.0"/>
+            </iidm:generator>
+            <iidm:load id="LOAD1" loadType="UNDEFINED" p0="10.0" q0="3.0" node="2"/>
+            <iidm:load id="LOAD2" loadType="UNDEFINED" p0="20.0" q0="5.0" node="4"/>
+        </iidm:voltageLevel>
+    </iidm:substation>
+    <iidm:substation id="S2" country="FR" tso="RTE">
+        <iidm:voltageLevel id="VL2" nominalV="400.0" topologyKind="BUS_BREAKER">
+            <iidm:busBreakerTopology>
+                <iidm:bus id="B2"/>
+            </iidm:busBreakerTopology>
+        </iidm:voltageLevel>
+    </iidm:substation>
+    <iidm:line id="L" r="1.0" x="10.0" g1="0.0" b1="0.0" g2="0.0" b2="0.0" voltageLevelId1="VL1" node1="3" voltageLevelId2="VL2" bus2="B2" connectableBus2="B2"/>
+</iidm:network>"#;
+
+/// Case with a transformer whose ratio is off-nominal, a phase shifter, a shunt and an
+/// isolated bus, rows being written on single lines
+const TRANSFORMER_CASE: &str = "function mpc = transformers
+mpc.version = '2';
+mpc.baseMVA = 100;
+mpc.bus = [1 3 0 0 0 0 1 1.02 0 220 1 1.1 0.9; 2 1 50 10 0 19.36 1 1 -2 110 1 1.1 0.9;
+    3 1 20 5 0 0 1 1 -3 110 1 1.1 0.9;
+    4 4 10 0 0 0 1 1 0 110 1 1.1 0.9];
+mpc.gen = [1 80 10 50 -50 1.02 100 1 100 0];
+mpc.branch = [
+    1 2 0.001 0.05 0 100 100 100 0.95 0 1 -360 360;
+    1 3 0.001 0.05 0 0 0 0 1 5 1 -360 360;
+    2 3 0.01 0.1 0.02 50 50 50 0 0 0 -360 360;
+];
+mpc.bus_name = {
+    'HV';
+    'MV';
+};
+";
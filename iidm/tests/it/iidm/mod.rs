@@ -1,4 +1,22 @@
+mod area;
+mod cgmes;
+mod compression;
 mod current_limit;
+mod extension;
+mod generator;
+mod ieee_cdf;
 mod line;
+mod matpower;
 mod network;
+mod node_breaker;
+mod operational_limits_group;
+mod psse;
+mod shunt_compensator;
+mod snapshot;
+mod stream;
+mod tabular;
 mod temporary_limit;
+mod three_windings_transformer;
+mod ucte;
+mod version;
+mod xml;
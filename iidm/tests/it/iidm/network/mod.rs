@@ -35,6 +35,7 @@ fn create_test_network() -> Network {
                 geographical_tags: vec!["region1".to_string()],
                 voltage_levels: vec![],
                 two_windings_transformers: vec![],
+                unknown_fields: UnknownFields::new(),
             },
             Substation {
                 id: "sub2".to_string(),
@@ -43,6 +44,7 @@ fn create_test_network() -> Network {
                 geographical_tags: vec!["region2".to_string()],
                 voltage_levels: vec![],
                 two_windings_transformers: vec![],
+                unknown_fields: UnknownFields::new(),
             },
         ],
         lines: vec![],
@@ -53,6 +55,9 @@ fn create_test_network() -> Network {
         dangling_lines: vec![],
         tie_lines: vec![],
         hvdc_lines: vec![],
+        areas: vec![],
+        extensions: vec![],
+        unknown_fields: UnknownFields::new(),
     }
 }
 
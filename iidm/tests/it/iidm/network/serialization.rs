@@ -138,3 +138,56 @@ fn test_serialization_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_bus_state_variables_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let bus: Bus = serde_json::from_str(r#"{"id": "NHV1", "v": 402.14, "angle": 0.0}"#)?;
+    assert_eq!(bus.v, Some(402.14));
+    assert_eq!(bus.angle, Some(0.0));
+
+    let unsolved: Bus = serde_json::from_str(r#"{"id": "NHV2"}"#)?;
+    assert!(unsolved.v.is_none());
+    assert!(unsolved.angle.is_none());
+    assert_eq!(serde_json::to_string(&unsolved)?, r#"{"id":"NHV2"}"#);
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_fields_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let test_network = std::fs::read_to_string(NETWORK_FILE)?;
+    let mut value: serde_json::Value = serde_json::from_str(&test_network)?;
+    value["vendorVersion"] = "2.1".into();
+    value["substations"][0]["fictitious"] = true.into();
+    value["substations"][0]["voltageLevels"][0]["generators"][0]["condenser"] = false.into();
+    value["substations"][1]["twoWindingsTransformers"][0]["ratioTapChanger"]["steps"][0]
+        ["vendor"] = serde_json::json!({ "code": 12 });
+    value["lines"][0]["fictitious"] = true.into();
+
+    let network: Network = serde_json::from_value(value.clone())?;
+    assert_eq!(network.unknown_fields["vendorVersion"], "2.1");
+    assert_eq!(network.lines[0].unknown_fields["fictitious"], true);
+
+    // Modelled fields, including flattened ones, aren't duplicated in the side map
+    let generator = &network.substations[0].voltage_levels[0]
+        .generators
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!(generator.unknown_fields.len(), 1);
+    assert!(generator.reactive_limits.is_some());
+
+    let exported = serde_json::to_value(&network)?;
+    assert_eq!(exported["vendorVersion"], value["vendorVersion"]);
+    assert_eq!(exported["substations"][0]["fictitious"], true);
+    assert_eq!(
+        exported["substations"][0]["voltageLevels"][0]["generators"][0],
+        value["substations"][0]["voltageLevels"][0]["generators"][0]
+    );
+    assert_eq!(
+        exported["substations"][1]["twoWindingsTransformers"][0]["ratioTapChanger"]["steps"][0],
+        value["substations"][1]["twoWindingsTransformers"][0]["ratioTapChanger"]["steps"][0]
+    );
+    assert_eq!(exported["lines"][0]["fictitious"], true);
+
+    Ok(())
+}
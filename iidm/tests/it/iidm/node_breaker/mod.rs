@@ -0,0 +1,95 @@
+use iidm::Network;
+
+mod serialization;
+mod validation;
+
+const VALID_NODE_BREAKER_NETWORK_JSON: &str = r#"{
+        "version": "1.12",
+        "id": "nb",
+        "caseDate": "2013-01-15T18:45:00.000+01:00",
+        "forecastDistance": 0,
+        "sourceFormat": "test",
+        "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+        "substations": [
+            {
+                "id": "S1",
+                "country": "FR",
+                "tso": "RTE",
+                "geographicalTags": [],
+                "voltageLevels": [
+                    {
+                        "id": "VL1",
+                        "nominalV": 400.0,
+                        "topologyKind": "NODE_BREAKER",
+                        "generators": [
+                            {
+                                "id": "GEN",
+                                "energySource": "NUCLEAR",
+                                "minP": 0.0,
+                                "maxP": 1000.0,
+                                "voltageRegulatorOn": true,
+                                "targetP": 500.0,
+                                "targetV": 400.0,
+                                "targetQ": 0.0,
+                                "node": 1
+                            }
+                        ],
+                        "loads": [
+                            {
+                                "id": "LOAD",
+                                "loadType": "UNDEFINED",
+                                "p0": 300.0,
+                                "q0": 50.0,
+                                "node": 2
+                            }
+                        ],
+                        "nodeBreakerTopology": {
+                            "nodes": [{ "id": 0 }, { "id": 1 }, { "id": 2 }, { "id": 3 }],
+                            "switches": [
+                                {
+                                    "id": "BRK",
+                                    "kind": "BREAKER",
+                                    "open": false,
+                                    "retained": true,
+                                    "node1": 0,
+                                    "node2": 1,
+                                    "voltageLevelId": "VL1"
+                                }
+                            ],
+                            "internalConnections": [{ "node1": 0, "node2": 2 }]
+                        }
+                    },
+                    {
+                        "id": "VL2",
+                        "nominalV": 400.0,
+                        "topologyKind": "BUS_BREAKER",
+                        "busBreakerTopology": {
+                            "buses": [{ "id": "B2" }],
+                            "switches": []
+                        }
+                    }
+                ],
+                "twoWindingsTransformers": []
+            }
+        ],
+        "lines": [
+            {
+                "id": "L1",
+                "r": 1.0,
+                "x": 10.0,
+                "g1": 0.0,
+                "b1": 0.0,
+                "g2": 0.0,
+                "b2": 0.0,
+                "voltageLevelId1": "VL1",
+                "node1": 3,
+                "voltageLevelId2": "VL2",
+                "bus2": "B2",
+                "connectableBus2": "B2"
+            }
+        ]
+    }"#;
+
+fn create_node_breaker_network() -> Network {
+    serde_json::from_str(VALID_NODE_BREAKER_NETWORK_JSON).unwrap()
+}
@@ -0,0 +1,43 @@
+use iidm::Network;
+
+use super::{create_node_breaker_network, VALID_NODE_BREAKER_NETWORK_JSON};
+
+#[test]
+fn test_deserialize_node_connections() {
+    let network: Network = serde_json::from_str(VALID_NODE_BREAKER_NETWORK_JSON).unwrap();
+    let voltage_level = &network.substations[0].voltage_levels[0];
+
+    let generator = &voltage_level.generators.as_ref().unwrap()[0];
+    assert_eq!(generator.node, Some(1));
+    assert!(generator.bus.is_empty());
+    assert!(generator.connectable_bus.is_empty());
+
+    let switch = &voltage_level
+        .node_breaker_topology
+        .as_ref()
+        .unwrap()
+        .switches[0];
+    assert_eq!((switch.node1, switch.node2), (Some(0), Some(1)));
+
+    let line = &network.lines[0];
+    assert_eq!(line.node1, Some(3));
+    assert_eq!(line.node2, None);
+    assert_eq!(line.bus2, "B2");
+}
+
+#[test]
+fn test_serialize_skips_missing_connections() {
+    let network = create_node_breaker_network();
+    let json = serde_json::to_value(&network).unwrap();
+
+    let generator = &json["substations"][0]["voltageLevels"][0]["generators"][0];
+    assert_eq!(generator["node"], 1);
+    assert!(generator.get("bus").is_none());
+    assert!(generator.get("connectableBus").is_none());
+
+    let line = &json["lines"][0];
+    assert_eq!(line["node1"], 3);
+    assert!(line.get("node2").is_none());
+    assert!(line.get("bus1").is_none());
+    assert_eq!(line["bus2"], "B2");
+}
@@ -0,0 +1,138 @@
+use iidm::NodeConnectionError;
+use serde_json::json;
+
+use super::create_node_breaker_network;
+
+#[test]
+fn test_valid_node_connections() {
+    let network = create_node_breaker_network();
+    assert!(network.validate_node_connections().is_empty());
+}
+
+#[test]
+fn test_voltage_level_nodes() {
+    let network = create_node_breaker_network();
+    let voltage_levels = &network.substations[0].voltage_levels;
+
+    let mut nodes: Vec<i32> = voltage_levels[0].nodes().unwrap().into_iter().collect();
+    nodes.sort();
+    assert_eq!(nodes, vec![0, 1, 2, 3]);
+    assert!(voltage_levels[1].nodes().is_none());
+}
+
+#[test]
+fn test_unknown_node() {
+    let mut network = create_node_breaker_network();
+    network.substations[0].voltage_levels[0]
+        .loads
+        .as_mut()
+        .unwrap()[0]
+        .node = Some(42);
+    network.lines[0].node1 = Some(7);
+
+    assert_eq!(
+        network.validate_node_connections(),
+        vec![
+            NodeConnectionError::UnknownNode {
+                equipment: "LOAD".to_string(),
+                voltage_level: "VL1".to_string(),
+                node: 42,
+            },
+            NodeConnectionError::UnknownNode {
+                equipment: "L1".to_string(),
+                voltage_level: "VL1".to_string(),
+                node: 7,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_missing_node() {
+    let mut network = create_node_breaker_network();
+    network.substations[0].voltage_levels[0]
+        .generators
+        .as_mut()
+        .unwrap()[0]
+        .node = None;
+
+    assert_eq!(
+        network.validate_node_connections(),
+        vec![NodeConnectionError::MissingNode {
+            equipment: "GEN".to_string(),
+            voltage_level: "VL1".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_bus_breaker_connections_not_checked() {
+    let mut network = create_node_breaker_network();
+    network.lines[0].node2 = Some(99);
+    assert!(network.validate_node_connections().is_empty());
+}
+
+#[test]
+fn test_network_injections_node_connections() {
+    let mut network = create_node_breaker_network();
+    network.shunt_compensators.push(
+        serde_json::from_value(json!({
+            "id": "SHUNT",
+            "sectionCount": 1,
+            "shuntLinearModel": { "bPerSection": 1e-5, "maximumSectionCount": 1 },
+            "voltageLevelId": "VL1",
+            "node": 42
+        }))
+        .unwrap(),
+    );
+    network.static_var_compensators.push(
+        serde_json::from_value(json!({
+            "id": "SVC",
+            "bMin": -1e-3,
+            "bMax": 1e-3,
+            "regulationMode": "VOLTAGE",
+            "voltageSetpoint": 400.0,
+            "reactivePowerSetpoint": 0.0,
+            "voltageLevelId": "VL1"
+        }))
+        .unwrap(),
+    );
+    network.dangling_lines.push(
+        serde_json::from_value(json!({
+            "id": "DL",
+            "p0": 10.0,
+            "q0": 0.0,
+            "r": 1.0,
+            "x": 10.0,
+            "g": 0.0,
+            "b": 0.0,
+            "voltageLevelId": "VL1",
+            "node": 3
+        }))
+        .unwrap(),
+    );
+
+    assert_eq!(
+        network.validate_node_connections(),
+        vec![
+            NodeConnectionError::UnknownNode {
+                equipment: "SHUNT".to_string(),
+                voltage_level: "VL1".to_string(),
+                node: 42,
+            },
+            NodeConnectionError::MissingNode {
+                equipment: "SVC".to_string(),
+                voltage_level: "VL1".to_string(),
+            },
+        ]
+    );
+
+    network.dangling_lines[0].node = Some(8);
+    assert!(network
+        .validate_node_connections()
+        .contains(&NodeConnectionError::UnknownNode {
+            equipment: "DL".to_string(),
+            voltage_level: "VL1".to_string(),
+            node: 8,
+        }));
+}
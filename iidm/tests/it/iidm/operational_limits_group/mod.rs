@@ -0,0 +1,48 @@
+use iidm::Line;
+
+mod serialization;
+mod update;
+
+const LINE_WITH_GROUPS_JSON: &str = r#"{
+            "id": "NHV1_NHV2_1",
+            "r": 3.0,
+            "x": 33.0,
+            "g1": 0.0,
+            "b1": 1.93E-4,
+            "g2": 0.0,
+            "b2": 1.93E-4,
+            "voltageLevelId1": "VLHV1",
+            "bus1": "NHV1",
+            "connectableBus1": "NHV1",
+            "voltageLevelId2": "VLHV2",
+            "bus2": "NHV2",
+            "connectableBus2": "NHV2",
+            "selectedOperationalLimitsGroupId1": "SUMMER",
+            "operationalLimitsGroups1": [
+                {
+                    "id": "SUMMER",
+                    "currentLimits": {
+                        "permanentLimit": 931.0,
+                        "temporaryLimits": [
+                            { "name": "20'", "acceptableDuration": 1200, "value": 1200.0 }
+                        ]
+                    },
+                    "activePowerLimits": { "permanentLimit": 600.0 }
+                },
+                {
+                    "id": "WINTER",
+                    "currentLimits": { "permanentLimit": 1100.0, "temporaryLimits": [] },
+                    "apparentPowerLimits": { "permanentLimit": 700.0 }
+                }
+            ],
+            "operationalLimitsGroups2": [
+                {
+                    "id": "DEFAULT",
+                    "currentLimits": { "permanentLimit": 1000.0, "temporaryLimits": [] }
+                }
+            ]
+        }"#;
+
+fn create_line_with_groups() -> Line {
+    serde_json::from_str(LINE_WITH_GROUPS_JSON).unwrap()
+}
@@ -0,0 +1,63 @@
+use iidm::{Line, OperationalLimitsHolder, Side};
+
+use super::{create_line_with_groups, LINE_WITH_GROUPS_JSON};
+
+#[test]
+fn test_deserialize_groups() {
+    let line: Line = serde_json::from_str(LINE_WITH_GROUPS_JSON).unwrap();
+    assert_eq!(line.operational_limits_groups1.len(), 2);
+    assert_eq!(line.operational_limits_groups2.len(), 1);
+    assert_eq!(
+        line.selected_operational_limits_group_id1.as_deref(),
+        Some("SUMMER")
+    );
+    assert!(line.selected_operational_limits_group_id2.is_none());
+
+    let summer = &line.operational_limits_groups1[0];
+    assert_eq!(
+        summer.current_limits.as_ref().unwrap().permanent_limit,
+        931.0
+    );
+    assert_eq!(
+        summer.active_power_limits.as_ref().unwrap().permanent_limit,
+        600.0
+    );
+    assert!(summer.apparent_power_limits.is_none());
+
+    let winter = &line.operational_limits_groups1[1];
+    assert_eq!(
+        winter
+            .apparent_power_limits
+            .as_ref()
+            .unwrap()
+            .permanent_limit,
+        700.0
+    );
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let line = create_line_with_groups();
+    let json = serde_json::to_value(&line).unwrap();
+    assert!(json.get("operationalLimitsGroups1").is_some());
+    assert!(json.get("selectedOperationalLimitsGroupId2").is_none());
+
+    let deserialized: Line = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&deserialized).unwrap(),
+        serde_json::to_value(&line).unwrap()
+    );
+}
+
+#[test]
+fn test_selected_group() {
+    let line = create_line_with_groups();
+
+    let selected = line.selected_operational_limits_group(Side::One).unwrap();
+    assert_eq!(selected.id, "SUMMER");
+    assert!(line.selected_operational_limits_group(Side::Two).is_none());
+    assert!(line
+        .operational_limits_group(Side::Two, "DEFAULT")
+        .is_some());
+    assert!(line.operational_limits_groups(Side::Three).is_empty());
+}
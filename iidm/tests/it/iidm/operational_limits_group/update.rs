@@ -0,0 +1,181 @@
+use super::*;
+use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+use iidm::*;
+
+#[test]
+fn test_update_selected_group() {
+    let mut line = create_line_with_groups();
+    line.update(LineUpdater {
+        selected_operational_limits_group_id1: Some(Some("WINTER".to_string())),
+        ..Default::default()
+    });
+
+    let selected = line.selected_operational_limits_group(Side::One).unwrap();
+    assert_eq!(selected.id, "WINTER");
+    assert_eq!(
+        selected.current_limits.as_ref().unwrap().permanent_limit,
+        1100.0
+    );
+}
+
+#[test]
+fn test_json_update_selected_group() {
+    let json = r#"{"selectedOperationalLimitsGroupId2": "DEFAULT"}"#;
+    let validation = LineUpdater::validate_json(json);
+    assert!(
+        validation.is_ok(),
+        "Validation should succeed with a valid JSON: {:?}",
+        validation.err()
+    );
+
+    let mut line = create_line_with_groups();
+    line.update(validation.unwrap());
+
+    let selected = line.selected_operational_limits_group(Side::Two).unwrap();
+    assert_eq!(selected.id, "DEFAULT");
+    assert_eq!(
+        line.selected_operational_limits_group_id1.as_deref(),
+        Some("SUMMER")
+    );
+}
+
+#[test]
+fn test_update_group_limits() {
+    let json = r#"{"currentLimits": {"permanentLimit": 950.0, "temporaryLimits": []}}"#;
+    let validation = OperationalLimitsGroupUpdater::validate_json(json);
+    assert!(validation.is_ok(), "{:?}", validation.err());
+
+    let mut group = create_line_with_groups()
+        .operational_limits_groups1
+        .remove(0);
+    group.update(validation.unwrap());
+
+    assert_eq!(group.id, "SUMMER");
+    assert_eq!(group.current_limits.unwrap().permanent_limit, 950.0);
+    assert!(group.active_power_limits.is_some());
+}
+
+#[test]
+fn test_handle_selected_group_update() {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<Events<UpdateErrorEvent>>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    world.init_resource::<Events<UpdateEvent<Line>>>();
+    world.init_resource::<AssetRegistry>();
+    schedule.add_systems(handle_register_events::<Line>);
+    schedule.add_systems(handle_update_events::<Line>);
+
+    // Register line with event
+    let line = create_line_with_groups();
+    let mut event_writer = world.resource_mut::<Events<RegisterEvent<Line>>>();
+    event_writer.send(RegisterEvent {
+        id: line.id.clone(),
+        component: line,
+    });
+    schedule.run(&mut world);
+
+    // Switch to the winter group
+    let mut event_writer = world.resource_mut::<Events<UpdateEvent<Line>>>();
+    event_writer.send(UpdateEvent {
+        id: "NHV1_NHV2_1".to_string(),
+        update: LineUpdater {
+            selected_operational_limits_group_id1: Some(Some("WINTER".to_string())),
+            ..Default::default()
+        },
+    });
+    schedule.run(&mut world);
+
+    // Check changed state
+    let registry = world.resource::<AssetRegistry>();
+    let entity = registry.find("NHV1_NHV2_1").unwrap();
+    let line = world.entity(entity).get::<Line>().unwrap();
+    let selected = line.selected_operational_limits_group(Side::One).unwrap();
+    assert_eq!(selected.id, "WINTER");
+}
+
+#[test]
+fn test_json_update_unknown_selected_group() {
+    let mut line = create_line_with_groups();
+    let result = line.update_from_json(r#"{"selectedOperationalLimitsGroupId1": "AUTUMN"}"#);
+
+    assert!(matches!(
+        result,
+        Err(LineError::SelectedOperationalLimitsGroupId1(_))
+    ));
+    assert_eq!(
+        line.selected_operational_limits_group_id1.as_deref(),
+        Some("SUMMER")
+    );
+}
+
+#[test]
+fn test_update_groups_without_selected_group() {
+    let line = create_line_with_groups();
+    let updates = LineUpdater {
+        operational_limits_groups2: Some(Vec::new()),
+        selected_operational_limits_group_id2: Some(Some("DEFAULT".to_string())),
+        ..Default::default()
+    };
+    assert!(matches!(
+        line.check_update(&updates),
+        Err(LineError::SelectedOperationalLimitsGroupId2(_))
+    ));
+
+    // Clearing the selection is always allowed
+    let updates = LineUpdater {
+        selected_operational_limits_group_id1: Some(None),
+        ..Default::default()
+    };
+    assert!(line.check_update(&updates).is_ok());
+}
+
+#[test]
+fn test_handle_unknown_selected_group_update() {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<Events<UpdateErrorEvent>>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    world.init_resource::<Events<UpdateEvent<Line>>>();
+    world.init_resource::<AssetRegistry>();
+    schedule.add_systems(handle_register_events::<Line>);
+    schedule.add_systems(handle_update_events::<Line>);
+
+    let line = create_line_with_groups();
+    let mut event_writer = world.resource_mut::<Events<RegisterEvent<Line>>>();
+    event_writer.send(RegisterEvent {
+        id: line.id.clone(),
+        component: line,
+    });
+    schedule.run(&mut world);
+
+    let mut event_writer = world.resource_mut::<Events<UpdateEvent<Line>>>();
+    event_writer.send(UpdateEvent {
+        id: "NHV1_NHV2_1".to_string(),
+        update: LineUpdater {
+            selected_operational_limits_group_id1: Some(Some("AUTUMN".to_string())),
+            ..Default::default()
+        },
+    });
+    schedule.run(&mut world);
+
+    // The update is reported and not applied
+    let error_events = world.resource::<Events<UpdateErrorEvent>>();
+    let errors: Vec<_> = error_events
+        .get_cursor()
+        .read(error_events)
+        .cloned()
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].error_type, ErrorType::InvalidUpdate(_)));
+
+    let registry = world.resource::<AssetRegistry>();
+    let entity = registry.find("NHV1_NHV2_1").unwrap();
+    let line = world.entity(entity).get::<Line>().unwrap();
+    assert_eq!(
+        line.selected_operational_limits_group_id1.as_deref(),
+        Some("SUMMER")
+    );
+}
@@ -0,0 +1,206 @@
+use iidm::libs::psse::{from_psse, PsseError};
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_network_import() -> Result<(), Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(NETWORK_RAW_FILE)?;
+    let import = from_psse("network", &raw)?;
+    let network = &import.network;
+
+    assert_eq!(network.source_format, "PSS/E");
+    // Buses linked by transformers are in the same substation
+    assert_eq!(network.substations.len(), 3);
+    assert_eq!(network.substations[0].id, "SUB-1");
+    assert_eq!(network.substations[0].voltage_levels.len(), 2);
+    assert_eq!(network.substations[1].voltage_levels.len(), 3);
+
+    let bus = &network.substations[0].voltage_levels[0]
+        .bus_breaker_topology
+        .as_ref()
+        .unwrap()
+        .buses[0];
+    assert_eq!(bus.id, "BUS-1");
+    assert_eq!(bus.v, Some(1.04 * 18.0));
+    assert_eq!(bus.unknown_fields["name"], "GEN1");
+
+    // Constant admittance loads are added at 1 p.u. voltage
+    let voltage_level = &network.substations[1].voltage_levels[2];
+    let load = &voltage_level.loads.as_ref().unwrap()[0];
+    assert_eq!(load.id, "LOAD-5-1");
+    assert_eq!((load.p0, load.q0), (55.0, 22.0));
+
+    let generator = &network.substations[0].voltage_levels[0]
+        .generators
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!(generator.id, "GEN-1-1");
+    assert_eq!((generator.min_p, generator.max_p), (0.0, 250.0));
+    assert!((generator.target_v - 1.04 * 18.0).abs() < 1e-9);
+    assert!(generator.voltage_regulator_on);
+
+    // Remote regulations target the first equipment of the regulated bus, in its voltage
+    let generator = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .flat_map(|voltage_level| voltage_level.generators.iter().flatten())
+        .find(|generator| generator.id == "GEN-4-1")
+        .unwrap();
+    let regulating_terminal = generator.regulating_terminal.as_ref().unwrap();
+    assert_eq!(regulating_terminal.id, "LOAD-5-1");
+    assert_eq!(regulating_terminal.side, None);
+    assert!((generator.target_v - 1.025 * 115.0).abs() < 1e-9);
+
+    // Branches to isolated buses are disconnected from them
+    assert_eq!(network.lines.len(), 2);
+    let line = &network.lines[0];
+    assert_eq!(line.id, "LINE-2-3-1");
+    let base_impedance = 230.0 * 230.0 / 100.0;
+    assert!((line.x - 0.085 * base_impedance).abs() < 1e-9);
+    assert!((line.b1 - 0.176 / 2.0 / base_impedance).abs() < 1e-12);
+    let limits = line.selected_operational_limits_group(Side::Two).unwrap();
+    let permanent_limit = limits.current_limits.as_ref().unwrap().permanent_limit;
+    assert!((permanent_limit - 250.0e3 / (3f64.sqrt() * 230.0)).abs() < 1e-9);
+    assert!(!network.lines[1].bus1.is_empty());
+    assert!(network.lines[1].bus2.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_transformers_import() -> Result<(), Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(NETWORK_RAW_FILE)?;
+    let network = from_psse("network", &raw)?.network;
+
+    let transformer = &network.substations[0].two_windings_transformers[0];
+    assert_eq!(transformer.id, "TWT-1-2-1");
+    assert!((transformer.rated_u1 - 1.05 * 18.0).abs() < 1e-9);
+    assert_eq!(transformer.rated_u2, 230.0);
+    assert!((transformer.x - 0.0625 * 230.0 * 230.0 / 100.0).abs() < 1e-9);
+
+    // Voltage controls become ratio tap changers whose positions span the ratio limits
+    let tap_changer = transformer.ratio_tap_changer.as_ref().unwrap();
+    assert!(tap_changer.regulating);
+    assert_eq!(tap_changer.steps.len(), 5);
+    assert_eq!(tap_changer.tap_position, 3);
+    assert!((tap_changer.steps[3].rho - 1.0).abs() < 1e-9);
+    assert!((tap_changer.regulation_value - 1.03 * 230.0).abs() < 1e-9);
+    assert_eq!(tap_changer.terminal_ref.side, Some(Side::Two));
+
+    let transformer = &network.three_windings_transformers[0];
+    assert_eq!(transformer.id, "TWT-3-4-5-1");
+    assert_eq!(transformer.rated_u0(), 230.0);
+    assert_eq!(transformer.leg2.rated_u, 13.8);
+    assert_eq!(transformer.leg3.voltage_level_id, "VL-5");
+    // Pair impedances are converted to a star
+    let base_impedance = 230.0 * 230.0 / 100.0;
+    assert!((transformer.leg1.x - 0.03 * base_impedance).abs() < 1e-9);
+    let tap_changer = transformer.leg2.phase_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.steps[0].alpha, -30.0);
+    Ok(())
+}
+
+#[test]
+fn test_areas_and_shunts_import() -> Result<(), Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(NETWORK_RAW_FILE)?;
+    let network = from_psse("network", &raw)?.network;
+
+    assert_eq!(network.areas.len(), 4);
+    let area = &network.areas[0];
+    assert_eq!(area.id, "AREA-1");
+    assert_eq!(area.name.as_deref(), Some("NORTH"));
+    assert_eq!(area.interchange_target, Some(40.0));
+    assert_eq!(area.voltage_level_ids, ["VL-1", "VL-2"]);
+    let zone = &network.areas[3];
+    assert_eq!(zone.id, "ZONE-2");
+    assert_eq!(zone.area_type, "Zone");
+    assert_eq!(zone.voltage_level_ids, ["VL-4", "VL-5", "VL-6"]);
+
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.id, "SHUNT-2-1");
+    assert_eq!(shunt.admittance(), Some((50.0 / 230.0 / 230.0, 0.0)));
+
+    // Switched shunt blocks become cumulated sections, the closest to the initial
+    // susceptance being in service
+    let shunt = &network.shunt_compensators[1];
+    assert_eq!(shunt.id, "SWSHUNT-5-1");
+    assert_eq!(shunt.model.maximum_section_count(), 3);
+    assert_eq!(shunt.section_count, 2);
+    assert_eq!(shunt.admittance(), Some((20.0 / 115f64.powi(2), 0.0)));
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_records() -> Result<(), Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(NETWORK_RAW_FILE)?;
+    let warnings = from_psse("network", &raw)?.warnings;
+
+    assert_eq!(
+        warnings,
+        [
+            "Two-terminal DC line data is not supported, 3 lines skipped",
+            "Owner data is not supported, 1 lines skipped",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_v35_import() -> Result<(), Box<dyn std::error::Error>> {
+    let import = from_psse("breaker", V35_CASE)?;
+    let network = &import.network;
+
+    // Buses linked by switching devices are in the same voltage level
+    assert_eq!(network.substations.len(), 2);
+    let voltage_level = &network.substations[0].voltage_levels[0];
+    assert_eq!(voltage_level.id, "VL-1");
+    let topology = voltage_level.bus_breaker_topology.as_ref().unwrap();
+    assert_eq!(topology.buses.len(), 2);
+    let switch = &topology.switches[0];
+    assert_eq!(switch.id, "SW-1-2-1");
+    assert!(!switch.open);
+
+    let line = &network.lines[0];
+    assert_eq!(line.voltage_level_id1, "VL-1");
+    let limits = line.selected_operational_limits_group(Side::One).unwrap();
+    let permanent_limit = limits.current_limits.as_ref().unwrap().permanent_limit;
+    assert!((permanent_limit - 1000.0e3 / (3f64.sqrt() * 400.0)).abs() < 1e-9);
+
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.id, "SWSHUNT-3-1");
+    assert_eq!(shunt.section_count, 0);
+    assert_eq!(shunt.model.maximum_section_count(), 2);
+
+    assert_eq!(
+        import.warnings,
+        ["Substation data is not supported, 2 lines skipped"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_psse_unsupported_version() {
+    let raw = V35_CASE.replace("100.00, 35,", "100.00, 34,");
+    let result = from_psse("unsupported", &raw);
+    assert!(matches!(result, Err(PsseError::Syntax { line: 1, .. })));
+}
+
+#[test]
+fn test_psse_invalid_number() -> Result<(), Box<dyn std::error::Error>> {
+    let raw =
+        std::fs::read_to_string(NETWORK_RAW_FILE)?.replace("100.000,    35.000", "100.000,    x");
+    let result = from_psse("invalid", &raw);
+    assert!(matches!(result, Err(PsseError::Syntax { line: 11, .. })));
+    Ok(())
+}
+
+#[test]
+fn test_psse_unknown_bus() {
+    let raw = V35_CASE.replace("    1,     2,'1 ', 1.0E-4", "    1,     9,'1 ', 1.0E-4");
+    let result = from_psse("unknown", &raw);
+    assert!(matches!(
+        result,
+        Err(PsseError::UnknownBus { line: 19, bus: 9 })
+    ));
+}
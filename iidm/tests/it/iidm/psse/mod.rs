@@ -0,0 +1,49 @@
+mod import;
+
+const NETWORK_RAW_FILE: &str = "tests/data/network.raw";
+
+/// Version 35 case with system-wide data, field annotations, two buses linked by a breaker
+/// and substation data
+const V35_CASE: &str = " 0,   100.00, 35, 0, 0, 60.00     / PSS(R)E-35.0
+BREAKER CASE
+
+GENERAL, THRSHZ=0.0001, PQBRAK=0.7, BLOWUP=5.0
+RATING, 1, \"RATE1 \", \"RATING SET 1                    \"
+0 / END OF SYSTEM-WIDE DATA, BEGIN BUS DATA
+@!   I,'NAME        ', BASKV, IDE,AREA,ZONE,OWNER, VM,        VA,    NVHI,   NVLO,   EVHI,   EVLO
+    1,'BUS1A       ', 400.0000,3,   1,   1,   1,1.00000,   0.0000,1.10000,0.90000,1.10000,0.90000
+    2,'BUS1B       ', 400.0000,1,   1,   1,   1,1.00000,   0.0000,1.10000,0.90000,1.10000,0.90000
+    3,'BUS2        ', 400.0000,1,   1,   1,   1,0.99000,  -1.0000,1.10000,0.90000,1.10000,0.90000
+0 / END OF BUS DATA, BEGIN LOAD DATA
+    3,'1 ',1,   1,   1,   200.000,    50.000,     0.000,     0.000,     0.000,     0.000,   1,1,0,0,0
+0 / END OF LOAD DATA, BEGIN FIXED SHUNT DATA
+0 / END OF FIXED SHUNT DATA, BEGIN GENERATOR DATA
+    1,'1 ',   200.000,     0.000,   100.000,  -100.000,1.00000,     0,     0,   250.000, 0.0, 0.2, 0.0, 0.0,1.00000,1,  100.0,   300.000,     0.000,   1,1.0000
+0 / END OF GENERATOR DATA, BEGIN BRANCH DATA
+    2,     3,'1 ', 1.0E-3, 1.0E-2, 0.02,'LINE 2-3    ', 1000.0, 1100.0, 1200.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,1,1,   0.00,   1,1.0000
+0 / END OF BRANCH DATA, BEGIN SYSTEM SWITCHING DEVICE DATA
+    1,     2,'1 ', 1.0E-4, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,1,1,0,2,'BRK 1       '
+0 / END OF SYSTEM SWITCHING DEVICE DATA, BEGIN TRANSFORMER DATA
+0 / END OF TRANSFORMER DATA, BEGIN AREA DATA
+0 / END OF AREA DATA, BEGIN TWO-TERMINAL DC DATA
+0 / END OF TWO-TERMINAL DC DATA, BEGIN VSC DC LINE DATA
+0 / END OF VSC DC LINE DATA, BEGIN IMPEDANCE CORRECTION DATA
+0 / END OF IMPEDANCE CORRECTION DATA, BEGIN MULTI-TERMINAL DC DATA
+0 / END OF MULTI-TERMINAL DC DATA, BEGIN MULTI-SECTION LINE DATA
+0 / END OF MULTI-SECTION LINE DATA, BEGIN ZONE DATA
+0 / END OF ZONE DATA, BEGIN INTER-AREA TRANSFER DATA
+0 / END OF INTER-AREA TRANSFER DATA, BEGIN OWNER DATA
+0 / END OF OWNER DATA, BEGIN FACTS DEVICE DATA
+0 / END OF FACTS DEVICE DATA, BEGIN SWITCHED SHUNT DATA
+    3,'1 ',1,0,1,1.05000,0.95000,     0,     0,100.0,'            ',     0.00, 1,  2, -25.00
+0 / END OF SWITCHED SHUNT DATA, BEGIN GNE DEVICE DATA
+0 / END OF GNE DEVICE DATA, BEGIN INDUCTION MACHINE DATA
+0 / END OF INDUCTION MACHINE DATA, BEGIN SUBSTATION DATA
+    1,'SUB1        ',  0.0000,  0.0000, 0.1000
+    1,'NODE 1      ',    1,1,1.00000,   0.0000
+0 / END OF SUBSTATION NODE DATA, BEGIN SUBSTATION SWITCHING DEVICE DATA
+0 / END OF SUBSTATION SWITCHING DEVICE DATA, BEGIN SUBSTATION TERMINAL DATA
+0 / END OF SUBSTATION TERMINAL DATA
+0 / END OF SUBSTATION DATA
+Q
+";
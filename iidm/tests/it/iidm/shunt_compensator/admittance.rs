@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn test_linear_admittance() {
+    let mut shunt = create_linear_shunt();
+    assert_eq!(shunt.admittance(), Some((1e-5, 2e-6)));
+
+    shunt.section_count = 3;
+    let (b, g) = shunt.admittance().unwrap();
+    assert!((b - 3e-5).abs() < 1e-12);
+    assert!((g - 6e-6).abs() < 1e-12);
+
+    shunt.section_count = 0;
+    assert_eq!(shunt.admittance(), Some((0.0, 0.0)));
+}
+
+#[test]
+fn test_non_linear_admittance() {
+    let mut shunt = create_non_linear_shunt();
+    assert_eq!(shunt.admittance(), Some((3e-5, 2e-6)));
+
+    shunt.section_count = 3;
+    assert_eq!(shunt.admittance(), Some((4e-5, 0.0)));
+
+    shunt.section_count = 0;
+    assert_eq!(shunt.admittance(), Some((0.0, 0.0)));
+}
+
+#[test]
+fn test_admittance_out_of_model() {
+    let mut shunt = create_non_linear_shunt();
+    shunt.section_count = 4;
+    assert_eq!(shunt.admittance(), None);
+
+    let mut shunt = create_linear_shunt();
+    shunt.section_count = -1;
+    assert_eq!(shunt.admittance(), None);
+}
@@ -0,0 +1,38 @@
+use iidm::ShuntCompensator;
+
+mod admittance;
+mod serialization;
+
+const VALID_LINEAR_SHUNT_JSON: &str = r#"{
+            "id": "SHUNT",
+            "sectionCount": 1,
+            "bus": "B1",
+            "connectableBus": "B1",
+            "shuntLinearModel": {
+                "bPerSection": 1e-5,
+                "gPerSection": 2e-6,
+                "maximumSectionCount": 3
+            }
+        }"#;
+
+const VALID_NON_LINEAR_SHUNT_JSON: &str = r#"{
+            "id": "BANK",
+            "sectionCount": 2,
+            "bus": "B1",
+            "connectableBus": "B1",
+            "shuntNonLinearModel": {
+                "sections": [
+                    { "b": 1e-5, "g": 1e-6 },
+                    { "b": 3e-5, "g": 2e-6 },
+                    { "b": 4e-5 }
+                ]
+            }
+        }"#;
+
+fn create_linear_shunt() -> ShuntCompensator {
+    serde_json::from_str(VALID_LINEAR_SHUNT_JSON).unwrap()
+}
+
+fn create_non_linear_shunt() -> ShuntCompensator {
+    serde_json::from_str(VALID_NON_LINEAR_SHUNT_JSON).unwrap()
+}
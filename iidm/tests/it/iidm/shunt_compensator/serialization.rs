@@ -0,0 +1,109 @@
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_deserialize_linear_model() {
+    let shunt = create_linear_shunt();
+    match shunt.model {
+        ShuntCompensatorModel::Linear(model) => {
+            assert_eq!(model.b_per_section, 1e-5);
+            assert_eq!(model.g_per_section, Some(2e-6));
+            assert_eq!(model.maximum_section_count, 3);
+        }
+        other => panic!("Expected a linear model, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_deserialize_non_linear_model() {
+    let shunt = create_non_linear_shunt();
+    match shunt.model {
+        ShuntCompensatorModel::NonLinear(model) => {
+            assert_eq!(model.sections.len(), 3);
+            assert_eq!(model.sections[2].g, 0.0);
+        }
+        other => panic!("Expected a non-linear model, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_deserialize_without_model() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_LINEAR_SHUNT_JSON).unwrap();
+    value.as_object_mut().unwrap().remove("shuntLinearModel");
+    assert!(serde_json::from_value::<ShuntCompensator>(value).is_err());
+}
+
+#[test]
+fn test_serialize_iidm_attribute() {
+    let value = serde_json::to_value(create_linear_shunt()).unwrap();
+    assert_eq!(value["shuntLinearModel"]["maximumSectionCount"], 3);
+    assert!(value.get("shuntNonLinearModel").is_none());
+    assert!(value.get("model").is_none());
+
+    let value = serde_json::to_value(create_non_linear_shunt()).unwrap();
+    assert!(value["shuntNonLinearModel"]["sections"].is_array());
+    assert!(value.get("shuntLinearModel").is_none());
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let shunt = create_non_linear_shunt();
+    let json = serde_json::to_string(&shunt).unwrap();
+    let deserialized: ShuntCompensator = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&deserialized).unwrap(),
+        serde_json::to_value(&shunt).unwrap()
+    );
+}
+
+#[test]
+fn test_json_update_model() {
+    let json = r#"{"shuntNonLinearModel": {"sections": [{"b": 5e-5, "g": 0.0}]}}"#;
+    let validation = ShuntCompensatorUpdater::validate_json(json);
+    assert!(validation.is_ok(), "{:?}", validation.err());
+
+    let mut shunt = create_linear_shunt();
+    shunt.update(validation.unwrap());
+    assert_eq!(shunt.model.maximum_section_count(), 1);
+    assert_eq!(shunt.admittance(), Some((5e-5, 0.0)));
+}
+
+#[test]
+fn test_json_update_section_count() {
+    let validation = ShuntCompensatorUpdater::validate_json(r#"{"sectionCount": 3}"#);
+    assert!(validation.is_ok(), "{:?}", validation.err());
+
+    let mut shunt = create_linear_shunt();
+    shunt.update(validation.unwrap());
+    assert_eq!(shunt.section_count, 3);
+}
+
+#[test]
+fn test_json_update_linear_model() {
+    let json = r#"{"shuntLinearModel": {"bPerSection": 2e-5, "maximumSectionCount": 4}}"#;
+    let mut shunt = create_non_linear_shunt();
+    let result = shunt.update_from_json(json);
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert_eq!(shunt.model.maximum_section_count(), 4);
+
+    // The nested shape of the model is not an IIDM attribute
+    let json =
+        r#"{"model": {"shuntLinearModel": {"bPerSection": 2e-5, "maximumSectionCount": 4}}}"#;
+    assert!(ShuntCompensatorUpdater::validate_json(json).is_err());
+}
+
+#[test]
+fn test_json_update_invalid_model() {
+    let mut shunt = create_linear_shunt();
+    let maximum_section_count = shunt.model.maximum_section_count();
+    let malformed = r#"{"shuntLinearModel": {"bPerSection": "high"}}"#;
+    assert!(shunt.update_from_json(malformed).is_err());
+
+    let both = r#"{
+        "shuntLinearModel": {"bPerSection": 2e-5, "maximumSectionCount": 4},
+        "shuntNonLinearModel": {"sections": []}
+    }"#;
+    assert!(ShuntCompensatorUpdater::validate_json(both).is_err());
+    assert_eq!(shunt.model.maximum_section_count(), maximum_section_count);
+}
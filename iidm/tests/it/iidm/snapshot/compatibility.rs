@@ -0,0 +1,87 @@
+use iidm::libs::json::from_json;
+use iidm::libs::snapshot::{
+    from_snapshot, schema_hash, to_snapshot, SnapshotError, SNAPSHOT_VERSION,
+};
+use iidm::libs::version::IidmVersion;
+
+use super::*;
+
+fn snapshot() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    Ok(to_snapshot(&network)?)
+}
+
+#[test]
+fn test_reject_other_format_version() -> Result<(), Box<dyn std::error::Error>> {
+    let mut snapshot = snapshot()?;
+    snapshot[8..10].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+
+    match from_snapshot(&snapshot) {
+        Err(SnapshotError::FormatVersion { found, expected }) => {
+            assert_eq!(found, SNAPSHOT_VERSION + 1);
+            assert_eq!(expected, SNAPSHOT_VERSION);
+        }
+        other => panic!("Expected a format version error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reject_other_iidm_version() -> Result<(), Box<dyn std::error::Error>> {
+    let mut snapshot = snapshot()?;
+    snapshot[12..14].copy_from_slice(&11u16.to_le_bytes());
+
+    match from_snapshot(&snapshot) {
+        Err(error @ SnapshotError::IidmVersion { found, expected }) => {
+            assert_eq!(found, IidmVersion::new(1, 11));
+            assert_eq!(expected, IidmVersion::LATEST);
+            assert!(error.to_string().contains("IIDM 1.11"));
+        }
+        other => panic!("Expected an IIDM version error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reject_other_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let mut snapshot = snapshot()?;
+    let other = schema_hash() ^ 1;
+    snapshot[14..22].copy_from_slice(&other.to_le_bytes());
+
+    match from_snapshot(&snapshot) {
+        Err(SnapshotError::Schema { found, expected }) => {
+            assert_eq!(found, other);
+            assert_eq!(expected, schema_hash());
+        }
+        other => panic!("Expected a schema error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reject_other_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read(NETWORK_JSON_FILE)?;
+    assert!(matches!(
+        from_snapshot(&json),
+        Err(SnapshotError::NotSnapshot)
+    ));
+    assert!(matches!(
+        from_snapshot(b"IIDM"),
+        Err(SnapshotError::NotSnapshot)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_reject_truncated_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    let snapshot = snapshot()?;
+    assert!(matches!(
+        from_snapshot(&snapshot[..18]),
+        Err(SnapshotError::Io(_))
+    ));
+    assert!(matches!(
+        from_snapshot(&snapshot[..snapshot.len() / 2]),
+        Err(SnapshotError::Decode(_))
+    ));
+    Ok(())
+}
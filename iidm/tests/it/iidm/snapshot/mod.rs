@@ -0,0 +1,5 @@
+mod compatibility;
+mod roundtrip;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_XML_FILE: &str = "tests/data/network.xiidm";
@@ -0,0 +1,46 @@
+use iidm::libs::json::from_json;
+use iidm::libs::snapshot::{from_snapshot, to_snapshot, SnapshotHeader};
+use iidm::libs::version::IidmVersion;
+use iidm::libs::xml::from_xml;
+
+use super::*;
+
+#[test]
+fn test_snapshot_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let snapshot = to_snapshot(&network)?;
+
+    let restored = from_snapshot(&snapshot)?;
+    assert_eq!(
+        serde_json::to_value(&restored)?,
+        serde_json::to_value(&network)?
+    );
+    assert_eq!(restored.case_date, network.case_date);
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_roundtrip_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(&std::fs::read_to_string(NETWORK_XML_FILE)?)?;
+    let restored = from_snapshot(&to_snapshot(&network)?)?;
+    assert_eq!(
+        serde_json::to_value(&restored)?,
+        serde_json::to_value(&network)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_header() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let snapshot = to_snapshot(&network)?;
+
+    assert!(snapshot.starts_with(b"IIDMSNAP"));
+    let header = SnapshotHeader::read(&mut snapshot.as_slice())?;
+    assert_eq!(header, SnapshotHeader::current());
+    assert_eq!(header.iidm_version, IidmVersion::LATEST);
+
+    // The encoding is more compact than the JSON document
+    assert!(snapshot.len() < serde_json::to_vec(&network)?.len());
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use std::fs::File;
+
+use iidm::libs::error::IidmError;
+use iidm::libs::json::{from_json, to_json};
+use iidm::libs::stream::{register_iidm, register_json};
+use iidm::libs::version::IidmVersion;
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_register_streamed_network() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut world, mut schedule) = init_world();
+    let import = register_json(File::open(NETWORK_JSON_FILE)?, &mut world, &mut schedule)?;
+
+    assert_eq!(import.network.id, "sim1");
+    assert_eq!(import.network.version, "1.12");
+    assert_eq!(import.network.substations.len(), 2);
+    assert_eq!(import.network.lines.len(), 2);
+    assert_eq!(import.registered["substations"], 2);
+    assert_eq!(import.registered["lines"], 2);
+
+    // The streamed network registers the same entities as the whole one
+    let (mut expected_world, mut expected_schedule) = init_world();
+    from_json(&std::fs::read(NETWORK_JSON_FILE)?)?
+        .register(&mut expected_world, &mut expected_schedule);
+    assert_eq!(world.entities().len(), expected_world.entities().len());
+    let registry = world.resource::<AssetRegistry>();
+    for id in ["sim1", "P1", "VLGEN", "GEN", "NGEN_NHV1", "NHV1_NHV2_1"] {
+        assert!(registry.find(id).is_some(), "{} is not registered", id);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_register_compressed_stream() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut world, mut schedule) = init_world();
+    let import = register_json(File::open(NETWORK_GZIP_FILE)?, &mut world, &mut schedule)?;
+
+    assert_eq!(import.network.id, "sim1");
+    assert_eq!(import.registered["substations"], 2);
+    let mut lines = world.query::<&Line>();
+    assert_eq!(lines.iter(&world).count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_register_archived_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut world, mut schedule) = init_world();
+    let import = register_iidm(File::open(NETWORK_ZIP_FILE)?, &mut world, &mut schedule)?;
+
+    assert_eq!(import.registered["substations"], 2);
+    assert_eq!(import.registered["lines"], 2);
+    assert!(world
+        .resource::<AssetRegistry>()
+        .find(import.network.id.as_str())
+        .is_some());
+    Ok(())
+}
+
+#[test]
+fn test_register_archive_with_data_descriptors() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut world, mut schedule) = init_world();
+    let import = register_iidm(
+        File::open(NETWORK_DESCRIPTOR_ZIP_FILE)?,
+        &mut world,
+        &mut schedule,
+    )?;
+
+    assert_eq!(import.network.id, "sim1");
+    assert_eq!(import.registered["substations"], 2);
+    assert_eq!(import.registered["lines"], 2);
+    Ok(())
+}
+
+#[test]
+fn test_register_legacy_stream() -> Result<(), Box<dyn std::error::Error>> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(NETWORK_JSON_FILE)?)?;
+    value["version"] = "1.6".into();
+    value
+        .as_object_mut()
+        .unwrap()
+        .remove("minimumValidationLevel");
+    value["lines"][0]["currentLimits2"] = serde_json::json!({
+        "permanentLimit": 800.0,
+        "temporaryLimits": []
+    });
+    let line_id = value["lines"][0]["id"].as_str().unwrap().to_string();
+
+    let (mut world, mut schedule) = init_world();
+    let import = register_json(
+        serde_json::to_vec(&value)?.as_slice(),
+        &mut world,
+        &mut schedule,
+    )?;
+    assert_eq!(
+        import.network.minimum_validation_level,
+        "STEADY_STATE_HYPOTHESIS"
+    );
+
+    let entity = world.resource::<AssetRegistry>().find(&line_id).unwrap();
+    let line = world.entity(entity).get::<Line>().unwrap();
+    let group = line.selected_operational_limits_group(Side::Two).unwrap();
+    assert_eq!(
+        group.current_limits.as_ref().unwrap().permanent_limit,
+        800.0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_version_after_elements() {
+    let json = r#"{"id": "late", "substations": [], "lines": [{"id": "L"}], "version": "1.12"}"#;
+    let (mut world, mut schedule) = init_world();
+    assert!(matches!(
+        register_json(json.as_bytes(), &mut world, &mut schedule),
+        Err(IidmError::Malformed(_))
+    ));
+}
+
+#[test]
+fn test_invalid_element_registers_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(NETWORK_JSON_FILE)?)?;
+    value["lines"][1]["r"] = "not a number".into();
+
+    let (mut world, mut schedule) = init_world();
+    let result = register_json(
+        serde_json::to_vec(&value)?.as_slice(),
+        &mut world,
+        &mut schedule,
+    );
+    assert!(matches!(result, Err(IidmError::Json(_))));
+
+    // Elements read before the invalid one are not registered either
+    let mut lines = world.query::<&Line>();
+    assert_eq!(lines.iter(&world).count(), 0);
+    assert!(world.resource::<AssetRegistry>().find("sim1").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_register_exported_network() -> Result<(), Box<dyn std::error::Error>> {
+    // Exported documents write their version before the collections
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let json = serde_json::to_vec(&to_json(&network, IidmVersion::LATEST)?)?;
+
+    let (mut world, mut schedule) = init_world();
+    let import = register_json(json.as_slice(), &mut world, &mut schedule)?;
+    assert_eq!(import.registered["substations"], 2);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+mod import;
+
+use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+use iidm::*;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_GZIP_FILE: &str = "tests/data/network.json.gz";
+/// Archive of a readme followed by `network.xiidm`
+const NETWORK_ZIP_FILE: &str = "tests/data/network.zip";
+/// Archive of `network.json` whose entry sizes follow its data, as written by streaming writers
+const NETWORK_DESCRIPTOR_ZIP_FILE: &str = "tests/data/network-descriptor.zip";
+
+fn init_world() -> (World, Schedule) {
+    let mut world = World::default();
+    let mut schedule = Schedule::default();
+
+    world.init_resource::<AssetRegistry>();
+    world.init_resource::<Events<RegisterEvent<Network>>>();
+    world.init_resource::<Events<RegisterEvent<Substation>>>();
+    world.init_resource::<Events<RegisterEvent<VoltageLevel>>>();
+    world.init_resource::<Events<RegisterEvent<Bus>>>();
+    world.init_resource::<Events<RegisterEvent<Generator>>>();
+    world.init_resource::<Events<RegisterEvent<Load>>>();
+    world.init_resource::<Events<RegisterEvent<TwoWindingsTransformer>>>();
+    world.init_resource::<Events<RegisterEvent<Line>>>();
+    schedule.add_systems(handle_register_events::<Network>);
+    schedule.add_systems(handle_register_events::<Substation>);
+    schedule.add_systems(handle_register_events::<VoltageLevel>);
+    schedule.add_systems(handle_register_events::<Bus>);
+    schedule.add_systems(handle_register_events::<Generator>);
+    schedule.add_systems(handle_register_events::<Load>);
+    schedule.add_systems(handle_register_events::<TwoWindingsTransformer>);
+    schedule.add_systems(handle_register_events::<Line>);
+
+    (world, schedule)
+}
@@ -0,0 +1,187 @@
+use std::fs::File;
+
+use arrow_array::{
+    Array, Float64Array, Int32Array, ListArray, RecordBatch, StringArray, StructArray,
+};
+use arrow_schema::DataType;
+use iidm::libs::json::from_json;
+use iidm::libs::psse::from_psse;
+use iidm::libs::tabular::{arrow_schema, to_record_batch, write_parquet_files, TabularError};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use super::*;
+
+fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> &'a T {
+    batch
+        .column_by_name(name)
+        .unwrap_or_else(|| panic!("No {} column", name))
+        .as_any()
+        .downcast_ref()
+        .unwrap()
+}
+
+#[test]
+fn test_arrow_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let schema = arrow_schema(&table(&network, "generators")?);
+
+    let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names[..3], ["id", "substationId", "voltageLevelId"]);
+    assert_eq!(
+        schema.field_with_name("targetP")?.data_type(),
+        &DataType::Float64
+    );
+    assert_eq!(
+        schema.field_with_name("voltageRegulatorOn")?.data_type(),
+        &DataType::Boolean
+    );
+    assert_eq!(
+        schema.field_with_name("energySource")?.data_type(),
+        &DataType::Utf8
+    );
+    assert_eq!(
+        schema.field_with_name("node")?.data_type(),
+        &DataType::Int32
+    );
+    let DataType::Struct(fields) = schema.field_with_name("minMaxReactiveLimits")?.data_type()
+    else {
+        panic!("minMaxReactiveLimits is not a struct column");
+    };
+    assert!(fields.find("maxQ").is_some());
+    let DataType::Struct(fields) = schema
+        .field_with_name("reactiveCapabilityCurve")?
+        .data_type()
+    else {
+        panic!("reactiveCapabilityCurve is not a struct column");
+    };
+    assert!(matches!(
+        fields.find("points").unwrap().1.data_type(),
+        DataType::List(_)
+    ));
+
+    // Nested collections have their own tables
+    let schema = arrow_schema(&table(&network, "substations")?);
+    assert!(schema.field_with_name("voltageLevels").is_err());
+    let schema = arrow_schema(&table(&network, "voltageLevels")?);
+    assert!(schema.field_with_name("generators").is_err());
+    assert!(schema.field_with_name("busBreakerTopology").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_record_batch_nested_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let batch = to_record_batch(&table(&network, "twoWindingsTransformers")?)?;
+    assert_eq!(batch.num_rows(), 2);
+
+    let ids: &StringArray = column(&batch, "id");
+    let row = (0..2).find(|row| ids.value(*row) == "NHV2_NLOAD").unwrap();
+    let substations: &StringArray = column(&batch, "substationId");
+    assert_eq!(substations.value(row), "P2");
+
+    let tap_changers: &StructArray = column(&batch, "ratioTapChanger");
+    assert!(tap_changers.is_null(1 - row));
+    let tap_positions = tap_changers
+        .column_by_name("tapPosition")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(tap_positions.value(row), 1);
+    let steps = tap_changers
+        .column_by_name("steps")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    assert_eq!(steps.value_length(row), 3);
+    let rho = steps.value(row);
+    let rho = rho
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .unwrap()
+        .column_by_name("rho")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap()
+        .value(1);
+    assert!((rho - 1.0006666666666666).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_record_batch_int32_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let mut table = table(&network, "twoWindingsTransformers")?;
+    let value = &mut table.rows[0].value;
+    value["ratioTapChanger"]["tapPosition"] = (i64::from(i32::MAX) + 1).into();
+
+    match to_record_batch(&table) {
+        Err(TabularError::Int32Overflow(value)) => assert_eq!(value, i64::from(i32::MAX) + 1),
+        other => panic!("Expected an overflow error, got {:?}", other.map(|_| ())),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_batch_limits_and_flattened_models() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_psse("raw", &std::fs::read_to_string(NETWORK_RAW_FILE)?)?.network;
+
+    let batch = to_record_batch(&table(&network, "threeWindingsTransformers")?)?;
+    let groups: &ListArray = column(&batch, "operationalLimitsGroups1");
+    let groups = groups.value(0);
+    let groups = groups.as_any().downcast_ref::<StructArray>().unwrap();
+    let group_ids = groups
+        .column_by_name("id")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(group_ids.value(0), "DEFAULT");
+
+    let batch = to_record_batch(&table(&network, "shuntCompensators")?)?;
+    assert_eq!(batch.num_rows(), 2);
+    let linear: &StructArray = column(&batch, "shuntLinearModel");
+    let non_linear: &StructArray = column(&batch, "shuntNonLinearModel");
+    assert!(linear.is_valid(0) && linear.is_null(1));
+    assert!(non_linear.is_null(0) && non_linear.is_valid(1));
+    let voltage_levels: &StringArray = column(&batch, "voltageLevelId");
+    assert_eq!(voltage_levels.value(0), "VL-2");
+    Ok(())
+}
+
+#[test]
+fn test_write_parquet_files() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_json(&std::fs::read(NETWORK_JSON_FILE)?)?;
+    let directory = std::env::temp_dir().join(format!("iidm-parquet-{}", std::process::id()));
+    let paths = write_parquet_files(&network, &directory)?;
+    assert_eq!(paths.len(), 17);
+
+    let lines = table(&network, "lines")?;
+    let reader =
+        ParquetRecordBatchReaderBuilder::try_new(File::open(directory.join("lines.parquet"))?)?
+            .build()?;
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 2);
+    let names: Vec<&String> = batches[0]
+        .schema_ref()
+        .fields()
+        .iter()
+        .map(|field| field.name())
+        .collect();
+    let expected = arrow_schema(&lines);
+    assert_eq!(
+        names,
+        expected
+            .fields()
+            .iter()
+            .map(|f| f.name())
+            .collect::<Vec<_>>()
+    );
+    let r: &Float64Array = column(&batches[0], "r");
+    assert_eq!(r.value(0), 3.0);
+
+    std::fs::remove_dir_all(&directory)?;
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use iidm::libs::json::from_json;
+use iidm::libs::psse::from_psse;
+use iidm::libs::tabular::{write_csv, write_csv_files, Table};
+use iidm::Network;
+
+use super::*;
+
+fn read_network() -> Result<Network, Box<dyn std::error::Error>> {
+    Ok(from_json(&std::fs::read(NETWORK_JSON_FILE)?)?)
+}
+
+/// Header and records of a table written in CSV
+fn read_csv(table: &Table) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>> {
+    let mut csv = Vec::new();
+    write_csv(table, &mut csv)?;
+    let mut reader = ::csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(csv.as_slice());
+    let mut records = Vec::new();
+    for record in reader.records() {
+        records.push(record?.iter().map(str::to_string).collect());
+    }
+    Ok(records)
+}
+
+fn cell<'a>(records: &'a [Vec<String>], row: usize, column: &str) -> &'a str {
+    let index = records[0]
+        .iter()
+        .position(|header| header == column)
+        .unwrap_or_else(|| panic!("No {} column", column));
+    &records[row][index]
+}
+
+#[test]
+fn test_export_generators() -> Result<(), Box<dyn std::error::Error>> {
+    let records = read_csv(&table(&read_network()?, "generators")?)?;
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0][..3], ["id", "substationId", "voltageLevelId"]);
+    assert_eq!(records[1][..3], ["GEN", "P1", "VLGEN"]);
+    assert_eq!(cell(&records, 1, "energySource"), "OTHER");
+    assert_eq!(cell(&records, 1, "targetP"), "607.0");
+    assert_eq!(cell(&records, 1, "minMaxReactiveLimits.maxQ"), "9999.99");
+    // Missing optional values are empty cells
+    assert_eq!(cell(&records, 1, "p"), "");
+    Ok(())
+}
+
+#[test]
+fn test_export_flattened_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let network = read_network()?;
+    let records = read_csv(&table(&network, "twoWindingsTransformers")?)?;
+
+    let row = records
+        .iter()
+        .position(|record| record[0] == "NHV2_NLOAD")
+        .unwrap();
+    assert_eq!(cell(&records, row, "substationId"), "P2");
+    assert_eq!(cell(&records, row, "ratioTapChanger.tapPosition"), "1");
+    assert_eq!(
+        cell(&records, row, "ratioTapChanger.terminalRef.side"),
+        "TWO"
+    );
+    // Arrays are written in JSON
+    let steps: serde_json::Value =
+        serde_json::from_str(cell(&records, row, "ratioTapChanger.steps"))?;
+    assert_eq!(steps.as_array().unwrap().len(), 3);
+
+    let lines = table(&network, "lines")?;
+    let columns = lines.columns();
+    assert!(columns.contains(&"currentLimits1.permanentLimit".to_string()));
+    assert!(columns.contains(&"operationalLimitsGroups2".to_string()));
+    let records = read_csv(&lines)?;
+    assert_eq!(records[0][..3], ["id", "substationId1", "substationId2"]);
+    assert_eq!(records[1][..3], ["NHV1_NHV2_1", "P1", "P2"]);
+    Ok(())
+}
+
+#[test]
+fn test_export_without_nested_collections() -> Result<(), Box<dyn std::error::Error>> {
+    let network = read_network()?;
+
+    let substations = read_csv(&table(&network, "substations")?)?;
+    assert_eq!(substations[0], ["id", "country", "geographicalTags", "tso"]);
+    assert_eq!(substations[1], ["P1", "FR", r#"["A"]"#, "RTE"]);
+
+    let voltage_levels = read_csv(&table(&network, "voltageLevels")?)?;
+    assert!(!voltage_levels[0]
+        .iter()
+        .any(|column| column.starts_with("generators") || column.starts_with("busBreaker")));
+    assert_eq!(cell(&voltage_levels, 1, "substationId"), "P1");
+    assert_eq!(cell(&voltage_levels, 1, "topologyKind"), "BUS_BREAKER");
+
+    let buses = read_csv(&table(&network, "buses")?)?;
+    assert_eq!(buses.len(), 5);
+    assert_eq!(
+        buses[0],
+        ["id", "substationId", "voltageLevelId", "angle", "v"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_export_located_injections() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_psse("raw", &std::fs::read_to_string(NETWORK_RAW_FILE)?)?.network;
+    let records = read_csv(&table(&network, "shuntCompensators")?)?;
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[1][..3], ["SHUNT-2-1", "SUB-1", "VL-2"]);
+    assert_eq!(
+        cell(&records, 1, "shuntLinearModel.maximumSectionCount"),
+        "1"
+    );
+    assert_eq!(cell(&records, 2, "shuntLinearModel.bPerSection"), "");
+    assert!(cell(&records, 2, "shuntNonLinearModel.sections").starts_with('['));
+
+    // Attributes naming a container are not repeated
+    let switches = read_csv(&table(&network, "switches")?)?;
+    assert_eq!(
+        switches[0]
+            .iter()
+            .filter(|column| *column == "voltageLevelId")
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn test_export_located_dangling_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let mut network = read_network()?;
+    network
+        .dangling_lines
+        .push(serde_json::from_value(serde_json::json!({
+            "id": "DL", "p0": 10.0, "q0": 0.0, "r": 1.0, "x": 10.0, "g": 0.0, "b": 0.0,
+            "voltageLevelId": "VLHV1", "bus": "NHV1"
+        }))?);
+    let records = read_csv(&table(&network, "danglingLines")?)?;
+
+    assert_eq!(records[1][..3], ["DL", "P1", "VLHV1"]);
+    Ok(())
+}
+
+#[test]
+fn test_write_csv_files() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = std::env::temp_dir().join(format!("iidm-csv-{}", std::process::id()));
+    let paths = write_csv_files(&read_network()?, &directory)?;
+
+    assert_eq!(paths.len(), 17);
+    assert!(paths.contains(&directory.join("generators.csv")));
+    let lines = std::fs::read_to_string(directory.join("lines.csv"))?;
+    assert_eq!(lines.lines().count(), 3);
+    // Tables without equipments only have their header
+    let tie_lines = std::fs::read_to_string(directory.join("tieLines.csv"))?;
+    assert_eq!(tie_lines.lines().count(), 1);
+
+    std::fs::remove_dir_all(&directory)?;
+    Ok(())
+}
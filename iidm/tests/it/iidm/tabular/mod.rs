@@ -0,0 +1,15 @@
+use iidm::libs::tabular::{tables, Table};
+use iidm::Network;
+
+mod arrow;
+mod csv;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_RAW_FILE: &str = "tests/data/network.raw";
+
+fn table(network: &Network, name: &str) -> Result<Table, Box<dyn std::error::Error>> {
+    Ok(tables(network)?
+        .into_iter()
+        .find(|table| table.name == name)
+        .unwrap())
+}
@@ -0,0 +1,44 @@
+use iidm::ThreeWindingsTransformer;
+
+mod serialization;
+mod update;
+
+const VALID_3WT_JSON: &str = r#"{
+            "id": "3WT",
+            "ratedU0": 132.0,
+            "r1": 17.424, "x1": 1.7424, "g1": 0.00573921028466483, "b1": 5.73921028466483E-4,
+            "ratedU1": 132.0,
+            "voltageLevelId1": "VL_132", "bus1": "BUS_132", "connectableBus1": "BUS_132",
+            "p1": 100.0, "q1": 20.0,
+            "r2": 1.089, "x2": 0.1089, "g2": 0.0, "b2": 0.0,
+            "ratedU2": 33.0, "ratedS2": 50.0,
+            "voltageLevelId2": "VL_33", "bus2": "BUS_33", "connectableBus2": "BUS_33",
+            "r3": 0.121, "x3": 0.0121, "g3": 0.0, "b3": 0.0,
+            "ratedU3": 11.0,
+            "voltageLevelId3": "VL_11", "bus3": "BUS_11", "connectableBus3": "BUS_11",
+            "ratioTapChanger1": {
+                "regulating": false, "lowTapPosition": 0, "tapPosition": 1,
+                "targetDeadband": 0.0, "loadTapChangingCapabilities": false,
+                "regulationMode": "VOLTAGE", "regulationValue": 132.0,
+                "terminalRef": { "id": "3WT", "side": "ONE" },
+                "steps": [
+                    { "r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 0.95 },
+                    { "r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 1.0 }
+                ]
+            },
+            "phaseTapChanger3": {
+                "regulating": false, "lowTapPosition": 0, "tapPosition": 0,
+                "regulationMode": "FIXED_TAP", "regulationValue": 0.0,
+                "targetDeadband": 0.0,
+                "terminalRef": { "id": "3WT", "side": "THREE" },
+                "steps": [
+                    { "r": 0.0, "x": 0.0, "g": 0.0, "b": 0.0, "rho": 1.0, "alpha": -5.0 }
+                ]
+            },
+            "activePowerLimits2": { "permanentLimit": 45.0 },
+            "currentLimits3": { "permanentLimit": 2000.0, "temporaryLimits": [] }
+        }"#;
+
+fn create_default_3wt() -> ThreeWindingsTransformer {
+    serde_json::from_str(VALID_3WT_JSON).unwrap()
+}
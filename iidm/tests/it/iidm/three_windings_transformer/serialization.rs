@@ -0,0 +1,77 @@
+use iidm::{Side, ThreeWindingsTransformer};
+
+use super::{create_default_3wt, VALID_3WT_JSON};
+
+#[test]
+fn test_deserialize_legs() {
+    let twt: ThreeWindingsTransformer = serde_json::from_str(VALID_3WT_JSON).unwrap();
+    assert_eq!(twt.id, "3WT");
+    assert_eq!(twt.rated_u0, Some(132.0));
+
+    assert_eq!(twt.leg1.rated_u, 132.0);
+    assert_eq!(twt.leg1.voltage_level_id, "VL_132");
+    assert_eq!(twt.leg1.p, Some(100.0));
+    assert_eq!(twt.leg1.ratio_tap_changer.as_ref().unwrap().steps.len(), 2);
+    assert!(twt.leg1.phase_tap_changer.is_none());
+
+    assert_eq!(twt.leg2.rated_s, Some(50.0));
+    assert_eq!(
+        twt.leg2
+            .active_power_limits
+            .as_ref()
+            .unwrap()
+            .permanent_limit,
+        45.0
+    );
+    assert!(twt.leg2.ratio_tap_changer.is_none());
+
+    let leg3 = twt.leg(Side::Three);
+    assert_eq!(leg3.bus, "BUS_11");
+    assert_eq!(
+        leg3.phase_tap_changer.as_ref().unwrap().steps[0].alpha,
+        -5.0
+    );
+    assert_eq!(
+        leg3.current_limits.as_ref().unwrap().permanent_limit,
+        2000.0
+    );
+}
+
+#[test]
+fn test_rated_u0_defaults_to_leg1() {
+    let mut value: serde_json::Value = serde_json::from_str(VALID_3WT_JSON).unwrap();
+    value.as_object_mut().unwrap().remove("ratedU0");
+    value["ratedU1"] = serde_json::json!(138.0);
+
+    let twt: ThreeWindingsTransformer = serde_json::from_value(value).unwrap();
+    assert_eq!(twt.rated_u0, None);
+    assert_eq!(twt.rated_u0(), 138.0);
+
+    // An absent star bus voltage is written back absent
+    let value = serde_json::to_value(&twt).unwrap();
+    assert!(value.get("ratedU0").is_none());
+}
+
+#[test]
+fn test_serialize_flat_attributes() {
+    let twt = create_default_3wt();
+    let value = serde_json::to_value(&twt).unwrap();
+
+    assert!(value.get("leg1").is_none());
+    assert_eq!(value["ratedU0"], 132.0);
+    assert_eq!(value["voltageLevelId2"], "VL_33");
+    assert!(value.get("ratioTapChanger1").is_some());
+    assert!(value.get("ratioTapChanger2").is_none());
+    assert!(value.get("phaseTapChanger3").is_some());
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let twt = create_default_3wt();
+    let json = serde_json::to_string(&twt).unwrap();
+    let deserialized: ThreeWindingsTransformer = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&deserialized).unwrap(),
+        serde_json::to_value(&twt).unwrap()
+    );
+}
@@ -0,0 +1,59 @@
+use super::*;
+use iidm::*;
+
+#[test]
+fn test_update_rated_u0() {
+    let mut twt = create_default_3wt();
+    twt.update(ThreeWindingsTransformerUpdater {
+        rated_u0: Some(Some(1.0)),
+        ..Default::default()
+    });
+    assert_eq!(twt.rated_u0(), 1.0);
+    assert_eq!(twt.leg1.rated_u, 132.0);
+}
+
+#[test]
+fn test_update_leg() {
+    let mut twt = create_default_3wt();
+    let json = r#"{"r": 2.0, "ratedU": 34.5}"#;
+    let validation = LegUpdater::validate_json(json);
+    assert!(
+        validation.is_ok(),
+        "Validation should succeed with a valid JSON: {:?}",
+        validation.err()
+    );
+
+    twt.leg_mut(Side::Two).update(validation.unwrap());
+    assert_eq!(twt.leg2.r, 2.0);
+    assert_eq!(twt.leg2.rated_u, 34.5);
+    assert_eq!(twt.leg2.x, 0.1089);
+}
+
+#[test]
+fn test_update_leg_with_unknown_field() {
+    let json = r#"{"ratedU2": 34.5}"#;
+    let validation = LegUpdater::validate_json(json);
+    assert!(validation.is_err());
+}
+
+#[test]
+fn test_update_leg_unknown_selected_group() {
+    let mut twt = create_default_3wt();
+    let json = r#"{"selectedOperationalLimitsGroupId": "WINTER"}"#;
+    let result = twt.leg_mut(Side::One).update_from_json(json);
+    assert!(matches!(
+        result,
+        Err(LegError::SelectedOperationalLimitsGroupId(_))
+    ));
+
+    let mut leg = twt.leg3.clone();
+    leg.selected_operational_limits_group_id = Some("WINTER".to_string());
+    let updates = ThreeWindingsTransformerUpdater {
+        leg3: Some(leg),
+        ..Default::default()
+    };
+    assert!(matches!(
+        twt.check_update(&updates),
+        Err(ThreeWindingsTransformerError::Leg3(_))
+    ));
+}
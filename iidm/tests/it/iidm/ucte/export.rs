@@ -0,0 +1,115 @@
+use iidm::libs::ucte::{from_ucte, to_ucte, UcteError};
+use iidm::{Network, ReactiveCapabilityCurve, ReactiveLimits, UnknownFields};
+
+use super::*;
+
+#[test]
+fn test_ucte_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let ucte = std::fs::read_to_string(NETWORK_UCTE_FILE)?;
+    let network = from_ucte(NETWORK_UCTE_ID, &ucte)?;
+
+    let export = to_ucte(&network)?;
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+    let exported = from_ucte(NETWORK_UCTE_ID, &export.ucte)?;
+
+    assert_eq!(
+        serde_json::to_value(&exported)?,
+        serde_json::to_value(&network)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ucte_export_warnings() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let network: Network = serde_json::from_str(&json)?;
+
+    let export = to_ucte(&network)?;
+    assert!(export.warnings.contains(
+        &"Nominal voltage 24 kV of voltage level VLGEN is exported as 27 kV".to_string()
+    ));
+    assert!(export
+        .warnings
+        .contains(&"Rated power of transformer NGEN_NHV1 is unknown".to_string()));
+
+    // Buses without UCTE code are named after their country and substation
+    let exported = from_ucte("sim1", &export.ucte)?;
+    assert_eq!(exported.substations.len(), 2);
+    assert_eq!(exported.substations[0].voltage_levels[0].id, "FP1___7");
+    assert_eq!(exported.lines.len(), 2);
+    assert_eq!(
+        exported.lines[0].unknown_fields["elementName"],
+        "NHV1_NHV2_1"
+    );
+    let generator = &exported.substations[0].voltage_levels[0]
+        .generators
+        .as_ref()
+        .unwrap()[0];
+    assert_eq!(generator.target_p, 607.0);
+    Ok(())
+}
+
+#[test]
+fn test_ucte_export_country_codes() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut network: Network = serde_json::from_str(&json)?;
+    network.substations[0].country = "AT".to_string();
+    network.substations[1].country = "CH".to_string();
+
+    let export = to_ucte(&network)?;
+    let exported = from_ucte("sim1", &export.ucte)?;
+    let voltage_levels: Vec<&str> = exported
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .map(|voltage_level| voltage_level.id.as_str())
+        .collect();
+    assert!(voltage_levels.contains(&"OP1___7"));
+    assert!(voltage_levels.contains(&"SP2___1"));
+
+    // Substations without UCTE country code are not exported
+    network.substations[1].country = String::new();
+    let export = to_ucte(&network)?;
+    assert!(export
+        .warnings
+        .contains(&"Country '' of substation P2 has no UCTE code, it is not exported".to_string()));
+    assert!(!export.ucte.contains("##Z\n"));
+    Ok(())
+}
+
+#[test]
+fn test_ucte_export_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut network: Network = serde_json::from_str(&json)?;
+    network.lines[0].r = 1.0e7;
+
+    match to_ucte(&network) {
+        Err(error @ UcteError::Overflow { .. }) => assert_eq!(
+            error.to_string(),
+            "Value 10000000 of line NHV1_NHV2_1 overflows columns 23-28"
+        ),
+        other => panic!("Expected an overflow error, got {:?}", other.map(|_| ())),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ucte_export_empty_curve() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut network: Network = serde_json::from_str(&json)?;
+    let generator = &mut network.substations[0].voltage_levels[0]
+        .generators
+        .as_mut()
+        .unwrap()[0];
+    generator.reactive_limits = Some(ReactiveLimits::Curve(ReactiveCapabilityCurve {
+        points: vec![],
+        unknown_fields: UnknownFields::new(),
+    }));
+
+    let export = to_ucte(&network)?;
+    assert!(export.warnings.contains(
+        &"Reactive capability curve of generator GEN has no points, it is not exported".to_string()
+    ));
+    assert!(!export.ucte.contains("1.8e308"));
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use iidm::libs::ucte::{from_ucte, UcteError};
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_ucte_import_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let ucte = std::fs::read_to_string(NETWORK_UCTE_FILE)?;
+    let network = from_ucte(NETWORK_UCTE_ID, &ucte)?;
+
+    assert_eq!(network.id, NETWORK_UCTE_ID);
+    assert_eq!(network.source_format, "UCTE");
+    assert_eq!(network.case_date.to_rfc3339(), "2007-05-01T10:30:00+00:00");
+
+    // Substations linked by a transformer are merged
+    assert_eq!(network.substations.len(), 3);
+    let voltage_levels: usize = network
+        .substations
+        .iter()
+        .map(|substation| substation.voltage_levels.len())
+        .sum();
+    assert_eq!(voltage_levels, 5);
+    assert_eq!(network.lines.len(), 2);
+    assert_eq!(network.tie_lines.len(), 1);
+    assert!(network.dangling_lines.is_empty());
+
+    let transformers: Vec<&TwoWindingsTransformer> = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.two_windings_transformers)
+        .collect();
+    assert_eq!(transformers.len(), 2);
+    let tap_changer = transformers[1].ratio_tap_changer.as_ref().unwrap();
+    assert_eq!(tap_changer.steps.len(), 33);
+    assert_eq!(tap_changer.low_tap_position, -16);
+    assert_eq!(tap_changer.regulation_value, 150.0);
+    Ok(())
+}
+
+#[test]
+fn test_ucte_import_nodes() -> Result<(), Box<dyn std::error::Error>> {
+    let ucte = std::fs::read_to_string(NETWORK_UCTE_FILE)?;
+    let network = from_ucte(NETWORK_UCTE_ID, &ucte)?;
+
+    let find_voltage_level = |id: &str| {
+        network
+            .substations
+            .iter()
+            .flat_map(|substation| &substation.voltage_levels)
+            .find(|voltage_level| voltage_level.id == id)
+            .unwrap()
+    };
+
+    // The generation substation is merged in the substation its transformer connects to
+    let substation = &network.substations[0];
+    assert_eq!(substation.id, "FFNHV1");
+    assert_eq!(substation.country, "FR");
+    let voltage_level_ids: Vec<&str> = substation
+        .voltage_levels
+        .iter()
+        .map(|voltage_level| voltage_level.id.as_str())
+        .collect();
+    assert_eq!(voltage_level_ids, vec!["FFNHV11", "FFNGEN7"]);
+    assert_eq!(
+        substation.two_windings_transformers[0].id,
+        "FFNGEN71 FFNHV111 1"
+    );
+
+    let voltage_level = find_voltage_level("FFNGEN7");
+    assert_eq!(voltage_level.nominal_v, 27.0);
+    let generator = &voltage_level.generators.as_ref().unwrap()[0];
+    assert_eq!(generator.id, "FFNGEN71_generator");
+    assert_eq!(generator.energy_source, EnergySource::Nuclear);
+    assert_eq!(generator.target_p, 1500.0);
+    assert_eq!(generator.target_v, 24.5);
+    assert!(generator.voltage_regulator_on);
+
+    let load = network
+        .substations
+        .iter()
+        .flat_map(|substation| &substation.voltage_levels)
+        .flat_map(|voltage_level| voltage_level.loads.iter().flatten())
+        .find(|load| load.id == "FFNLOA31_load")
+        .unwrap();
+    assert_eq!((load.p0, load.q0), (600.0, 200.0));
+
+    let topology = find_voltage_level("FFNHV21")
+        .bus_breaker_topology
+        .as_ref()
+        .unwrap();
+    assert_eq!(topology.buses.len(), 2);
+    assert_eq!(topology.switches[0].id, "FFNHV211 FFNHV212 1");
+
+    // Lines out of operation are disconnected
+    let line = &network.lines[1];
+    assert_eq!(line.id, "FFNHV111 FFNHV212 2");
+    assert!(line.bus1.is_empty());
+    assert_eq!(line.connectable_bus2, "FFNHV212");
+
+    let tie_line = &network.tie_lines[0];
+    assert_eq!(tie_line.ucte_xnode_code, "XXNODE11");
+    assert_eq!(tie_line.dangling_line1.p0, 10.0);
+    assert_eq!(tie_line.dangling_line2.p0, 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_ucte_invalid_number() {
+    let ucte = format!(
+        "{}##L\nFFNHV111 FFNHV211 1 0    abc    5.0       20   1000\n",
+        UCTE_NODES
+    );
+    let result = from_ucte("invalid", &ucte);
+    assert!(matches!(result, Err(UcteError::Syntax { line: 7, .. })));
+}
+
+#[test]
+fn test_ucte_unknown_node() {
+    let ucte = format!(
+        "{}##L\nFFNHV111 FFNHV311 1 0    0.5    5.0       20   1000\n",
+        UCTE_NODES
+    );
+    let result = from_ucte("unknown", &ucte);
+    assert!(matches!(result, Err(UcteError::UnknownNode { line: 7, node }) if node == "FFNHV311"));
+}
+
+#[test]
+fn test_ucte_unknown_section() {
+    let ucte = format!("{}##Q\n", UCTE_NODES);
+    let result = from_ucte("unknown", &ucte);
+    assert!(matches!(result, Err(UcteError::Syntax { line: 6, .. })));
+}
+
+#[test]
+fn test_ucte_regulation_of_unknown_transformer() {
+    let ucte = format!(
+        "{}##R\nFFNHV111 FFNHV211 1  1.25 16   0 150.0\n",
+        UCTE_NODES
+    );
+    let result = from_ucte("unknown", &ucte);
+    assert!(matches!(
+        result,
+        Err(UcteError::UnknownTransformer { line: 7, .. })
+    ));
+}
+
+#[test]
+fn test_ucte_duplicate_node() {
+    let ucte = format!(
+        "{}FFNHV111 HV1 BIS      0 0 380.00       0       0       0       0\n",
+        UCTE_NODES
+    );
+    let result = from_ucte("duplicate", &ucte);
+    assert!(matches!(result, Err(UcteError::Syntax { line: 6, .. })));
+}
@@ -0,0 +1,14 @@
+mod export;
+mod import;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_UCTE_FILE: &str = "tests/data/network.uct";
+const NETWORK_UCTE_ID: &str = "20070501_1030_SN2_FR0";
+
+/// Header of a small UCTE-DEF file with a node in each voltage level of a substation
+const UCTE_NODES: &str = "##C 2007.05.01
+##N
+##ZFR
+FFNHV111 HV1          0 0 380.00       0       0       0       0
+FFNHV211 HV2          0 0 380.00       0       0       0       0
+";
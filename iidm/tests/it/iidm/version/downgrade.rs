@@ -0,0 +1,242 @@
+use iidm::libs::json::{from_json, to_json};
+use iidm::libs::version::{downgrade, IidmVersion};
+use iidm::libs::xml::{from_xml, to_xml};
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_export_latest_keeps_model() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(LEGACY_XML)?;
+    let value = to_json(&network, IidmVersion::LATEST)?;
+
+    assert_eq!(value["version"], "1.12");
+    assert!(value["tieLines"][0]["danglingLine1"].is_object());
+    assert!(value["lines"][0]["operationalLimitsGroups1"].is_array());
+    assert!(value["shuntCompensators"][0]["shuntLinearModel"].is_object());
+    Ok(())
+}
+
+#[test]
+fn test_export_legacy_version() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(LEGACY_XML)?;
+    let value = to_json(&network, IidmVersion::new(1, 2))?;
+
+    assert_eq!(value["version"], "1.2");
+    assert!(value.get("minimumValidationLevel").is_none());
+
+    let shunt = &value["shuntCompensators"][0];
+    assert_eq!(shunt["bPerSection"], 1.0e-5);
+    assert_eq!(shunt["maximumSectionCount"], 4);
+    assert_eq!(shunt["currentSectionCount"], 2);
+    assert!(shunt.get("shuntLinearModel").is_none());
+
+    let line = &value["lines"][0];
+    assert_eq!(line["currentLimits1"]["permanentLimit"], 1000.0);
+    assert!(line.get("operationalLimitsGroups1").is_none());
+    assert!(line.get("selectedOperationalLimitsGroupId1").is_none());
+
+    let tie_line = &value["tieLines"][0];
+    assert!(tie_line.get("danglingLine1").is_none());
+    assert_eq!(tie_line["id_2"], "TIE_2");
+    assert_eq!(tie_line["b1_1"], 1.0e-4);
+    assert_eq!(tie_line["voltageLevelId2"], "VL2");
+    assert_eq!(tie_line["currentLimits2"]["permanentLimit"], 500.0);
+    Ok(())
+}
+
+#[test]
+fn test_legacy_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(LEGACY_XML)?;
+
+    let xml = to_xml(&network, IidmVersion::new(1, 2))?;
+    assert!(xml.contains("http://www.itesla_project.eu/schema/iidm/1_2"));
+    let from_legacy_xml = from_xml(&xml)?;
+    assert_eq!(
+        serde_json::to_value(&from_legacy_xml)?,
+        serde_json::to_value(&network)?
+    );
+
+    let json = serde_json::to_vec(&to_json(&network, IidmVersion::new(1, 2))?)?;
+    let from_legacy_json = from_json(&json)?;
+    assert_eq!(
+        serde_json::to_value(&from_legacy_json)?,
+        serde_json::to_value(&network)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_export_drops_unselected_limits_groups() -> Result<(), Box<dyn std::error::Error>> {
+    let mut network = from_xml(LEGACY_XML)?;
+    let line = &mut network.lines[0];
+    let mut group = line.operational_limits_groups1[0].clone();
+    group.id = "WINTER".to_string();
+    group.current_limits.as_mut().unwrap().permanent_limit = 1500.0;
+    line.operational_limits_groups1.push(group);
+    line.selected_operational_limits_group_id1 = Some("WINTER".to_string());
+
+    let value = to_json(&network, IidmVersion::new(1, 11))?;
+    assert_eq!(
+        value["lines"][0]["currentLimits1"]["permanentLimit"],
+        1500.0
+    );
+    assert!(value["lines"][0].get("operationalLimitsGroups1").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_export_non_linear_shunt() -> Result<(), Box<dyn std::error::Error>> {
+    let mut network = from_xml(LEGACY_XML)?;
+    network.shunt_compensators[0].model = ShuntCompensatorModel::NonLinear(ShuntNonLinearModel {
+        sections: vec![
+            ShuntSection {
+                b: 1.0e-5,
+                g: 0.0,
+                unknown_fields: UnknownFields::new(),
+            },
+            ShuntSection {
+                b: 3.0e-5,
+                g: 0.0,
+                unknown_fields: UnknownFields::new(),
+            },
+        ],
+        unknown_fields: UnknownFields::new(),
+    });
+
+    let value = to_json(&network, IidmVersion::new(1, 2))?;
+    let shunt = &value["shuntCompensators"][0];
+    assert_eq!(shunt["bPerSection"], 1.5e-5);
+    assert_eq!(shunt["maximumSectionCount"], 2);
+    Ok(())
+}
+
+// Downgrades a network to the version before a gate and to the gate itself
+fn downgrade_around(network: &serde_json::Value, gate: IidmVersion) -> [serde_json::Value; 2] {
+    let before = IidmVersion::new(gate.major, gate.minor - 1);
+    [before, gate].map(|version| {
+        let mut value = network.clone();
+        downgrade(&mut value, version);
+        value
+    })
+}
+
+fn network_json() -> serde_json::Value {
+    serde_json::json!({
+        "version": "1.12",
+        "id": "gates",
+        "minimumValidationLevel": "STEADY_STATE_HYPOTHESIS",
+        "substations": [{
+            "id": "S1",
+            "voltageLevels": [{
+                "id": "VL1",
+                "loads": [{"id": "LOAD", "exponentialModel": {"np": 1.0, "nq": 2.0}}],
+                "batteries": [{"id": "BAT"}]
+            }],
+            "twoWindingsTransformers": [{"id": "TWT", "ratedS": 100.0}]
+        }],
+        "threeWindingsTransformers": [{"id": "3WT", "ratedU0": 225.0, "ratedS1": 100.0}],
+        "shuntCompensators": [{
+            "id": "SHUNT",
+            "sectionCount": 1,
+            "shuntLinearModel": {"bPerSection": 1.0e-5, "maximumSectionCount": 2},
+            "regulatingTerminal": {"id": "SHUNT"}
+        }],
+        "tieLines": [{"id": "TIE", "danglingLine1": {"id": "TIE_1"}}],
+        "lines": [{
+            "id": "LINE",
+            "operationalLimitsGroups1": [{"id": "DEFAULT", "currentLimits": {"permanentLimit": 1.0}}],
+            "selectedOperationalLimitsGroupId1": "DEFAULT"
+        }],
+        "areas": [{"id": "AREA"}]
+    })
+}
+
+#[test]
+fn test_downgrade_limits_groups() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 12));
+    assert!(before["lines"][0].get("operationalLimitsGroups1").is_none());
+    assert_eq!(before["lines"][0]["currentLimits1"]["permanentLimit"], 1.0);
+    assert!(gate["lines"][0]["operationalLimitsGroups1"].is_array());
+}
+
+#[test]
+fn test_downgrade_areas() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 11));
+    assert!(before.get("areas").is_none());
+    assert_eq!(gate["areas"][0]["id"], "AREA");
+}
+
+#[test]
+fn test_downgrade_paired_tie_lines() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 10));
+    assert!(before["tieLines"][0].get("danglingLine1").is_none());
+    assert_eq!(before["tieLines"][0]["id_1"], "TIE_1");
+    assert!(gate["tieLines"][0]["danglingLine1"].is_object());
+}
+
+#[test]
+fn test_downgrade_load_models() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 10));
+    let load =
+        |value: &serde_json::Value| value["substations"][0]["voltageLevels"][0]["loads"][0].clone();
+    assert!(load(&before).get("exponentialModel").is_none());
+    assert!(load(&gate)["exponentialModel"].is_object());
+}
+
+#[test]
+fn test_downgrade_validation_level() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 7));
+    assert!(before.get("minimumValidationLevel").is_none());
+    assert_eq!(gate["minimumValidationLevel"], "STEADY_STATE_HYPOTHESIS");
+}
+
+#[test]
+fn test_downgrade_shunt_models() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 3));
+    assert!(before["shuntCompensators"][0]
+        .get("shuntLinearModel")
+        .is_none());
+    assert_eq!(before["shuntCompensators"][0]["maximumSectionCount"], 2);
+    assert!(gate["shuntCompensators"][0]["shuntLinearModel"].is_object());
+}
+
+#[test]
+fn test_downgrade_shunt_regulation() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 2));
+    assert!(before["shuntCompensators"][0]
+        .get("regulatingTerminal")
+        .is_none());
+    assert!(gate["shuntCompensators"][0]["regulatingTerminal"].is_object());
+}
+
+#[test]
+fn test_downgrade_rated_s() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 2));
+    let two_windings =
+        |value: &serde_json::Value| value["substations"][0]["twoWindingsTransformers"][0].clone();
+    assert!(two_windings(&before).get("ratedS").is_none());
+    assert!(before["threeWindingsTransformers"][0]
+        .get("ratedS1")
+        .is_none());
+    assert_eq!(two_windings(&gate)["ratedS"], 100.0);
+    assert_eq!(gate["threeWindingsTransformers"][0]["ratedS1"], 100.0);
+}
+
+#[test]
+fn test_downgrade_rated_u0() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 1));
+    assert!(before["threeWindingsTransformers"][0]
+        .get("ratedU0")
+        .is_none());
+    assert_eq!(gate["threeWindingsTransformers"][0]["ratedU0"], 225.0);
+}
+
+#[test]
+fn test_downgrade_batteries() {
+    let [before, gate] = downgrade_around(&network_json(), IidmVersion::new(1, 1));
+    let voltage_level =
+        |value: &serde_json::Value| value["substations"][0]["voltageLevels"][0].clone();
+    assert!(voltage_level(&before).get("batteries").is_none());
+    assert!(voltage_level(&gate)["batteries"].is_array());
+}
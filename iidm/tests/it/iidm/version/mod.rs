@@ -0,0 +1,30 @@
+mod downgrade;
+mod upgrade;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+
+/// Network of IIDM 1.2 with a flat shunt compensator, a flat tie line and current limits
+const LEGACY_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<iidm:network xmlns:iidm="http://www.itesla_project.eu/schema/iidm/1_2" id="legacy" caseDate="2019-05-27T12:17:02.504+02:00" forecastDistance="0" sourceFormat="test">
+    <iidm:substation id="S1" country="FR" tso="RTE">
+        <iidm:voltageLevel id="VL1" nominalV="380.0" topologyKind="BUS_BREAKER">
+            <iidm:busBreakerTopology>
+                <iidm:bus id="B1"/>
+            </iidm:busBreakerTopology>
+            <iidm:shunt id="SHUNT" bPerSection="1.0E-5" maximumSectionCount="4" currentSectionCount="2" bus="B1" connectableBus="B1"/>
+        </iidm:voltageLevel>
+        <iidm:voltageLevel id="VL2" nominalV="380.0" topologyKind="BUS_BREAKER">
+            <iidm:busBreakerTopology>
+                <iidm:bus id="B2"/>
+            </iidm:busBreakerTopology>
+        </iidm:voltageLevel>
+    </iidm:substation>
+    <iidm:line id="LINE" r="1.0" x="10.0" g1="0.0" b1="0.0" g2="0.0" b2="0.0" bus1="B1" connectableBus1="B1" voltageLevelId1="VL1" bus2="B2" connectableBus2="B2" voltageLevelId2="VL2">
+        <iidm:currentLimits1 permanentLimit="1000.0">
+            <iidm:temporaryLimit name="20'" acceptableDuration="1200" value="1200.0"/>
+        </iidm:currentLimits1>
+    </iidm:line>
+    <iidm:tieLine id="TIE" name="TIE" ucteXnodeCode="XNODE" id_1="TIE_1" r_1="1.0" x_1="10.0" g1_1="0.0" b1_1="1.0E-4" g2_1="0.0" b2_1="1.0E-4" xnodeP_1="0.0" xnodeQ_1="0.0" id_2="TIE_2" r_2="2.0" x_2="20.0" g1_2="0.0" b1_2="2.0E-4" g2_2="0.0" b2_2="2.0E-4" xnodeP_2="0.0" xnodeQ_2="0.0" bus1="B1" connectableBus1="B1" voltageLevelId1="VL1" bus2="B2" connectableBus2="B2" voltageLevelId2="VL2">
+        <iidm:currentLimits2 permanentLimit="500.0"/>
+    </iidm:tieLine>
+</iidm:network>"#;
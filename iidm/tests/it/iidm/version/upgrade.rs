@@ -0,0 +1,104 @@
+use iidm::libs::error::IidmError;
+use iidm::libs::json::from_json;
+use iidm::libs::version::{IidmVersion, DEFAULT_LIMITS_GROUP};
+use iidm::libs::xml::from_xml;
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_upgrade_legacy_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(LEGACY_XML)?;
+    assert_eq!(network.version, "1.2");
+    assert_eq!(network.minimum_validation_level, "STEADY_STATE_HYPOTHESIS");
+
+    let shunt = &network.shunt_compensators[0];
+    assert_eq!(shunt.section_count, 2);
+    assert_eq!(shunt.model.maximum_section_count(), 4);
+    assert_eq!(shunt.admittance(), Some((2.0e-5, 0.0)));
+
+    let line = &network.lines[0];
+    let group = line.selected_operational_limits_group(Side::One).unwrap();
+    assert_eq!(group.id, DEFAULT_LIMITS_GROUP);
+    let limits = group.current_limits.as_ref().unwrap();
+    assert_eq!(limits.permanent_limit, 1000.0);
+    assert_eq!(limits.temporary_limits[0].acceptable_duration, 1200);
+    assert!(line.current_limits1.is_none());
+    assert!(line.operational_limits_groups2.is_empty());
+
+    let tie_line = &network.tie_lines[0];
+    assert_eq!(tie_line.ucte_xnode_code, "XNODE");
+    assert_eq!(tie_line.dangling_line1.id, "TIE_1");
+    assert_eq!(tie_line.dangling_line1.b, 2.0e-4);
+    assert_eq!(tie_line.dangling_line1.bus, "B1");
+    assert_eq!(tie_line.dangling_line2.x, 20.0);
+    assert_eq!(tie_line.dangling_line2.voltage_level_id, "VL2");
+    let limits = tie_line.dangling_line2.operational_limits_groups[0]
+        .current_limits
+        .as_ref()
+        .unwrap();
+    assert_eq!(limits.permanent_limit, 500.0);
+    assert!(!tie_line.unknown_fields.contains_key("r_1"));
+    Ok(())
+}
+
+#[test]
+fn test_upgrade_legacy_json() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut value: serde_json::Value = serde_json::from_str(&json)?;
+    value["version"] = "1.6".into();
+    value
+        .as_object_mut()
+        .unwrap()
+        .remove("minimumValidationLevel");
+    value["lines"][0]["currentLimits2"] = serde_json::json!({
+        "permanentLimit": 800.0,
+        "temporaryLimits": []
+    });
+
+    let network = from_json(&serde_json::to_vec(&value)?)?;
+    assert_eq!(
+        network.version.parse::<IidmVersion>()?,
+        IidmVersion::new(1, 6)
+    );
+    assert_eq!(network.minimum_validation_level, "STEADY_STATE_HYPOTHESIS");
+    let group = network.lines[0]
+        .selected_operational_limits_group(Side::Two)
+        .unwrap();
+    assert_eq!(
+        group.current_limits.as_ref().unwrap().permanent_limit,
+        800.0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let mut value: serde_json::Value = serde_json::from_str(&json)?;
+
+    for version in ["1.13", "2.0", "latest"] {
+        value["version"] = version.into();
+        match from_json(&serde_json::to_vec(&value)?) {
+            Err(IidmError::UnsupportedVersion(found)) => assert_eq!(found, version),
+            other => panic!("Expected a version error for {}, got {:?}", version, other),
+        }
+    }
+
+    value.as_object_mut().unwrap().remove("version");
+    assert!(matches!(
+        from_json(&serde_json::to_vec(&value)?),
+        Err(IidmError::MissingVersion)
+    ));
+
+    let xml = LEGACY_XML.replace("iidm/1_2", "iidm/1_13");
+    match from_xml(&xml) {
+        Err(error @ IidmError::UnsupportedVersion(_)) => {
+            assert!(error
+                .to_string()
+                .contains("supported versions are 1.0 to 1.12"))
+        }
+        other => panic!("Expected a version error, got {:?}", other),
+    }
+    Ok(())
+}
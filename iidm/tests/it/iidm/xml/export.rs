@@ -0,0 +1,164 @@
+use iidm::libs::version::IidmVersion;
+use iidm::libs::xml::{from_xml, to_xml};
+use iidm::*;
+use roxmltree::Document;
+
+use super::*;
+
+#[test]
+fn test_json_network_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let network: Network = serde_json::from_str(&json)?;
+
+    let xml = to_xml(&network, IidmVersion::LATEST)?;
+    let exported = from_xml(&xml)?;
+
+    assert_eq!(
+        serde_json::to_value(&exported)?,
+        serde_json::to_value(&network)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_xml_network_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(NODE_BREAKER_XML)?;
+
+    let xml = to_xml(&network, IidmVersion::new(1, 10))?;
+    let exported = from_xml(&xml)?;
+
+    assert_eq!(
+        serde_json::to_value(&exported)?,
+        serde_json::to_value(&network)?
+    );
+    assert_eq!(exported.substations[1].tso, "ELIA & co");
+    assert_eq!(exported.tie_lines[0].dangling_line2.id, "DL2");
+    Ok(())
+}
+
+#[test]
+fn test_export_layout() -> Result<(), Box<dyn std::error::Error>> {
+    let xml = to_xml(&from_xml(NODE_BREAKER_XML)?, IidmVersion::LATEST)?;
+    let document = Document::parse(&xml)?;
+    let root = document.root_element();
+    assert_eq!(
+        root.tag_name().namespace(),
+        Some("http://www.powsybl.org/schema/iidm/1_12")
+    );
+    assert!(root.attribute("version").is_none());
+
+    let element = |name: &str, id: Option<&str>| {
+        document
+            .descendants()
+            .find(|node| node.has_tag_name(name) && (id.is_none() || node.attribute("id") == id))
+            .unwrap_or_else(|| panic!("missing element {}", name))
+    };
+    let parent = |name: &str, id: Option<&str>| {
+        let parent = element(name, id).parent_element().unwrap();
+        (
+            parent.tag_name().name().to_string(),
+            parent.attribute("id").map(str::to_string),
+        )
+    };
+
+    // Equipments are written back where IIDM XML nests them
+    assert_eq!(
+        parent("shunt", None),
+        ("voltageLevel".into(), Some("VL1".into()))
+    );
+    assert_eq!(
+        parent("busbarSection", None),
+        ("nodeBreakerTopology".into(), None)
+    );
+    assert_eq!(
+        parent("vscConverterStation", Some("VSC2")),
+        ("voltageLevel".into(), Some("VL1".into()))
+    );
+    assert_eq!(
+        parent("danglingLine", Some("DL2")),
+        ("voltageLevel".into(), Some("VL2".into()))
+    );
+    assert_eq!(
+        parent("threeWindingsTransformer", None),
+        ("substation".into(), Some("S2".into()))
+    );
+    assert!(element("shunt", None).attribute("voltageLevelId").is_none());
+    assert!(element("switch", Some("BRK"))
+        .attribute("voltageLevelId")
+        .is_none());
+
+    // Nodes are implicit and references are written as ids
+    assert!(!document.descendants().any(|node| node.has_tag_name("node")));
+    assert_eq!(
+        element("hvdcLine", None).attribute("converterStation1"),
+        Some("VSC1")
+    );
+    assert_eq!(
+        element("tieLine", None).attribute("danglingLineId1"),
+        Some("DL1")
+    );
+    assert_eq!(
+        element("substation", Some("S1")).attribute("geographicalTags"),
+        Some("A,B")
+    );
+
+    let entsoe_area = element("entsoeArea", None);
+    assert_eq!(
+        entsoe_area.tag_name().namespace(),
+        Some("http://www.powsybl.org/schema/iidm/ext/entsoe_area/1_0")
+    );
+    assert_eq!(
+        entsoe_area.parent_element().unwrap().attribute("id"),
+        Some("S1")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_export_escapes_line_breaks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut network = from_xml(NODE_BREAKER_XML)?;
+    network.substations[0].tso = "RTE\n\tOuest\r".to_string();
+
+    let xml = to_xml(&network, IidmVersion::LATEST)?;
+    assert!(xml.contains(r#"tso="RTE&#10;&#9;Ouest&#13;""#));
+    let exported = from_xml(&xml)?;
+    assert_eq!(exported.substations[0].tso, "RTE\n\tOuest\r");
+    Ok(())
+}
+
+#[test]
+fn test_export_keeps_nodes_not_implied_by_connections() -> Result<(), Box<dyn std::error::Error>> {
+    let mut network = from_xml(NODE_BREAKER_XML)?;
+    let topology = network.substations[0].voltage_levels[0]
+        .node_breaker_topology
+        .as_mut()
+        .unwrap();
+    topology.nodes[0]
+        .unknown_fields
+        .insert("v".to_string(), 401.0.into());
+    topology.nodes.push(Node {
+        id: 7,
+        unknown_fields: UnknownFields::new(),
+    });
+
+    let xml = to_xml(&network, IidmVersion::LATEST)?;
+    let document = Document::parse(&xml)?;
+    let mut written: Vec<&str> = document
+        .descendants()
+        .filter(|node| node.has_tag_name("node"))
+        .filter_map(|node| node.attribute("id"))
+        .collect();
+    written.sort();
+    assert_eq!(written, vec!["0", "7"]);
+
+    let exported = from_xml(&xml)?;
+    let nodes = &exported.substations[0].voltage_levels[0]
+        .node_breaker_topology
+        .as_ref()
+        .unwrap()
+        .nodes;
+    assert_eq!(nodes.len(), 8);
+    let node = nodes.iter().find(|node| node.id == 0).unwrap();
+    assert_eq!(node.unknown_fields["v"], 401.0);
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use iidm::libs::error::IidmError;
+use iidm::libs::xml::from_xml;
+use iidm::*;
+
+use super::*;
+
+#[test]
+fn test_xml_and_json_networks_are_equal() -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(NETWORK_JSON_FILE)?;
+    let xml = std::fs::read_to_string(NETWORK_XML_FILE)?;
+
+    let from_json: Network = serde_json::from_str(&json)?;
+    let from_xml = from_xml(&xml)?;
+
+    assert_eq!(
+        serde_json::to_value(&from_xml)?,
+        serde_json::to_value(&from_json)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_node_breaker_import() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(NODE_BREAKER_XML)?;
+    assert_eq!(network.version, "1.10");
+    assert_eq!(network.minimum_validation_level, "STEADY_STATE_HYPOTHESIS");
+
+    let substation = &network.substations[0];
+    assert_eq!(substation.geographical_tags, vec!["A", "B"]);
+
+    let voltage_level = &substation.voltage_levels[0];
+    let busbar_sections = voltage_level.busbar_sections.as_ref().unwrap();
+    assert_eq!(busbar_sections[0].node, Some(0));
+
+    let topology = voltage_level.node_breaker_topology.as_ref().unwrap();
+    assert_eq!(topology.switches[0].voltage_level_id, "VL1");
+    assert_eq!(topology.switches[0].node2, Some(1));
+    let mut nodes: Vec<i32> = topology.nodes.iter().map(|node| node.id).collect();
+    nodes.sort();
+    // The dangling line node is only declared by its connection
+    assert_eq!(nodes, vec![0, 1, 2, 3, 4, 5, 6]);
+    assert!(network.validate_node_connections().is_empty());
+
+    let generator = &voltage_level.generators.as_ref().unwrap()[0];
+    assert!(matches!(
+        &generator.reactive_limits,
+        Some(ReactiveLimits::Curve(curve)) if curve.points.len() == 2
+    ));
+
+    assert_eq!(
+        network.shunt_compensators[0].admittance(),
+        Some((1.0e-5, 0.0))
+    );
+
+    let hvdc_line = &network.hvdc_lines[0];
+    assert_eq!(hvdc_line.converter_station1.voltage_level_id, "VL1");
+    assert_eq!(hvdc_line.converter_station2.node, Some(4));
+    assert_eq!(hvdc_line.converter_station1.name, "VSC1");
+    // Attributes of the station not modelled yet are kept
+    let unknown_fields = &hvdc_line.converter_station1.unknown_fields;
+    assert_eq!(unknown_fields["voltageSetpoint"], 405.0);
+    assert_eq!(unknown_fields["voltageRegulatorOn"], true);
+    Ok(())
+}
+
+#[test]
+fn test_extensions_import() -> Result<(), Box<dyn std::error::Error>> {
+    let network = from_xml(NODE_BREAKER_XML)?;
+    let holder = &network.extensions[0];
+    assert_eq!(holder.id, "S1");
+
+    let position = holder.get::<SubstationPosition>().unwrap()?;
+    assert_eq!(position.coordinate.latitude, 48.5);
+    assert_eq!(holder.get::<EntsoeArea>().unwrap()?.code, "FR");
+    // Extensions without typed model keep their attributes as strings
+    assert_eq!(holder.extensions["rating"]["level"], 2);
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_namespace() {
+    let xml = r#"<iidm:network xmlns:iidm="http://www.powsybl.org/schema/iidm/2_0" id="n"/>"#;
+    assert!(matches!(
+        from_xml(xml),
+        Err(IidmError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn test_invalid_xml() {
+    assert!(matches!(
+        from_xml("<iidm:network"),
+        Err(IidmError::Malformed(_))
+    ));
+}
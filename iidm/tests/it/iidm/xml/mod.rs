@@ -0,0 +1,55 @@
+mod export;
+mod import;
+
+const NETWORK_JSON_FILE: &str = "tests/data/network.json";
+const NETWORK_XML_FILE: &str = "tests/data/network.xiidm";
+
+/// Node-breaker network of IIDM 1.10 with converter stations, a tie line and extensions
+const NODE_BREAKER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<iidm:network xmlns:iidm="http://www.powsybl.org/schema/iidm/1_10" xmlns:sp="http://www.powsybl.org/schema/iidm/ext/substation_position/1_0" xmlns:ea="http://www.powsybl.org/schema/iidm/ext/entsoe_area/1_0" id="nb" caseDate="2016-06-27T12:27:58.535+02:00" forecastDistance="0" sourceFormat="test" minimumValidationLevel="STEADY_STATE_HYPOTHESIS">
+    <iidm:substation id="S1" country="FR" tso="RTE" geographicalTags="A,B">
+        <iidm:voltageLevel id="VL1" nominalV="400.0" topologyKind="NODE_BREAKER">
+            <iidm:nodeBreakerTopology>
+                <iidm:busbarSection id="BBS1" name="BBS1" node="0"/>
+                <iidm:switch id="BRK" kind="BREAKER" retained="true" open="false" node1="0" node2="1"/>
+                <iidm:internalConnection node1="0" node2="2"/>
+            </iidm:nodeBreakerTopology>
+            <iidm:generator id="GEN" energySource="HYDRO" minP="0.0" maxP="100.0" voltageRegulatorOn="false" targetP="50.0" targetV="400.0" targetQ="0.0" node="1">
+                <iidm:reactiveCapabilityCurve>
+                    <iidm:point p="0.0" minQ="-10.0" maxQ="10.0"/>
+                    <iidm:point p="100.0" minQ="-5.0" maxQ="5.0"/>
+                </iidm:reactiveCapabilityCurve>
+            </iidm:generator>
+            <iidm:shunt id="SHUNT" sectionCount="1" node="2">
+                <iidm:shuntLinearModel bPerSection="1.0E-5" maximumSectionCount="2"/>
+            </iidm:shunt>
+            <iidm:vscConverterStation id="VSC1" voltageRegulatorOn="true" lossFactor="1.1" voltageSetpoint="405.0" reactivePowerSetpoint="0.0" node="3">
+                <iidm:minMaxReactiveLimits minQ="-50.0" maxQ="50.0"/>
+            </iidm:vscConverterStation>
+            <iidm:vscConverterStation id="VSC2" voltageRegulatorOn="true" lossFactor="1.1" voltageSetpoint="405.0" reactivePowerSetpoint="0.0" node="4"/>
+            <iidm:danglingLine id="DL1" p0="0.0" q0="0.0" r="1.0" x="10.0" g="0.0" b="0.0" node="6"/>
+        </iidm:voltageLevel>
+        <iidm:twoWindingsTransformer id="TWT" r="0.1" x="1.0" g="0.0" b="0.0" ratedU1="400.0" ratedU2="225.0" voltageLevelId1="VL1" node1="5" voltageLevelId2="VL2" bus2="B2" connectableBus2="B2"/>
+    </iidm:substation>
+    <iidm:substation id="S2" country="BE" tso="ELIA &amp; co">
+        <iidm:voltageLevel id="VL2" nominalV="225.0" topologyKind="BUS_BREAKER">
+            <iidm:busBreakerTopology>
+                <iidm:bus id="B2"/>
+                <iidm:bus id="B3"/>
+                <iidm:switch id="COUPLER" kind="BREAKER" retained="false" open="true" bus1="B2" bus2="B3"/>
+            </iidm:busBreakerTopology>
+            <iidm:load id="LOAD" loadType="UNDEFINED" p0="10.0" q0="3.0" bus="B2" connectableBus="B2"/>
+            <iidm:danglingLine id="DL2" p0="0.0" q0="0.0" r="1.0" x="10.0" g="0.0" b="0.0" bus="B2" connectableBus="B2"/>
+        </iidm:voltageLevel>
+        <iidm:threeWindingsTransformer id="3WT" r1="0.1" x1="1.0" g1="0.0" b1="0.0" ratedU1="225.0" r2="0.1" x2="1.0" g2="0.0" b2="0.0" ratedU2="225.0" r3="0.1" x3="1.0" g3="0.0" b3="0.0" ratedU3="225.0" voltageLevelId1="VL2" bus1="B2" connectableBus1="B2" voltageLevelId2="VL2" bus2="B3" connectableBus2="B3" voltageLevelId3="VL2" bus3="B3" connectableBus3="B3"/>
+    </iidm:substation>
+    <iidm:hvdcLine id="HVDC" r="1.0" nominalV="400.0" convertersMode="SIDE1_RECTIFIER_SIDE2_INVERTER" activePowerSetpoint="100.0" maxP="300.0" converterStation1="VSC1" converterStation2="VSC2"/>
+    <iidm:tieLine id="TL" name="TL" danglingLineId1="DL1" danglingLineId2="DL2"/>
+    <iidm:extension id="S1">
+        <sp:substationPosition>
+            <sp:coordinate latitude="48.5" longitude="2.3"/>
+        </sp:substationPosition>
+        <ea:entsoeArea code="FR"/>
+        <vendor:rating xmlns:vendor="http://example.com/vendor" level="2"/>
+    </iidm:extension>
+</iidm:network>"#;
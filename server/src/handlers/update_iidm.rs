@@ -7,7 +7,6 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use thiserror::Error;
-use tracing::error;
 
 use crate::states::AppState;
 
@@ -23,6 +22,7 @@ pub struct RegisterResponse {
 }
 
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum UpdateError {
     #[error("Failed to parse JSON: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -1,20 +1,29 @@
 use crate::states::AppState;
 use askama::Template;
 use axum::{
+    body::Bytes,
     extract::{Multipart, State},
     http::StatusCode,
     response::{Html, IntoResponse, Response},
 };
+use iidm::libs::error::IidmError;
+use iidm::libs::stream::{read_iidm_stream, register_network, StreamImport};
 use iidm::*;
-use std::sync::Arc;
+use std::{collections::BTreeMap, io::Read, sync::Arc};
 use thiserror::Error;
+use tokio::sync::mpsc;
+
+/// Number of multipart chunks buffered ahead of the import
+const CHUNK_BUFFER: usize = 16;
 
 #[derive(Error, Debug)]
 pub enum UploadError {
     #[error("Multipart field error: {0}")]
     MultipartError(#[from] axum::extract::multipart::MultipartError),
-    #[error("JSON parsing error: {0}")]
-    JsonError(#[from] NetworkError),
+    #[error("IIDM parsing error: {0}")]
+    JsonError(#[from] IidmError),
+    #[error("Import task error: {0}")]
+    TaskError(#[from] tokio::task::JoinError),
     #[error("Template rendering error: {0}")]
     TemplateError(#[from] askama::Error),
     #[error("No IIDM file provided")]
@@ -34,11 +43,23 @@ impl IntoResponse for UploadError {
 struct IIdmTableTemplate {
     message: String,
     network: Option<Network>,
+    registered: BTreeMap<String, usize>,
 }
 
 impl IIdmTableTemplate {
-    fn new(message: String, network: Option<Network>) -> Self {
-        Self { message, network }
+    fn new(message: String, import: Option<StreamImport>) -> Self {
+        match import {
+            Some(import) => Self {
+                message,
+                network: Some(import.network),
+                registered: import.registered,
+            },
+            None => Self {
+                message,
+                network: None,
+                registered: BTreeMap::new(),
+            },
+        }
     }
 }
 
@@ -46,12 +67,11 @@ pub async fn upload_iidm(
     State(state): State<Arc<AppState>>,
     mut multipart: Multipart,
 ) -> Result<impl IntoResponse, UploadError> {
-    let result = process_upload(&mut multipart).await;
+    let result = process_upload(&state, &mut multipart).await;
 
     match result {
-        Ok(network) => {
-            update_ecs_state(&state, &network).await;
-            let template = IIdmTableTemplate::new("".to_string(), Some(network));
+        Ok(import) => {
+            let template = IIdmTableTemplate::new("".to_string(), Some(import));
             let html = template.render().map_err(UploadError::TemplateError)?;
             Ok(Html(html))
         }
@@ -64,26 +84,70 @@ pub async fn upload_iidm(
     }
 }
 
-async fn process_upload(multipart: &mut Multipart) -> Result<Network, UploadError> {
-    while let Some(field) = multipart
+/// Reads the uploaded network while it is received, its chunks being parsed by a blocking
+/// task, then registers it. The ECS state is only locked once the whole network is read, so
+/// that slow uploads don't block the other requests and invalid ones register nothing.
+async fn process_upload(
+    state: &Arc<AppState>,
+    multipart: &mut Multipart,
+) -> Result<StreamImport, UploadError> {
+    while let Some(mut field) = multipart
         .next_field()
         .await
         .map_err(UploadError::MultipartError)?
     {
         if field.name() == Some("iidm_file") {
-            let bytes = field.bytes().await.map_err(UploadError::MultipartError)?;
-            return serde_json::from_slice(&bytes)
-                .map_err(|e| NetworkError::Deserialization(e))
-                .map_err(UploadError::JsonError);
+            let (sender, receiver) = mpsc::channel(CHUNK_BUFFER);
+            let network =
+                tokio::task::spawn_blocking(move || read_iidm_stream(ChunkReader::new(receiver)));
+
+            while let Some(chunk) = field.chunk().await.map_err(UploadError::MultipartError)? {
+                // The import stopped reading on error
+                if sender.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+            drop(sender);
+            let network = network.await?.map_err(UploadError::JsonError)?;
+
+            let state = state.clone();
+            let import = tokio::task::spawn_blocking(move || {
+                let ecs = state.ecs.blocking_read();
+                let mut world = ecs.world.blocking_write();
+                let mut schedule = ecs.schedule.blocking_write();
+                register_network(network, &mut world, &mut schedule)
+            });
+            return Ok(import.await?);
         }
     }
     Err(UploadError::NoFile)
 }
 
-async fn update_ecs_state(state: &Arc<AppState>, network: &Network) {
-    let ecs = state.ecs.read().await;
-    let mut world = ecs.world.write().await;
-    let mut schedule = ecs.schedule.write().await;
+/// Blocking reader over the chunks of an upload
+struct ChunkReader {
+    receiver: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
 
-    network.register(&mut world, &mut schedule);
+impl ChunkReader {
+    fn new(receiver: mpsc::Receiver<Bytes>) -> Self {
+        Self {
+            receiver,
+            chunk: Bytes::new(),
+        }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let length = buf.len().min(self.chunk.len());
+        buf[..length].copy_from_slice(&self.chunk.split_to(length));
+        Ok(length)
+    }
 }
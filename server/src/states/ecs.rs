@@ -11,6 +11,7 @@ impl<T: 'static + Send + Sync> ComponentInit for T {}
 fn assert_identifiable<T: Identifiable>() {}
 fn assert_component<T: ComponentInit>() {}
 fn assert_updatable<T: Updatable>() {}
+fn assert_extension<T: Extension>() {}
 
 macro_rules! init_identifiable_components {
     ($($component:ty),* $(,)?) => {
@@ -62,6 +63,30 @@ macro_rules! init_updatable_components {
     };
 }
 
+macro_rules! init_extension_components {
+    ($($component:ty),* $(,)?) => {
+        fn init_extension_components(world: &mut World, schedule: &mut Schedule) {
+            let mut registry = ExtensionRegistry::default();
+            $(
+                // Static verification that the type implements Extension
+                assert_extension::<$component>();
+
+                world.init_resource::<Events<RegisterExtensionEvent<$component>>>();
+                schedule.add_systems(handle_register_extension_events::<$component>);
+
+                // Register extension type by its IIDM name
+                registry.register::<$component>();
+            )*
+
+            // Extensions without typed model are kept verbatim
+            world.init_resource::<Events<RegisterExtensionEvent<UnknownExtensions>>>();
+            schedule.add_systems(handle_register_extension_events::<UnknownExtensions>);
+
+            world.insert_resource(registry);
+        }
+    };
+}
+
 init_identifiable_components!(
     Network,
     Line,
@@ -69,6 +94,7 @@ init_identifiable_components!(
     VoltageLevel,
     Generator,
     Load,
+    Battery,
     Bus,
     BusbarSection,
     TwoWindingsTransformer,
@@ -80,7 +106,8 @@ init_identifiable_components!(
     TieLine,
     HvdcLine,
     HvdcConverterStation,
-    TerminalRef
+    TerminalRef,
+    Area
 );
 
 init_updatable_components!(
@@ -90,10 +117,12 @@ init_updatable_components!(
     VoltageLevel,
     Generator,
     Load,
+    Battery,
     Bus,
     BusbarSection,
     TwoWindingsTransformer,
     ThreeWindingsTransformer,
+    Leg,
     Switch,
     ShuntCompensator,
     StaticVarCompensator,
@@ -101,9 +130,14 @@ init_updatable_components!(
     TieLine,
     HvdcLine,
     HvdcConverterStation,
+    Area,
+    AreaBoundary,
     ReactiveCapabilityCurve,
     ReactiveCapabilityCurvePoint,
     MinMaxReactiveLimits,
+    ShuntLinearModel,
+    ShuntNonLinearModel,
+    ShuntSection,
     ExponentialLoadModel,
     ZipLoadModel,
     BusBreakerTopology,
@@ -114,10 +148,29 @@ init_updatable_components!(
     PhaseTapChanger,
     TapStep,
     PhaseTapStep,
+    SubstationPosition,
+    Coordinate,
+    BusbarSectionPosition,
+    ActivePowerControl,
+    GeneratorShortCircuit,
+    HvdcAngleDroopActivePowerControl,
+    EntsoeArea,
     CurrentLimits,
+    ActivePowerLimits,
+    ApparentPowerLimits,
+    OperationalLimitsGroup,
     TemporaryLimit
 );
 
+init_extension_components!(
+    SubstationPosition,
+    BusbarSectionPosition,
+    ActivePowerControl,
+    GeneratorShortCircuit,
+    HvdcAngleDroopActivePowerControl,
+    EntsoeArea
+);
+
 pub struct EcsState {
     pub world: RwLock<World>,
     pub schedule: RwLock<Schedule>,
@@ -137,9 +190,10 @@ impl Default for EcsState {
         // Init Resources and Systems
         init_identifiable_component(&mut world, &mut schedule);
         init_updatable_components(&mut world, &mut schedule, &mut update_registry);
+        init_extension_components(&mut world, &mut schedule);
 
         // Init Errors handler
-        world.insert_resource(Events::<EntityNotFoundEvent>::default());
+        world.insert_resource(Events::<UpdateErrorEvent>::default());
 
         Self {
             world: RwLock::new(world),
@@ -9,7 +9,7 @@ use axum::{
     Json,
 };
 use bevy_ecs::event::Events;
-use iidm::{EntityNotFoundEvent, ErrorType, JsonSchema, Updatable, UpdateEvent};
+use iidm::{ErrorType, JsonSchema, Updatable, UpdateErrorEvent, UpdateEvent};
 use std::fmt::Display;
 use std::future::Future;
 use std::sync::Arc;
@@ -127,9 +127,9 @@ where
         )));
     }
 
-    // Check for EntityNotFoundEvent resource
-    if !world.contains_resource::<Events<EntityNotFoundEvent>>() {
-        tracing::error!("Events<EntityNotFoundEvent> not initialized");
+    // Check for UpdateErrorEvent resource
+    if !world.contains_resource::<Events<UpdateErrorEvent>>() {
+        tracing::error!("Events<UpdateErrorEvent> not initialized");
         return Err(UpdateError::InternalError(
             "Error errors system not initialized".to_string(),
         ));
@@ -178,12 +178,12 @@ where
 
 // Helper function to check for errors after update
 fn check_for_errors(world: &bevy_ecs::world::World, id: &str) -> Result<(), UpdateError> {
-    let error_events = world.resource::<Events<EntityNotFoundEvent>>();
+    let error_events = world.resource::<Events<UpdateErrorEvent>>();
     let mut error_reader = error_events.get_cursor();
 
     for error in error_reader.read(error_events) {
         if error.id == id {
-            match error.error_type {
+            match &error.error_type {
                 ErrorType::EntityNotFound => {
                     return Err(UpdateError::NotFoundError(format!(
                         "Entity with ID '{}' not found",
@@ -196,6 +196,9 @@ fn check_for_errors(world: &bevy_ecs::world::World, id: &str) -> Result<(), Upda
                         error.component_type, id
                     )));
                 }
+                ErrorType::InvalidUpdate(message) => {
+                    return Err(UpdateError::ValidationError(message.clone()));
+                }
             }
         }
     }
@@ -265,7 +268,7 @@ mod tests {
 
         // Add required resources
         world.insert_resource(Events::<UpdateEvent<MockComponent>>::default());
-        world.insert_resource(Events::<EntityNotFoundEvent>::default());
+        world.insert_resource(Events::<UpdateErrorEvent>::default());
 
         // Test with all resources present
         let result = verify_resources::<MockComponent>(&world);
@@ -275,15 +278,15 @@ mod tests {
     #[test]
     fn test_check_for_errors() {
         let mut world = World::new();
-        world.insert_resource(Events::<EntityNotFoundEvent>::default());
+        world.insert_resource(Events::<UpdateErrorEvent>::default());
 
         // Test with no errors
         let result = check_for_errors(&world, "test_id");
         assert!(result.is_ok());
 
         // Add an error event
-        let mut error_events = world.resource_mut::<Events<EntityNotFoundEvent>>();
-        error_events.send(EntityNotFoundEvent {
+        let mut error_events = world.resource_mut::<Events<UpdateErrorEvent>>();
+        error_events.send(UpdateErrorEvent {
             id: "test_id".to_string(),
             error_type: ErrorType::EntityNotFound,
             component_type: "MockComponent".to_string(),